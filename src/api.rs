@@ -0,0 +1,90 @@
+//! A listing of the generated public surface for detecting breaking changes when a protocol is regenerated.
+//!
+//! Under the `#![api_snapshot]` module flag each item is listed in `API_ITEMS` and the listing is hashed into
+//! `API_HASH`. Items are listed in specification order, written independently of how `quote` spaces tokens, so that the
+//! listing and hash only change when the generated API does.
+
+use std::collections::HashMap;
+use crate::{Binding, binding_of, events_trait, normalise_entry_name, requests_trait};
+use crate::names::camel_case;
+use crate::protocol::*;
+
+pub fn items(interfaces: &[&Interface], enum_interfaces: &[(&Interface, Vec<&Enum>)], bindings: &HashMap<String, Binding>) -> Vec<String> {
+    let mut items = Vec::new();
+    for interface in interfaces {
+        let trait_name = camel_case(&interface.name);
+        let binding = &bindings[&trait_name];
+        items.push(format!("trait {}", trait_name));
+        let (requests_trait, events_trait) = (requests_trait(interface, binding), events_trait(interface, binding));
+        if binding.options.split_traits {
+            items.push(format!("trait {}", requests_trait));
+            items.push(format!("trait {}", events_trait));
+        }
+        // Requests must be implemented unless they are provided by `default_error`, so adding one is breaking
+        let kind = if binding.options.default_error { "provided request" } else { "request" };
+        for request in &interface.requests {
+            let context = binding.context_name().map(|context| format!("context: &mut {}", context));
+            let parameters = context.into_iter()
+                .chain(request.args.iter().map(|arg| format!("{}: {}", arg.parameter(), request_type(arg, &interface.name, bindings))))
+                .collect::<Vec<_>>();
+            items.push(format!("{} {}::{}({})", kind, requests_trait, binding.method_name(&request.name), parameters.join(", ")));
+        }
+        for event in &interface.events {
+            let parameters = event.args.iter()
+                .map(|arg| format!("{}: {}", arg.parameter(), event_type(arg)))
+                .collect::<Vec<_>>();
+            items.push(format!("event {}::{}({})", events_trait, binding.method_name(&event.name), parameters.join(", ")));
+        }
+    }
+    for (interface, enums) in enum_interfaces {
+        for e in enums {
+            let type_name = format!("{}{}", camel_case(&interface.name), camel_case(&e.name));
+            items.push(format!("enum {}", type_name));
+            for entry in &e.entries {
+                items.push(format!("const {}::{} = {}", type_name, normalise_entry_name(e, &entry.name), entry.value));
+            }
+        }
+    }
+    items
+}
+
+/// A 64-bit FNV-1a hash of the listing in hexadecimal
+pub fn hash(items: &[String]) -> String {
+    format!("{:016x}", fnv1a(&items.join("\n")))
+}
+
+fn request_type(arg: &Arg, owning_interface: &str, bindings: &HashMap<String, Binding>) -> String {
+    match (arg.kind, &arg.interface) {
+        (DataType::Object, Some(interface)) if !arg.is_loose(owning_interface, bindings) => {
+            let lease = format!("Lease<{}>", binding_of(bindings, interface).map(Binding::implementation_type_name).unwrap_or_default());
+            let lease = if arg.nullable {
+                format!("Nullable<{}>", lease)
+            } else {
+                lease
+            };
+            if arg.allow_self {
+                format!("ReceiverOr<{}>", lease)
+            } else {
+                lease
+            }
+        },
+        (DataType::Object, _) if arg.nullable => "Nullable<Lease<dyn Any>>".into(),
+        (DataType::Object, _) => "Lease<dyn Any>".into(),
+        (DataType::Fixed, _) if bindings[&camel_case(owning_interface)].options.fixed_as_f64 => "f64".into(),
+        (DataType::Array, _) if bindings[&camel_case(owning_interface)].options.borrowed_args => "&[u8]".into(),
+        _ => arg.signature_type()
+    }
+}
+fn event_type(arg: &Arg) -> &'static str {
+    match arg.kind {
+        DataType::Int => "i32",
+        DataType::Uint => "u32",
+        DataType::Fixed => "Fixed",
+        DataType::String => "&str",
+        DataType::Array => "Array",
+        DataType::Fd => "&File",
+        DataType::Object if arg.nullable => "Nullable<&dyn Object>",
+        DataType::Object => "&dyn Object",
+        DataType::NewId => "NewId"
+    }
+}
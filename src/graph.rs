@@ -0,0 +1,186 @@
+//! The graph of interfaces depending on each other through the object and `new_id` arguments of their requests.
+//!
+//! Interfaces may depend on each other in cycles, as `wl_a` creating `wl_b` which creates `wl_a` again is legitimate,
+//! so the graph is handled by its strongly connected components. The roots of the graph are the first interface of each
+//! component that no interface outside of it depends on, and dependencies are described as a chain from a root.
+
+use crate::protocol::*;
+
+/// Interfaces as nodes, with an edge from each to the interfaces its requests take objects of or create
+pub struct Dependencies<'a> {
+    interfaces: Vec<&'a Interface>,
+    edges: Vec<Vec<usize>>
+}
+impl<'a> Dependencies<'a> {
+    /// The graph of the given interfaces, ignoring references to interfaces that are not among them
+    pub fn new(interfaces: &[&'a Interface]) -> Self {
+        let edges = interfaces.iter().map(|interface| {
+            let mut edges = Vec::new();
+            let referenced = interface.requests.iter().flat_map(|request| &request.args)
+                .filter(|arg| matches!(arg.kind, DataType::Object | DataType::NewId))
+                .filter_map(|arg| arg.interface.as_ref());
+            for name in referenced {
+                if let Some(index) = interfaces.iter().position(|interface| interface.name == *name) {
+                    if !edges.contains(&index) {
+                        edges.push(index);
+                    }
+                }
+            }
+            edges
+        }).collect();
+        Self {
+            interfaces: interfaces.to_vec(),
+            edges
+        }
+    }
+    /// The strongly connected components, as indices of interfaces that depend on each other directly or otherwise,
+    /// found by Tarjan's algorithm. Each component lists its interfaces in specification order
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        struct Search<'g> {
+            edges: &'g [Vec<usize>],
+            index: Vec<Option<usize>>,
+            low: Vec<usize>,
+            stack: Vec<usize>,
+            on_stack: Vec<bool>,
+            next: usize,
+            components: Vec<Vec<usize>>
+        }
+        impl Search<'_> {
+            fn visit(&mut self, node: usize) {
+                self.index[node] = Some(self.next);
+                self.low[node] = self.next;
+                self.next += 1;
+                self.stack.push(node);
+                self.on_stack[node] = true;
+                for &next in &self.edges[node] {
+                    match self.index[next] {
+                        None => {
+                            self.visit(next);
+                            self.low[node] = self.low[node].min(self.low[next]);
+                        },
+                        Some(index) if self.on_stack[next] => self.low[node] = self.low[node].min(index),
+                        Some(_) => ()
+                    }
+                }
+                if Some(self.low[node]) == self.index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = self.stack.pop() {
+                        self.on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break
+                        }
+                    }
+                    component.sort_unstable();
+                    self.components.push(component);
+                }
+            }
+        }
+        let nodes = self.interfaces.len();
+        let mut search = Search {
+            edges: &self.edges,
+            index: vec![None; nodes],
+            low: vec![0; nodes],
+            stack: Vec::new(),
+            on_stack: vec![false; nodes],
+            next: 0,
+            components: Vec::new()
+        };
+        for node in 0..nodes {
+            if search.index[node].is_none() {
+                search.visit(node);
+            }
+        }
+        search.components
+    }
+    /// The first interface of each component that no interface outside of the component depends on, in specification
+    /// order
+    fn roots(&self) -> Vec<usize> {
+        let components = self.components();
+        let mut component_of = vec![0; self.interfaces.len()];
+        for (component, members) in components.iter().enumerate() {
+            for &member in members {
+                component_of[member] = component;
+            }
+        }
+        let mut roots: Vec<usize> = components.iter().enumerate()
+            .filter(|(component, _)| !self.edges.iter().enumerate().any(|(from, edges)| component_of[from] != *component && edges.iter().any(|&to| component_of[to] == *component)))
+            .map(|(_, members)| members[0])
+            .collect();
+        roots.sort_unstable();
+        roots
+    }
+    /// Every interface, each root followed by the interfaces it depends on depth first, so that related interfaces are
+    /// listed together
+    pub fn order(&self) -> Vec<&'a Interface> {
+        fn visit(node: usize, edges: &[Vec<usize>], visited: &mut Vec<bool>, order: &mut Vec<usize>) {
+            if visited[node] {
+                return
+            }
+            visited[node] = true;
+            order.push(node);
+            for &next in &edges[node] {
+                visit(next, edges, visited, order);
+            }
+        }
+        let mut visited = vec![false; self.interfaces.len()];
+        let mut order = Vec::new();
+        for root in self.roots().into_iter().chain(0..self.interfaces.len()) {
+            visit(root, &self.edges, &mut visited, &mut order);
+        }
+        order.into_iter().map(|node| self.interfaces[node]).collect()
+    }
+    /// The names of the shortest chain of interfaces from a root to the named interface, each depending on the next
+    pub fn chain(&self, to: &str) -> Vec<&'a str> {
+        let target = match self.interfaces.iter().position(|interface| interface.name == to) {
+            Some(target) => target,
+            None => return Vec::new()
+        };
+        // Breadth first from every root at once, so that the chain found is the shortest
+        let mut previous: Vec<Option<usize>> = vec![None; self.interfaces.len()];
+        let mut visited = vec![false; self.interfaces.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for root in self.roots() {
+            visited[root] = true;
+            queue.push_back(root);
+        }
+        while let Some(node) = queue.pop_front() {
+            if node == target {
+                break
+            }
+            for &next in &self.edges[node] {
+                if !visited[next] {
+                    visited[next] = true;
+                    previous[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut chain = vec![self.interfaces[target].name.as_str()];
+        let mut node = target;
+        while let Some(before) = previous[node] {
+            chain.insert(0, &self.interfaces[before].name);
+            node = before;
+        }
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle_components() {
+        let protocol = Protocol::load("tests/dependencies.toml").unwrap();
+        let interfaces: Vec<&Interface> = protocol.interfaces.iter().collect();
+        let dependencies = Dependencies::new(&interfaces);
+        let mut components = dependencies.components();
+        components.sort();
+        // ext_a, ext_b and ext_c create each other in a cycle, listed around the unrelated ext_e
+        assert_eq!(components, [vec![0, 2, 3], vec![1], vec![4]]);
+        let order: Vec<&str> = dependencies.order().iter().map(|interface| interface.name.as_str()).collect();
+        assert_eq!(order, ["ext_a", "ext_b", "ext_c", "ext_d", "ext_e"]);
+        assert_eq!(dependencies.chain("ext_d"), ["ext_a", "ext_b", "ext_c", "ext_d"]);
+    }
+}
@@ -1,18 +1,395 @@
-use std::collections::HashMap;
+//! Generates Wayland server bindings from protocol specifications with [`server_protocol`], and checks implementations
+//! of the generated traits against them with [`dispatch`].
+//!
+//! # Specifications
+//!
+//! A specification may list files holding further `[[interface]]` tables with `include = ["interfaces/wl_surface.toml",
+//! ...]`, relative to the including file's directory. Included files may include others in turn, while a file including
+//! itself, directly or otherwise, or an interface defined by two files, is an error naming the files involved. The
+//! module is rebuilt when any specification file it was generated from changes, included files among them.
+//!
+//! Specifications are written in TOML, or with the `json` and `yaml` features of this crate in JSON or YAML, chosen by
+//! the file's extension: `.json`, `.yaml` or `.yml`. Each format holds the same tables and fields, and files of
+//! different formats may include each other. Only TOML records which enum values are written in hexadecimal, so those of
+//! other formats are documented in decimal.
+//!
+//! Fields added to the specification format beyond those of the upstream XML format, such as `include`, `trimmed` or
+//! `max_len`, belong to later schemas. A file using them declares the schema it was written for with a top-level `schema = 2`,
+//! so that a version of the macro which does not support that schema fails to load it rather than ignoring the fields.
+//! Using a field introduced after the declared schema is an error, while a file declaring no schema is read as the
+//! first and listed in `PROTOCOL_LINTS` with the schema it should declare.
+//!
+//! Large protocols may be narrowed before generation with `only = ["interface", ...]` and/or
+//! `exclude = ["interface", ...]` following the path, e.g. `#[server_protocol("viewporter.toml", only = ["wp_viewporter"])]`.
+//!
+//! Interfaces may declare `expected_requests` and `expected_events` counts in the specification. A specification whose
+//! interface lists a different number fails to load, catching requests and events listed under each other's sections.
+//! An interface listing more than 1024 requests or events is rejected regardless, well within the 16-bit wire opcode.
+//!
+//! A request, event, argument, enum or entry introduced in a version after its interface's version also fails to load.
+//! The reverse, an interface whose later versions introduce nothing, is listed in `PROTOCOL_LINTS` with the versions
+//! that appear to be missing messages, as a trimmed specification would advertise requests that are not dispatched.
+//! Interfaces sharing versions with those they create objects of, such as `wl_keyboard` with `wl_seat`, count changes
+//! to either. An interface may be marked `trimmed = true` where messages were removed on purpose.
+//!
+//! Trailing request arguments appended in a later version may declare `since` and a `default` in the specification,
+//! which requires `#![runtime(short_messages)]`. When a client sends the shorter message of an earlier version, the
+//! missing arguments take their default, or `Nullable::Null` for nullable objects.
+//!
+//! Event arguments may declare `from_field = "field"` to be sent from that field of the implementation by a generated
+//! `send_<event>` method, taking any other arguments as parameters. The methods belong to a trait such as
+//! `WlOutputFieldEvents` implemented for the binding.
+//!
+//! Interfaces marked `deprecated = true` in the specification, optionally with a `deprecated_reason`, generate a
+//! deprecated trait, and binding them warns at the binding.
+//!
+//! Interfaces, requests, events and enums in the specification may list `attrs = ["#[my_audit::sensitive]"]`, outer
+//! attributes written as in source that are added to the generated trait, trait methods or enum type, e.g. for tools
+//! reading custom attributes. Only attributes are accepted, and a string that is not one is an error naming the item.
+//! `cfg`, `cfg_attr` and `derive` attributes are allowed, but an item configured out must not be used by the rest of
+//! the generated code: a request method removed by `cfg` leaves its dispatch without a handler.
+//!
+//! With `lock = "wayland.lock.toml"` the wire format of the protocol is written to the lock file on the first build.
+//! Later builds report each incompatible change against it, such as a reordered request, a changed argument type or
+//! a removed enum entry, while new trailing messages and enum entries are accepted. Argument names are locked along with
+//! their types, so that arguments of the same type swapping places, such as two `uint` serials, are reported even though
+//! the signature is unchanged. Once reviewed, building with the `WL_MACRO_UPDATE_LOCK` environment variable set rewrites
+//! the lock.
+//!
+//! # Generated code
+//!
+//! Generated code only uses syntax and paths that every edition reads alike, so modules expand in 2015 crates as in
+//! later ones, as the crates of `tests/editions` check by expanding the core protocol with every runtime option in a
+//! 2015 and a 2021 crate. In a 2015 crate `::wl` names an item at the crate's root, such as `extern crate wl;`, and a
+//! runtime available only under another name is given with `crate = path`. Protocol names that are keywords in any
+//! edition, such as `gen`, are generated as raw identifiers.
+//!
+//! Setting `WL_MACRO_DUMP_INTERFACE` while building appends the interface an error is about to the error, as the macro
+//! sees it after merging and normalisation: its requests and events by opcode with their argument types, and its enums
+//! with the generated type and constant names.
+//!
+//! Requests and events are traced with their arguments while `wl::DEBUG` is set, labelled by name while
+//! `wl::DEBUG_VERBOSE` is also set, as in `wl_surface@3.attach(buffer: 5, x: 0, y: 0)`. The labels are the parameter
+//! names of the handlers without their `wl_` prefix. For framing problems, modules declaring `#![runtime(wire_dumps)]`
+//! also dump each message while `wl::DEBUG_WIRE` is set, requests before they are decoded and events once encoded, as a
+//! header line followed by its bytes in rows of 16 with an ASCII gutter.
+//!
+//! Each interface also has a trait such as `AsWlSurface`, implemented for the binding and its lease, with `as_wl_surface`
+//! and `as_wl_surface_mut` methods borrowing the implementation. Helpers written as `fn damage(s: &mut impl AsWlSurface)`
+//! then accept a request's `Lease<Surface>` argument as well as a `Surface` held elsewhere.
+//!
+//! The module is documented with an index of each protocol's interfaces, their versions and summaries, noting whether
+//! each is implemented by a binding, bound externally, left unbound or excluded, and linking to the generated traits.
+//! Each trait's documentation ends with an "Implementation" section linking to the type bound to it in this build, and
+//! each `Dispatch` implementation names the trait it dispatches to.
+//!
+//! Each interface also has a metadata module, e.g. `wl_surface`, with introspection tables and an `encode_<message>`
+//! function per request and event building its `::wl::Message` without a client, for recording and replaying traffic.
+//!
+//! Each metadata module's `SIGNATURE_HASH` is a hash of the interface's wire format, so that code loaded separately,
+//! such as a compositor plugin, can check it was built against the same protocol as its host. It covers the interface's
+//! name and version and, for each request and event in opcode order, the version it was introduced in and each
+//! argument's type, interface, nullability and `since`. Names, documentation, enums and the module's bindings do not
+//! affect it. The module's own `SIGNATURE_HASH` combines those of every interface in the specification, bound or not.
+//!
+//! Each message's introspection entry gives its `min_size` on the wire, with strings and arrays empty, and its
+//! `fixed_size` where none of its arguments vary in length. Every argument takes a word besides file descriptors, which
+//! are sent alongside the message, and a `new_id` of no particular interface, which is preceded by the interface name
+//! and version. `LARGEST_FIXED_MESSAGE` is the largest `min_size` of an interface. With `#![runtime(message_size)]`,
+//! event encoders check in debug builds that the message built is no smaller than its `min_size`.
+//!
+//! Requests annotated `double_buffered = true` in the specification, which set pending state, and `latching = true`,
+//! which apply it, such as `wl_surface.attach` and `wl_surface.commit`, are marked so in their introspection entries.
+//! The metadata module's `const fn is_double_buffered(opcode)`, e.g. `wl_surface::is_double_buffered`, answers the
+//! first for an opcode. It is not an associated function of the interface trait, as stable Rust has no `const fn` in
+//! traits.
+//!
+//! Interfaces with an argumentless `frame` event, such as `wl_pointer`, have a `begin_frame` method returning a guard
+//! such as `WlPointerFrame` with a method per other event. The collected events are sent together followed by `frame`
+//! when the guard is finished or dropped, so a frame cannot be left unterminated.
+//!
+//! Events introduced after the first version also have a `try_` method, e.g. `output.try_name(client, "DP-1")?`, that
+//! sends the event only if the object was bound at the event's version or later. It returns `Ok(false)` for older
+//! clients rather than sending them an event they do not know, so call sites need no version check. The bound version
+//! is read through the generated `BoundVersion` trait, implemented for leases by `Lease::version` under
+//! `#![runtime(lease_version)]`. Without it, implement the trait for the bindings' leases.
+//!
+//! Events without object arguments can also be broadcast with a generated function per event, e.g.
+//! `broadcast_wl_output_scale(clients, |client| outputs.get(client), 2)`. The closure picks the object receiving the
+//! event in each client, or `None` to skip it. The event is sent to every other client, and the failures are collected
+//! into a `BroadcastError` naming each client by its position, leaving out clients that have disconnected under
+//! `#![runtime(disconnects)]`.
+//!
+//! The generated module asserts at compile time that the runtime's `Fixed` is a single 4 byte word, that `Message`
+//! opcodes are `u16`, that `new_id` arguments are read from and pushed to messages as the runtime's `NewId`, and that
+//! file descriptors are received from the client and pushed to messages as `std::fs::File`. The generated code depends
+//! on each to match the wire format, so a runtime changing one fails to build rather than corrupting messages.
+//!
+//! With the `json` feature, setting `WL_MACRO_MANIFEST` to a directory at compile time writes a JSON description of the
+//! protocol, including opcodes, generated names and bindings, to `<protocol>.json` in that directory for use by
+//! external tooling.
+//!
+//! The generated `negotiate(interface, requested)` function returns the version a global should be bound at, the lower
+//! of the version a client requests in `wl_registry.bind` and the version the interface's binding supports, or `None`
+//! for an interface the module does not bind.
+//!
+//! Code creating the object of a `new_id` argument constructs its implementation with the generated `Construct` trait,
+//! as in `Surface::construct(client, wl_id)`. It is implemented for every implementation that is `Default` and for
+//! each binding with an `init` function, which must then not also implement `Default`.
+//!
+//! With `#![runtime(create)]`, the generated `create(client, id, value)` function then inserts the implementation into the client as the object
+//! `id` creates and returns its lease, so that initial events may be sent on it right away, as in
+//! `let surface = wayland::create(client, wl_id, Surface::new())?;`. `create_new(client, id)` does the same with the
+//! implementation built by `Construct`, as in `let surface: Lease<Surface> = wayland::create_new(client, wl_id)?;`.
+//! Requests receive the `NewId` of the objects they create rather than creating them during dispatch, so these are
+//! the generated code constructing new objects. They accept only the implementations of this module's bindings, as
+//! listed by the sealed `Implementation` trait, and are not generated under `delegate`.
+//!
+//! Generated code refers to its own items through `self::` and to the standard library through `::std::`, so types,
+//! traits and macros glob imported into the module do not change its meaning. The module itself must not share a name
+//! with an item generated inside it, as a module `wl_surface` holding the `wl_surface` metadata module would, which is
+//! reported naming the items it collides with.
+//!
+//! The generated code replaces the module's contents rather than nesting another module, so `self::` and `super::`
+//! paths in bindings, including `context` and `init` paths, mean what they would for any other item written in the
+//! module: `self::Surface` is an item of the module and `super::Surface` one of the module containing it. Where
+//! generated code refers to an implementation from a nested module, such as `mock`, relative paths are rewritten to
+//! reach the same item, while `crate::` and `::` paths are used unchanged.
+//!
+//! Other items in the module, such as helper functions or `impl` blocks for generated enums, are kept as written after
+//! the generated code. As bindings are written as `type` and `use ... as` items, such items are always bindings.
+//!
+//! # Enums
+//!
+//! Enums referenced by arguments are generated along with the interfaces, including those of unbound interfaces and,
+//! with dotted names such as `enum = "wl_output.transform"`, those of interfaces from another merged protocol. Where the
+//! enum is instead generated by another module, it may be aliased by the name it would have been generated with, e.g.
+//! `use crate::core::WlOutputTransform as WlOutputTransform;`, to re-export the existing type from the module and its
+//! prelude in place of a duplicate. The enum's interface must not be bound in the module.
+//!
+//! Enum entries are constants of the enum's type named in `SHOUTY_SNAKE_CASE`, so that they may be matched as patterns
+//! against a value converted with `new`, as in `match WlShmFormat::new(format)? { WlShmFormat::ARGB8888 => ..., other
+//! => ... }`. The `raw` module holds the same entries as integers, one module per enum, for matching the integers that
+//! arguments are received as: `match format { raw::wl_shm_format::ARGB8888 => ..., _ => ... }`. Entries not beginning
+//! with a letter are prefixed with the enum's name, so the `90` entry of `wl_output.transform` is
+//! `WlOutputTransform::TRANSFORM_90`. Entries whose names collide, such as `90` and `transform_90`, are reported as
+//! errors. Earlier versions prefixed the interface's name instead, as in `WL_OUTPUT_90`; such constants must be renamed,
+//! or kept for now with `#![legacy_entry_names]`.
+//!
+//! An enum mirroring another may name it with `maps_to = "interface.enum"` in the specification, or just the enum's name
+//! within the same interface. Conversions between the two generated types are implemented in both directions, mapping
+//! entries by name: `From` where every entry of the source has a counterpart, otherwise `TryFrom` returning the
+//! unmapped value. Each impl is documented with a table of the entry each source entry becomes. `From` never panics: a
+//! value that is no entry of the source, which only code in the module can build, keeps its value. Naming an enum that
+//! does not exist or that has none of the same entries is an error, as is mapping from an enum this module generates
+//! to one it does not.
+//!
+//! Enums named `error`, and others marked `is_error = true`, hold protocol error codes. They implement `Display`, by
+//! each entry's summary, and `std::error::Error`. Under `#![runtime(protocol_errors)]` a handler may return
+//! `Err(WlSurfaceError::ROLE.protocol_error("...").into())`, which the generated `report` function posts as
+//! `wl_display.error` on the object the request was sent to.
+//!
+//! # Request validation
+//!
+//! With `#![runtime(argument_limits)]`, string and array arguments may declare a `max_len` in bytes, as sent including a
+//! string's terminating nul. Longer arguments are rejected with `DispatchError::ArgumentTooLong` before they are
+//! decoded. With `#![runtime(argument_ranges)]`, fixed arguments may likewise declare an inclusive
+//! `range = [0.0, 16384.0]`, rejecting values outside of it with `DispatchError::ArgumentOutOfRange`.
+//! With `#![runtime(malformed_strings)]`, string arguments the runtime cannot decode are rejected with
+//! `DispatchError::MalformedString`, naming the request and argument along with the runtime's error.
+//!
+//! Strings are sent nul-terminated, so a string event argument containing a nul would be cut short by the client. With
+//! `#![runtime(interior_nul)]`, such events are rejected with `DispatchError::InteriorNul` naming the event and argument before anything is written: the
+//! event method returns the error, while the `_into` methods record it in the batch, failing `EventBatch::send` so
+//! that none of the batch is sent. Broadcasts report it as a failure for each client.
+//!
+//! Requests of privileged protocols, such as screen capture, may declare `privileged = true` in the specification, or
+//! their interface may to cover all of its requests. They are only dispatched for clients accepted by the predicate the
+//! module declares with `#![privileged(path)]`, a `fn(&Client) -> bool`, and are otherwise rejected with
+//! `DispatchError::AccessDenied` naming the request, without calling the handler or decoding any arguments. The denied
+//! request's file descriptors are left with the client, which is disconnected once `report` posts the error. A module
+//! binding a privileged request without declaring a predicate and `#![runtime(access_denied)]` fails to compile.
+//!
+//! The object receiving a request is already leased as `self`, so under `#![runtime(duplicate_objects)]` an object
+//! argument of the same interface naming it is rejected with `DispatchError::DuplicateObject`. Arguments of the receiver's interface that may name it declare `allow_self = true`
+//! and are received as `ReceiverOr<Lease<_>>`, which is `ReceiverOr::Receiver` in that case.
+//!
+//! # Module flags
+//!
+//! Flags may be set as inner attributes of the module:
+//! - `#![testing]`: generate a `mock` module with a function per request that encodes the request and dispatches it,
+//!   for exercising handlers through the generated argument decoding
+//! - `#![loose_objects]`: set the `loose_objects` option on every binding
+//! - `#![deny_lints]`: make protocol lints, otherwise only listed in `PROTOCOL_LINTS`, compile errors
+//! - `#![roundtrip_tests]`: generate a test per event that encodes representative argument values and decodes them with
+//!   the request argument getters, checking the runtime's wire format from the consuming crate
+//! - `#![api_snapshot]`: generate `API_ITEMS` listing the generated traits, methods and enum entries, their hash
+//!   `API_HASH`, and `check_api_snapshot` comparing the listing against a checked-in snapshot from a test, so that
+//!   regenerating from a changed protocol reports the items added and removed
+//! - `#![max_arg_len = 4096]`: limit the length of string and array request arguments without their own `max_len`,
+//!   under `#![runtime(argument_limits)]`
+//! - `#![no_debug]`: omit the code tracing messages under `wl::DEBUG` and dumping them under `wl::DEBUG_WIRE`
+//! - `#![docs(summaries_only)]`: document generated items with the one-line summaries of the specification only,
+//!   dropping the long descriptions, to reduce the size of documentation and metadata
+//! - `#![docs(examples)]`: document request and event methods with an example call, e.g.
+//!   `surface.attach(client, Nullable::Object(buffer), 0, 0)?;`, passing objects as variables named after the argument,
+//!   numbers as `0` and enum arguments as the enum's first entry. Options may be combined, as in
+//!   `#![docs(summaries_only, examples)]`
+//! - `#![legacy_entry_names]`: also generate the former constants of entries not beginning with a letter, such as
+//!   `WlOutputTransform::WL_OUTPUT_90`, as deprecated aliases while code migrates to the new names
+//! - `#![stats]`: count the requests dispatched and events sent by each interface in atomics of its metadata module,
+//!   e.g. `wl_surface::REQUESTS_DISPATCHED`, copied out with message names by the generated `stats()` function
+//! - `#![fixed(as_f64)]`: receive fixed request arguments as `f64`, converted while decoding, rather than `Fixed`
+//! - `#![borrowed_args]`: receive array request arguments as `&[u8]` borrowed from the message rather than as an owned
+//!   `Array`, so that dispatching requests such as key lists allocates nothing for them. Handlers that keep the bytes
+//!   must copy them
+//! - `#![delegate(path)]`: dispatch requests through a generated `dispatch_wl_surface(client, object, message)`
+//!   function per interface rather than an impl of `Dispatch`. The function calls `path::<T>(client, object)` to
+//!   receive the object, so the binding may be held in an `Rc<RefCell<T>>`, arena or other container the runtime knows
+//!   nothing of. The receiver returned must implement the interface trait and `Object`, and must not borrow the client.
+//!   Objects passed as request arguments are still leased from the client. Without `Dispatch` impls, objects of typed
+//!   `new_id` arguments are created at the version `negotiate` gives their interface, or under
+//!   `#![runtime(lease_version)]` at the version returned by the receiver's `version` method
+//! - `#![doc_all]`: also generate the traits and enums of interfaces without a binding, under `#[cfg(doc)]` so that
+//!   they appear in the crate's documentation but not in other builds. This suits crates publishing a protocol for
+//!   others to implement. Only bound interfaces get `Dispatch` impls
+//! - `#![split_traits]`: generate the request handlers and event senders of each interface as separate traits, e.g.
+//!   `WlPointerRequests` and `WlPointerEvents`, with `WlPointer` implemented for any type implementing both. Only the
+//!   binding must implement the requests trait, so the events trait may also be implemented by a lightweight handle
+//!   that can send events but never sees requests
+//! - `#![privileged(path)]`: dispatch requests declared `privileged` only for clients for which `path(client)` returns
+//!   `true`
+//! - `#![unchecked_strings]`: send string event arguments without checking them for nuls even under
+//!   `#![runtime(interior_nul)]`, for servers that only send strings known to be free of them
+//! - `#![skip_dead_clients]`: return `Ok(())` from event methods, `EventBatch::send` and frame guards without encoding
+//!   or sending anything once `Client::is_dead` reports that the runtime has marked the client for disconnection, such
+//!   as after posting a protocol error, and skip such clients in `broadcast_` functions. The events dropped are counted
+//!   in the client's data, returned by `suppressed_events(client)` and traced under `wl::DEBUG` in a single line once
+//!   the client is dropped, rather than as a failure per event. Requires `#![runtime(dead_clients, client_data)]`.
+//!   Without the flag every event is sent, or fails, as usual
+//!
+//! # Runtime options
+//!
+//! The generated code uses runtime APIs beyond sending messages only once they are listed in `#![runtime(...)]`, an inner
+//! attribute of the module, and options may be combined, as in `#![runtime(send_all, lease_version)]`. Specification
+//! fields and bindings relying on an option fail to compile without it.
+//!
+//! - `#![runtime(send_all)]`: send the events collected by an `EventBatch`, as the `_into` event methods and frame guards
+//!   do, in one write with `Client::send_all` rather than with a `Client::send` each
+//! - `#![runtime(lease_version)]`: create the objects of typed `new_id` arguments at the version the object receiving
+//!   the request was bound at, read with `Lease::version`, as Wayland requires of child objects. Without it they are
+//!   created at the version the child's binding supports, `Dispatch::VERSION`. `BoundVersion` is also implemented
+//!   for leases
+//! - `#![runtime(destroyed_objects)]`: mark objects destroyed with `Client::mark_destroyed` once a destructor request
+//!   has been handled and `delete_id` sent, and ignore requests sent to them after that, as reported by
+//!   `Client::is_destroyed`, rather than dispatching them. A client may send such requests before it has processed
+//!   `delete_id`. The ignored requests are traced under `wl::DEBUG`
+//! - `#![runtime(wire_dumps)]`: dump the bytes of each message, encoded with `Message::to_bytes`, while
+//!   `wl::DEBUG_WIRE` is set
+//! - `#![runtime(message_size)]`: check in debug builds that each event encoded is no smaller than its `min_size`,
+//!   measured with `Message::size`
+//! - `#![runtime(flush)]`: call `Client::flush` once an event ending an exchange has been sent
+//! - `#![runtime(disconnects)]`: report events failing to send to clients that have hung up as
+//!   `DispatchError::Disconnected`, and skip those clients in `broadcast_` functions
+//! - `#![runtime(interior_nul)]`: reject events with string arguments containing a nul with
+//!   `DispatchError::InteriorNul` before encoding them
+//! - `#![runtime(create)]`: generate the `create` and `create_new` functions, which insert objects with `Client::create`
+//! - `#![runtime(malformed_strings)]`: reject string request arguments the runtime cannot decode with
+//!   `DispatchError::MalformedString` naming the request and argument
+//! - `#![runtime(duplicate_objects)]`: reject requests naming one object in several arguments with
+//!   `DispatchError::DuplicateObject` before leasing any of them
+//! - `#![runtime(argument_limits)]`: reject string and array arguments longer than their `max_len` or `max_arg_len`
+//!   with `DispatchError::ArgumentTooLong`, read with `Args::peek_u32` before decoding them. Required by both limits
+//! - `#![runtime(argument_ranges)]`: reject fixed arguments outside of their `range` with
+//!   `DispatchError::ArgumentOutOfRange`. Required by specifications declaring ranges
+//! - `#![runtime(short_messages)]`: give since-gated arguments their default once `Args::is_empty` reports that an
+//!   older client's message has ended. Required by specifications declaring such arguments
+//! - `#![runtime(access_denied)]`: reject privileged requests of clients refused by the `privileged` predicate with
+//!   `DispatchError::AccessDenied`. Required by modules binding privileged requests
+//! - `#![runtime(protocol_errors)]`: generate `protocol_error` on error enums, returning `DispatchError::Protocol`
+//! - `#![runtime(client_data)]`: take the context of `context<Type>` bindings from the client with `Client::take_data`
+//!   for each request and return it with `Client::put_data` once handled, whether or not the handler failed. Required
+//!   by modules with such bindings, and by `#![skip_dead_clients]` to count the events dropped
+//! - `#![runtime(dead_clients)]`: check `Client::is_dead` before sending events under `#![skip_dead_clients]`, which
+//!   requires it
+//!
+//! With `#![runtime(flush)]`, events that end an exchange call `client.flush()` once sent so batched output does not
+//! stall behind them. These are events named `done` or `frame` and every event of an interface without requests, such as `wl_callback`, while
+//! any event may set `flush = true` or `flush = false` in the specification to override it.
+//!
+//! With `#![runtime(disconnects)]`, event methods and `EventBatch::send` return `wl::DispatchError::Disconnected` when
+//! sending fails after the client has hung up, as reported by `Client::is_disconnected`, so code broadcasting to many
+//! clients can skip the client and continue. Other failures are returned unchanged.
+//!
+//! # Bindings
+//!
+//! Bindings are matched to interfaces by CamelCase name. Where interfaces such as `zwp_tablet_v2` and `zwp_tablet_v_2`
+//! share a CamelCase name, the binding must name the interface it implements with an attribute, as in
+//! `#[interface = "zwp_tablet_v2"] type ZwpTabletV2 = crate::Tablet;`, and the other interface is left unbound.
+//!
+//! Implementation paths are resolved inside the generated module, where the generated traits and enums shadow types of
+//! the same name outside it, so such types are written as `crate::WlDisplay` rather than `WlDisplay`.
+//!
+//! Every interface a bound interface's requests take objects of or create must be bound as well. A missing binding is
+//! reported once for each interface depending on it, with the chain of bound interfaces leading to it from one that
+//! nothing else depends on, e.g. `ext_a → ext_b → ext_c → ext_d`. Interfaces creating each other in a cycle are
+//! allowed. The module index and the generated traits list each interface followed by those it depends on.
+//!
+//! An interface whose implementation is chosen at runtime may be bound to a trait, e.g.
+//! `type ZwlrLayerSurfaceV1 = dyn crate::LayerSurface;`. `Dispatch` is then implemented for `Box<dyn crate::LayerSurface>`,
+//! the interface trait is implemented for `Lease<Box<dyn crate::LayerSurface>>` and object arguments of the interface
+//! are received with that type.
+//!
+//! Bindings may be followed by options in parentheses, e.g. `type WlShell = ShellStub (default_error);`
+//! - `default_error`: requests become provided methods posting an `invalid_method` protocol error, so only supported
+//!   requests need to be implemented. The error is posted as `post_error` does where `wl_display` is bound, and is
+//!   otherwise encoded and sent as the display's `error` event method would, traced and checked alike
+//! - `loose_objects`: object arguments referencing an interface without a binding, such as one from another protocol,
+//!   are received as `Lease<dyn Any>` to be downcast by the implementation instead of being a compile error. The
+//!   generated `interface_of(&lease)` names the interface of such an object where it is bound by this module
+//! - `context<Type>`: requests take a `context: &mut Type` parameter following `client`, taken from the client's data
+//!   for the duration of the call, e.g. `type WlSurface = Surface (context<crate::Compositor>);`. Requires
+//!   `#![runtime(client_data)]`
+//! - `init<path>`: the implementation is constructed for new objects by the named function, taking
+//!   `(&mut Client, NewId)`, rather than with `Default`, e.g. `type WlSurface = Surface (init<crate::make_surface>);`
+//! - `rename<message = method, ...>`: name the trait methods of the listed requests and events differently, e.g.
+//!   `type WpFoo = crate::Foo (rename<r#impl = impl_request, commit = wp_commit>);` to avoid a keyword or a method the
+//!   implementation already has. Keywords are written as raw identifiers. Renamed methods keep the protocol's name as a
+//!   documentation alias, and naming a request or event the interface does not have is an error
+//! - `version<N>`: the implementation supports the interface up to version `N` rather than the specification's version,
+//!   e.g. `type WlSeat = crate::Seat (version<7>);`. This is the version of `Dispatch::VERSION` and `IMPLEMENTATIONS`
+use std::{collections::{BTreeMap, HashMap}, convert::TryFrom};
 
-use quote::{quote, format_ident};
-use syn::{parse_macro_input, parse::{Parse, ParseStream}, punctuated::Punctuated, LitStr, Visibility, Token, Ident, Path, braced, spanned::Spanned};
-use proc_macro2::TokenStream;
-
-use heck::{CamelCase, SnakeCase, ShoutySnakeCase};
+use quote::{quote, quote_spanned, format_ident};
+use syn::{parse_macro_input, parse::{Parse, ParseStream, Parser}, Attribute, Item, ItemImpl, Lit, LitInt, LitStr, Meta, MetaNameValue, NestedMeta, ext::IdentExt, Visibility, Token, Ident, Path, braced, bracketed, parenthesized, token, spanned::Spanned};
+use proc_macro2::{Span, TokenStream};
 
+mod api;
+mod graph;
+mod lints;
+mod lock;
+mod manifest;
+mod names;
 mod protocol;
+#[cfg(test)]
+mod tests;
+use graph::Dependencies;
+use lock::Lock;
+use manifest::Manifest;
+use names::*;
 use protocol::*;
 
 struct ProtocolModule {
     visibility: Visibility,
     ident: Ident,
-    bindings: HashMap<String, Binding>
+    /// Inner attributes that are not module flags, passed through to the generated module
+    attributes: Vec<Attribute>,
+    options: ModuleOptions,
+    bindings: HashMap<String, Binding>,
+    /// Implementations that may have been meant as bindings, checked against the protocol during expansion
+    impl_bindings: Vec<ImplBinding>,
+    /// Ordinary items that are not bindings, emitted after the generated code
+    items: Vec<Item>
 }
 impl Parse for ProtocolModule {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -21,328 +398,3770 @@ impl Parse for ProtocolModule {
         let ident = input.parse()?;
         let content;
         let _ = braced!(content in input);
+        let mut options = ModuleOptions::default();
+        let mut attributes = Vec::new();
+        for attribute in content.call(Attribute::parse_inner)? {
+            if !options.parse_flag(&attribute)? {
+                attributes.push(attribute)
+            }
+        }
         let mut bindings = HashMap::new();
-        let punctuated_bindings: Punctuated<Binding, Token![;]> = content.parse_terminated(Binding::parse)?;
-        for binding in punctuated_bindings {
-            let interface = binding.interface.to_string().to_snake_case();
+        let mut parsed_bindings = Vec::new();
+        let mut impl_bindings = Vec::new();
+        let mut items = Vec::new();
+        // The trailing separator is optional. Only `;` is accepted as rustc parses the body as items before expansion,
+        // rejecting `,` after a binding before the macro runs, as `tests/ui/comma_separators.rs` shows
+        while !content.is_empty() {
+            if let Ok(impl_binding) = content.fork().parse::<ImplBinding>() {
+                if impl_binding.is_bodiless {
+                    let _: ImplBinding = content.parse()?;
+                    let _: Token![;] = content.parse()?;
+                } else {
+                    items.push(content.parse()?);
+                }
+                impl_bindings.push(impl_binding);
+                continue
+            }
+            // Anything that is not a binding is kept as an ordinary item, reporting the binding error if it is neither
+            if let Err(error) = content.fork().parse::<Binding>() {
+                match content.parse::<Item>() {
+                    Ok(item) => items.push(item),
+                    Err(_) => return Err(error)
+                }
+                continue
+            }
+            let binding: Binding = content.parse()?;
+            let end = binding.end_span();
+            parsed_bindings.push(binding);
+            if content.peek(Token![;]) {
+                let _: Token![;] = content.parse()?;
+            } else if !content.is_empty() {
+                return Err(syn::Error::new(end, "Expected `;` after this binding"))
+            }
+        }
+        for mut binding in parsed_bindings {
+            options.apply(&mut binding.options);
+            // Bindings are keyed by the CamelCase trait name they implement
+            let interface = binding.interface.to_string();
             if bindings.contains_key(&interface) {
-                panic!("Duplicate definition of interface {:?}", interface.to_camel_case());
+                return Err(syn::Error::new(binding.interface.span(), format!("Duplicate definition of interface {:?}", interface)))
             }
             bindings.insert(interface, binding);
         }
         Ok(Self {
             visibility,
             ident,
-            bindings
+            attributes,
+            options,
+            bindings,
+            impl_bindings,
+            items
         })
     }
 }
+/// Module-wide flags, written as inner attributes of the module, e.g. `#![testing]`
+#[derive(Default)]
+struct ModuleOptions {
+    /// Generate the `mock` module of helpers that dispatch requests built from their arguments
+    testing: bool,
+    /// Apply the `loose_objects` binding option to every binding
+    loose_objects: bool,
+    /// Report protocol lints as compile errors
+    deny_lints: bool,
+    /// Generate tests checking that events decode to the values they were built from
+    roundtrip_tests: bool,
+    /// The length limit of string and array request arguments without their own `max_len`
+    max_arg_len: Option<u32>,
+    /// Generate a listing and hash of the generated API with a function comparing it against a snapshot
+    api_snapshot: bool,
+    /// Omit argument tracing and wire dumps from the generated code
+    no_debug: bool,
+    /// Document generated items with their one-line summaries only, dropping long descriptions
+    summaries_only: bool,
+    /// Document request and event methods with an example call
+    doc_examples: bool,
+    /// Keep deprecated constants named as before entries beginning with a digit were prefixed by their enum name
+    legacy_entry_names: bool,
+    /// Count the requests dispatched and events sent by each interface
+    stats: bool,
+    /// Generate the request handlers and event senders of each interface as separate traits
+    split_traits: bool,
+    /// Receive fixed request arguments as `f64`
+    fixed_as_f64: bool,
+    /// Receive array request arguments as slices borrowed from the message
+    borrowed_args: bool,
+    /// Send string event arguments without checking them for interior nuls
+    unchecked_strings: bool,
+    /// Drop events for clients the runtime has marked dead rather than attempting to send them, kept as the flag's span
+    /// to report the runtime options it requires
+    skip_dead_clients: Option<Span>,
+    /// The function obtaining the receiver of requests in place of the runtime's leases
+    delegate: Option<Path>,
+    /// The predicate deciding whether a client may send privileged requests
+    privileged: Option<Path>,
+    /// Generate the traits and enums of unbound interfaces for rustdoc
+    doc_all: bool,
+    /// Runtime APIs the generated code may use beyond those of every runtime
+    runtime: RuntimeOptions
+}
+/// Runtime APIs beyond those every runtime provides, used by the generated code only once the module declares them
+/// with `#![runtime(...)]`
+#[derive(Clone, Copy, Default)]
+pub(crate) struct RuntimeOptions {
+    /// `Client::send_all`, writing many messages at once
+    send_all: bool,
+    /// `Lease::version`, the version an object was bound at
+    pub(crate) lease_version: bool,
+    /// `Client::mark_destroyed` and `Client::is_destroyed`, tracking objects destroyed until their id is reused
+    destroyed_objects: bool,
+    /// `wl::DEBUG_WIRE` and `Message::to_bytes`, dumping the wire encoding of messages
+    wire_dumps: bool,
+    /// `Message::size`, the length of an encoded message in bytes
+    message_size: bool,
+    /// `Client::flush`, writing out the events a runtime holds back
+    flush: bool,
+    /// `Client::is_disconnected` and `DispatchError::Disconnected`, telling clients that have hung up from other failures
+    disconnects: bool,
+    /// `DispatchError::InteriorNul`, rejecting string event arguments containing a nul
+    interior_nul: bool,
+    /// `Client::create`, inserting the object a `NewId` creates and returning its lease
+    create: bool,
+    /// `DispatchError::MalformedString`, naming the request and argument of strings the runtime cannot decode
+    malformed_strings: bool,
+    /// `DispatchError::DuplicateObject`, rejecting requests naming one object in several arguments
+    duplicate_objects: bool,
+    /// `Args::peek_u32` and `DispatchError::ArgumentTooLong`, rejecting strings and arrays over their length limit
+    argument_limits: bool,
+    /// `DispatchError::ArgumentOutOfRange`, rejecting fixed arguments outside of their range
+    argument_ranges: bool,
+    /// `Args::is_empty`, telling the shorter messages of older clients apart by the arguments left
+    short_messages: bool,
+    /// `DispatchError::AccessDenied`, rejecting privileged requests of clients the module's predicate refuses
+    access_denied: bool,
+    /// `DispatchError::Protocol`, carrying the errors of error enums to `report`
+    protocol_errors: bool,
+    /// `Client::take_data` and `Client::put_data`, lending the context of `context<Type>` bindings to requests
+    client_data: bool,
+    /// `Client::is_dead`, telling clients the runtime has marked for disconnection
+    dead_clients: bool
+}
+impl ModuleOptions {
+    /// Copy the flags that change how each binding is generated into its options
+    fn apply(&self, options: &mut BindingOptions) {
+        options.loose_objects |= self.loose_objects;
+        options.max_arg_len = self.max_arg_len;
+        options.no_debug = self.no_debug;
+        options.stats = self.stats;
+        options.split_traits = self.split_traits;
+        options.fixed_as_f64 = self.fixed_as_f64;
+        options.borrowed_args = self.borrowed_args;
+        options.unchecked_strings = self.unchecked_strings;
+        options.skip_dead_clients = self.skip_dead_clients.is_some();
+        options.runtime = self.runtime;
+        options.delegate = self.delegate.clone();
+        options.privileged = self.privileged.clone();
+    }
+    /// Check that the runtime options the module's flags rely on are declared
+    fn check_runtime(&self) -> syn::Result<()> {
+        if let Some(span) = self.skip_dead_clients {
+            if !self.runtime.dead_clients {
+                return Err(syn::Error::new(span, "The `skip_dead_clients` flag checks `Client::is_dead`, which requires `#![runtime(dead_clients)]`"))
+            }
+            if !self.runtime.client_data {
+                return Err(syn::Error::new(span, "The `skip_dead_clients` flag counts the events dropped in the client's data, which requires `#![runtime(client_data)]`"))
+            }
+        }
+        Ok(())
+    }
+    /// Apply the attribute if it is a module flag, returning whether it was consumed
+    fn parse_flag(&mut self, attribute: &Attribute) -> syn::Result<bool> {
+        let flag = match attribute.path.get_ident() {
+            Some(flag) => flag.to_string(),
+            None => return Ok(false)
+        };
+        if flag == "docs" {
+            let options = match attribute.parse_meta()? {
+                Meta::List(list) if !list.nested.is_empty() => list.nested,
+                meta => return Err(syn::Error::new(meta.span(), "Expected documentation options, e.g. `#![docs(summaries_only)]`"))
+            };
+            for option in options {
+                match &option {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("summaries_only") => self.summaries_only = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("examples") => self.doc_examples = true,
+                    option => return Err(syn::Error::new(option.span(), "Expected `summaries_only` or `examples`"))
+                }
+            }
+            return Ok(true)
+        }
+        if flag == "fixed" {
+            let options = match attribute.parse_meta()? {
+                Meta::List(list) if !list.nested.is_empty() => list.nested,
+                meta => return Err(syn::Error::new(meta.span(), "Expected fixed argument options, e.g. `#![fixed(as_f64)]`"))
+            };
+            for option in options {
+                match &option {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("as_f64") => self.fixed_as_f64 = true,
+                    option => return Err(syn::Error::new(option.span(), "Expected `as_f64`"))
+                }
+            }
+            return Ok(true)
+        }
+        if flag == "runtime" {
+            let options = match attribute.parse_meta()? {
+                Meta::List(list) if !list.nested.is_empty() => list.nested,
+                meta => return Err(syn::Error::new(meta.span(), "Expected the runtime APIs to use, e.g. `#![runtime(send_all)]`"))
+            };
+            for option in options {
+                match &option {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("send_all") => self.runtime.send_all = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("lease_version") => self.runtime.lease_version = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("destroyed_objects") => self.runtime.destroyed_objects = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wire_dumps") => self.runtime.wire_dumps = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("message_size") => self.runtime.message_size = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flush") => self.runtime.flush = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("disconnects") => self.runtime.disconnects = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("interior_nul") => self.runtime.interior_nul = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("create") => self.runtime.create = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("malformed_strings") => self.runtime.malformed_strings = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("duplicate_objects") => self.runtime.duplicate_objects = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("argument_limits") => self.runtime.argument_limits = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("argument_ranges") => self.runtime.argument_ranges = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("short_messages") => self.runtime.short_messages = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("access_denied") => self.runtime.access_denied = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("protocol_errors") => self.runtime.protocol_errors = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("client_data") => self.runtime.client_data = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("dead_clients") => self.runtime.dead_clients = true,
+                    option => return Err(syn::Error::new(option.span(), "Expected `send_all`, `lease_version`, `destroyed_objects`, `wire_dumps`, `message_size`, `flush`, `disconnects`, `interior_nul`, `create`, `malformed_strings`, `duplicate_objects`, `argument_limits`, `argument_ranges`, `short_messages`, `access_denied`, `protocol_errors`, `client_data` or `dead_clients`"))
+                }
+            }
+            return Ok(true)
+        }
+        if flag == "delegate" {
+            self.delegate = Some(match attribute.parse_meta()? {
+                Meta::List(list) if list.nested.len() == 1 => match list.nested.into_iter().next() {
+                    Some(NestedMeta::Meta(Meta::Path(path))) => path,
+                    option => return Err(syn::Error::new(option.span(), "Expected the path of the function resolving receivers"))
+                },
+                meta => return Err(syn::Error::new(meta.span(), "Expected the function resolving receivers, e.g. `#![delegate(crate::resolve)]`"))
+            });
+            return Ok(true)
+        }
+        if flag == "privileged" {
+            self.privileged = Some(match attribute.parse_meta()? {
+                Meta::List(list) if list.nested.len() == 1 => match list.nested.into_iter().next() {
+                    Some(NestedMeta::Meta(Meta::Path(path))) => path,
+                    option => return Err(syn::Error::new(option.span(), "Expected the path of the function deciding whether a client is privileged"))
+                },
+                meta => return Err(syn::Error::new(meta.span(), "Expected the function deciding whether a client is privileged, e.g. `#![privileged(crate::is_privileged)]`"))
+            });
+            return Ok(true)
+        }
+        if flag == "max_arg_len" {
+            self.max_arg_len = Some(match attribute.parse_meta()? {
+                Meta::NameValue(MetaNameValue { lit: Lit::Int(limit), .. }) => limit.base10_parse()?,
+                meta => return Err(syn::Error::new(meta.span(), "Expected a length limit, e.g. `#![max_arg_len = 4096]`"))
+            });
+            return Ok(true)
+        }
+        match flag.as_str() {
+            "testing" => self.testing = true,
+            "loose_objects" => self.loose_objects = true,
+            "deny_lints" => self.deny_lints = true,
+            "roundtrip_tests" => self.roundtrip_tests = true,
+            "api_snapshot" => self.api_snapshot = true,
+            "no_debug" => self.no_debug = true,
+            "legacy_entry_names" => self.legacy_entry_names = true,
+            "stats" => self.stats = true,
+            "split_traits" => self.split_traits = true,
+            "borrowed_args" => self.borrowed_args = true,
+            "unchecked_strings" => self.unchecked_strings = true,
+            "skip_dead_clients" => self.skip_dead_clients = Some(attribute.span()),
+            "doc_all" => self.doc_all = true,
+            _ => return Ok(false)
+        }
+        if !attribute.tokens.is_empty() {
+            return Err(syn::Error::new(attribute.tokens.span(), format!("The `{}` flag does not take arguments", flag)))
+        }
+        Ok(true)
+    }
+}
+#[derive(Clone)]
 pub(crate) struct Binding {
     is_external: bool,
+    /// Stands in for the binding of an unbound interface documented under the module's `doc_all` flag, so that the
+    /// interface is still not bound
+    doc_only: bool,
     interface: Ident,
-    implementation: Path
+    /// The exact interface name, written `#[interface = "name"]`, for interfaces whose CamelCase names coincide
+    interface_name: Option<LitStr>,
+    implementation: Path,
+    /// The implementation is a trait, bound as `Box<dyn Trait>` so that implementations may be chosen at runtime
+    is_dyn: bool,
+    options: BindingOptions
 }
 impl Parse for Binding {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut interface_name = None;
+        for attribute in input.call(Attribute::parse_outer)? {
+            match attribute.parse_meta()? {
+                Meta::NameValue(MetaNameValue { path, lit: Lit::Str(name), .. }) if path.is_ident("interface") => interface_name = Some(name),
+                meta => return Err(syn::Error::new(meta.span(), "Expected the interface the binding implements, e.g. `#[interface = \"zwp_tablet_v2\"]`"))
+            }
+        }
         let lookahead = input.lookahead1();
         if lookahead.peek(Token![type]) {
             let _: Token![type] = input.parse()?;
             let interface = input.parse()?;
             let _: Token![=] = input.parse()?;
+            let dyn_token: Option<Token![dyn]> = input.parse()?;
             let implementation = input.parse()?;
+            let options = if input.peek(token::Paren) {
+                input.parse()?
+            } else {
+                BindingOptions::default()
+            };
             Ok(Self {
                 is_external: false,
+                doc_only: false,
                 interface,
-                implementation
+                interface_name,
+                implementation,
+                is_dyn: dyn_token.is_some(),
+                options
             })
-        } else {
+        } else if lookahead.peek(Token![use]) {
             let _: Token![use] = input.parse()?;
             let implementation = input.parse()?;
             let _: Token![as] = input.parse()?;
             let interface = input.parse()?;
             Ok(Self {
                 is_external: true,
+                doc_only: false,
                 interface,
-                implementation
+                interface_name,
+                implementation,
+                is_dyn: false,
+                options: BindingOptions::default()
             })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+/// An implementation of one plain path for another, a common mistake for the binding it stands for. Which side names
+/// the interface is only known once the protocol is loaded, so the mistake is reported during expansion
+struct ImplBinding {
+    impl_token: Token![impl],
+    trait_path: Path,
+    self_path: Path,
+    /// Written with `;` rather than a body, so it is not also kept as an ordinary item
+    is_bodiless: bool
+}
+impl Parse for ImplBinding {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let impl_token: Token![impl] = input.parse()?;
+        let trait_path: Path = input.parse()?;
+        let _: Token![for] = input.parse()?;
+        let self_path: Path = input.parse()?;
+        let plain = |path: &Path| path.segments.iter().all(|segment| segment.arguments.is_empty());
+        if !plain(&trait_path) || !plain(&self_path) || !(input.peek(Token![;]) || input.peek(token::Brace)) {
+            return Err(syn::Error::new(impl_token.span, "Expected an implementation of one plain path for another"))
+        }
+        Ok(Self {
+            impl_token,
+            trait_path,
+            self_path,
+            is_bodiless: input.peek(Token![;])
+        })
+    }
+}
+impl ImplBinding {
+    /// The binding the implementation was likely meant to be, if it names an unbound interface of the protocol
+    fn error(&self, protocol: &Protocol, bindings: &HashMap<String, Binding>) -> Option<syn::Error> {
+        let unbound_interface = |path: &Path| path.get_ident()
+            .map(|ident| ident.unraw().to_string())
+            .filter(|name| !bindings.contains_key(name) && protocol.interfaces.iter().any(|interface| camel_case(&interface.name) == *name));
+        let message = match (unbound_interface(&self.trait_path), unbound_interface(&self.self_path)) {
+            (Some(interface), _) => format!("Bindings are not trait implementations. Write `type {} = {};` instead", interface, path_name(&self.self_path)),
+            (None, Some(interface)) => format!("Bindings are not trait implementations. Write `type {} = {};` instead", interface, path_name(&self.trait_path)),
+            // Implementations with a body that do not name an interface are ordinary items
+            (None, None) if !self.is_bodiless => return None,
+            (None, None) => format!("Bindings are not trait implementations. Write `type WlInterface = path::Implementation;`, naming an unbound interface of protocol {:?} by its CamelCase name", protocol.name)
+        };
+        Some(syn::Error::new(self.impl_token.span, message))
+    }
+}
+/// The binding of the named interface. Bindings are keyed by CamelCase name, so one naming a different interface with
+/// the same CamelCase form is not the interface's binding
+pub(crate) fn binding_of<'a>(bindings: &'a HashMap<String, Binding>, interface: &str) -> Option<&'a Binding> {
+    bindings.get(&camel_case(interface)).filter(|binding| binding.binds(interface))
+}
+impl Binding {
+    /// The stand-in binding of an unbound interface documented under the `doc_all` flag. Its object arguments of other
+    /// unbound interfaces are untyped, as they would be if the interface were bound with `loose_objects`
+    fn doc_only(interface: &Interface, options: &ModuleOptions) -> Self {
+        let name = ident(&camel_case(&interface.name));
+        let mut binding_options = BindingOptions {
+            loose_objects: true,
+            ..BindingOptions::default()
+        };
+        options.apply(&mut binding_options);
+        Self {
+            is_external: false,
+            doc_only: true,
+            interface: name.clone(),
+            interface_name: None,
+            implementation: name.into(),
+            is_dyn: false,
+            options: binding_options
+        }
+    }
+    /// Whether the binding is for the named interface rather than another whose name has the same CamelCase form
+    pub(crate) fn binds(&self, interface: &str) -> bool {
+        !self.doc_only && self.interface_name.as_ref().map(|name| name.value() == interface).unwrap_or(true)
+    }
+    /// The implementation as written
+    fn implementation_name(&self) -> String {
+        if self.is_dyn {
+            format!("dyn {}", path_name(&self.implementation))
+        } else {
+            path_name(&self.implementation)
+        }
+    }
+    /// The type implementing `Dispatch` and leased for the interface
+    pub(crate) fn implementation_type(&self) -> TokenStream {
+        self.implementation_type_in(0)
+    }
+    /// The type implementing `Dispatch` as referenced from a module nested `depth` modules inside the generated module.
+    /// Paths relative to the generated module, whether bare or beginning with `self::` or `super::`, are made relative
+    /// to the nested module by prefixing a `super::` per level
+    fn implementation_type_in(&self, depth: usize) -> TokenStream {
+        let mut implementation = self.implementation.clone();
+        let is_absolute = implementation.leading_colon.is_some() || implementation.segments.first().map(|segment| segment.ident == "crate").unwrap_or(false);
+        if depth > 0 && !is_absolute {
+            if implementation.segments.first().map(|segment| segment.ident == "self").unwrap_or(false) {
+                implementation.segments = implementation.segments.into_iter().skip(1).collect();
+            }
+            let supers = (0..depth).map(|_| quote!{ super:: });
+            let relative = quote!{ #(#supers)* #implementation };
+            implementation = syn::parse_quote!{ #relative };
+        }
+        if self.is_dyn {
+            quote!{ ::std::boxed::Box<dyn #implementation> }
+        } else {
+            quote!{ #implementation }
+        }
+    }
+    /// The type implementing `Dispatch` as written with the prelude in scope
+    fn implementation_type_name(&self) -> String {
+        if self.is_dyn {
+            format!("Box<dyn {}>", path_name(&self.implementation))
+        } else {
+            path_name(&self.implementation)
+        }
+    }
+    /// A sentence naming the implementation for the interface trait's documentation, linking to it where the path as
+    /// written is also an intra-doc link
+    fn implementation_doc(&self) -> String {
+        let path = path_name(&self.implementation);
+        let linkable = self.implementation.segments.iter().all(|segment| segment.arguments.is_empty());
+        let implementation = if linkable {
+            format!("[`{}`]({})", path, path)
+        } else {
+            format!("`{}`", path)
+        };
+        if self.is_dyn {
+            format!("Implemented in this build by `Box<dyn {}>`, with implementations of {} chosen at runtime.", path, implementation)
+        } else if self.options.delegate.is_some() {
+            format!("Implemented in this build by {}.", implementation)
+        } else {
+            format!("Implemented in this build by {}. Implementations are owned by the client's leases, so must be `Sized` and `'static`, owning their data rather than borrowing it.", implementation)
+        }
+    }
+    /// The trait method of the named request or event, which is its snake_case name unless renamed by the binding
+    pub(crate) fn method_name(&self, message: &str) -> String {
+        self.options.renames.iter()
+            .find(|(name, _)| name.unraw() == message)
+            .map(|(_, method)| method.unraw().to_string())
+            .unwrap_or_else(|| snake_case(message))
+    }
+    /// A `doc(alias)` attribute naming the request or event a renamed method is for, so documentation searches for the
+    /// protocol's name still find it
+    fn method_alias(&self, message: &str) -> Option<TokenStream> {
+        self.options.renames.iter()
+            .any(|(name, _)| name.unraw() == message)
+            .then(|| quote!{ #[doc(alias = #message)] })
+    }
+    /// The highest version of the interface the implementation supports, which is the interface's version unless the
+    /// binding declares a lower one
+    fn supported_version(&self, interface: &Interface) -> u32 {
+        self.options.version.as_ref()
+            .and_then(|version| version.base10_parse().ok())
+            .unwrap_or(interface.version)
+    }
+    /// The context type path as written, if the binding has one
+    fn context_name(&self) -> Option<String> {
+        self.options.context.as_ref().map(path_name)
+    }
+    /// The span of the final token of the binding, for reporting a missing separator
+    fn end_span(&self) -> Span {
+        if self.is_external {
+            self.interface.span()
+        } else if let Some(options) = &self.options.span {
+            *options
+        } else {
+            self.implementation.segments.last().map(|segment| segment.ident.span()).unwrap_or_else(|| self.implementation.span())
+        }
+    }
+}
+/// A path as written, without generic arguments
+fn path_name(path: &Path) -> String {
+    let segments = path.segments.iter().map(|segment| segment.ident.to_string()).collect::<Vec<_>>().join("::");
+    if path.leading_colon.is_some() {
+        format!("::{}", segments)
+    } else {
+        segments
+    }
+}
+/// Per-binding options, written in parentheses after the implementation path
+#[derive(Clone, Default)]
+pub(crate) struct BindingOptions {
+    span: Option<Span>,
+    /// Requests are provided trait methods that post an `invalid_method` protocol error unless overridden
+    default_error: bool,
+    /// Object arguments of interfaces without a binding are received untyped rather than rejected
+    pub(crate) loose_objects: bool,
+    /// Set from the module's `max_arg_len` flag
+    max_arg_len: Option<u32>,
+    /// Set from the module's `no_debug` flag
+    no_debug: bool,
+    /// Set from the module's `stats` flag
+    stats: bool,
+    /// Set from the module's `split_traits` flag
+    pub(crate) split_traits: bool,
+    /// Set from the module's `fixed(as_f64)` flag
+    pub(crate) fixed_as_f64: bool,
+    /// Set from the module's `borrowed_args` flag
+    pub(crate) borrowed_args: bool,
+    /// Set from the module's `unchecked_strings` flag
+    unchecked_strings: bool,
+    /// Set from the module's `skip_dead_clients` flag
+    skip_dead_clients: bool,
+    /// Set from the module's `runtime` flag
+    pub(crate) runtime: RuntimeOptions,
+    /// Set from the module's `delegate` flag
+    delegate: Option<Path>,
+    /// Set from the module's `privileged` flag
+    privileged: Option<Path>,
+    /// State taken from the client and passed to every request, written `context<Type>`
+    pub(crate) context: Option<Path>,
+    /// The function constructing the implementation for a new object in place of `Default`, written `init<path>`
+    init: Option<Path>,
+    /// Trait methods named other than the snake_case name of their request or event, written
+    /// `rename<message = method, ...>`
+    renames: Vec<(Ident, Ident)>,
+    /// The highest version of the interface the implementation supports, written `version<N>`
+    version: Option<LitInt>
+}
+impl Parse for BindingOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        let parens = parenthesized!(content in input);
+        let mut options = Self {
+            span: Some(parens.span),
+            ..Self::default()
+        };
+        // Options must parse as a parenthesized type list as rustc parses the binding as a type alias first
+        while !content.is_empty() {
+            let option: Ident = content.parse()?;
+            match option.to_string().as_str() {
+                "default_error" => options.default_error = true,
+                "loose_objects" => options.loose_objects = true,
+                "context" => {
+                    let _: Token![<] = content.parse()?;
+                    options.context = Some(content.parse()?);
+                    let _: Token![>] = content.parse()?;
+                },
+                "init" => {
+                    let _: Token![<] = content.parse()?;
+                    options.init = Some(content.parse()?);
+                    let _: Token![>] = content.parse()?;
+                },
+                "version" => {
+                    let _: Token![<] = content.parse()?;
+                    let version: LitInt = content.parse()?;
+                    version.base10_parse::<u32>()?;
+                    options.version = Some(version);
+                    let _: Token![>] = content.parse()?;
+                },
+                "rename" => {
+                    let _: Token![<] = content.parse()?;
+                    loop {
+                        let message = content.call(Ident::parse_any)?;
+                        let _: Token![=] = content.parse()?;
+                        let method = content.call(Ident::parse_any)?;
+                        options.renames.push((message, method));
+                        if content.peek(Token![>]) {
+                            break
+                        }
+                        let _: Token![,] = content.parse()?;
+                    }
+                    let _: Token![>] = content.parse()?;
+                },
+                _ => return Err(syn::Error::new(option.span(), format!("Unknown binding option {:?}", option.to_string())))
+            }
+            if !content.is_empty() {
+                let _: Token![,] = content.parse()?;
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Where the protocol specification is read from
+enum ProtocolSource {
+    /// A path relative to the working directory of the compiler
+    Path(LitStr),
+    /// An environment variable set at compile time naming the path, e.g. by a build script via `cargo:rustc-env`
+    Env(LitStr)
+}
+impl ProtocolSource {
+    fn span(&self) -> Span {
+        match self {
+            Self::Path(path) => path.span(),
+            Self::Env(variable) => variable.span()
+        }
+    }
+    fn path(&self) -> syn::Result<String> {
+        match self {
+            Self::Path(path) => Ok(path.value()),
+            Self::Env(variable) => std::env::var(variable.value()).map_err(|_| syn::Error::new(variable.span(), format!("Environment variable {:?} is not set. It must name the protocol specification file at compile time", variable.value())))
+        }
+    }
+}
+struct ProtocolAttribute {
+    /// Specifications merged into one protocol, the first of which names it
+    sources: Vec<ProtocolSource>,
+    /// Only these interfaces are considered, as if the protocol contained no others
+    only: Option<Vec<LitStr>>,
+    /// These interfaces are removed from the protocol before generation
+    exclude: Vec<LitStr>,
+    /// The path of the `wl` runtime crate referenced by generated code
+    runtime: Path,
+    /// A lock file recording the wire format, against which protocol upgrades are checked
+    lock: Option<LitStr>
+}
+impl Parse for ProtocolAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        fn interface_list(input: ParseStream) -> syn::Result<Vec<LitStr>> {
+            let content;
+            let _ = bracketed!(content in input);
+            Ok(content.parse_terminated::<LitStr, Token![,]>(|input| input.parse())?.into_iter().collect())
         }
+        let mut sources = Vec::new();
+        let mut only = None;
+        let mut exclude = Vec::new();
+        let mut runtime = syn::parse_quote!{ ::wl };
+        let mut lock = None;
+        while !input.is_empty() {
+            if input.peek(LitStr) {
+                sources.push(ProtocolSource::Path(input.parse()?));
+            } else {
+                let key = input.call(Ident::parse_any)?;
+                let _: Token![=] = input.parse()?;
+                match key.to_string().as_str() {
+                    "env" => sources.push(ProtocolSource::Env(input.parse()?)),
+                    "only" => only = Some(interface_list(input)?),
+                    "exclude" => exclude.extend(interface_list(input)?),
+                    "crate" => runtime = input.parse()?,
+                    "lock" => lock = Some(input.parse()?),
+                    _ => return Err(syn::Error::new(key.span(), "Expected a protocol specification path, `env = \"VARIABLE\"`, `only = [...]`, `exclude = [...]`, `crate = path` or `lock = \"path\"`"))
+                }
+            }
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+        if sources.is_empty() {
+            return Err(syn::Error::new(Span::call_site(), "Expected a protocol specification path or `env = \"VARIABLE\"`"))
+        }
+        Ok(Self {
+            sources,
+            only,
+            exclude,
+            runtime,
+            lock
+        })
+    }
+}
+impl ProtocolAttribute {
+    /// Load and merge the specifications, returning them along with the merged and filtered protocol
+    fn load(&self) -> syn::Result<(Vec<Protocol>, Protocol)> {
+        let protocols = self.sources.iter()
+            .map(|source| Protocol::load(source.path()?).map_err(|error| syn::Error::new(source.span(), error)))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let mut protocol = Protocol::merge(&protocols).map_err(|error| syn::Error::new(Span::call_site(), error))?;
+        self.filter_interfaces(&mut protocol)?;
+        Ok((protocols, protocol))
+    }
+    /// Apply `only` and `exclude`, erroring on names that are not interfaces of the protocol
+    fn filter_interfaces(&self, protocol: &mut Protocol) -> syn::Result<()> {
+        for name in self.only.iter().flatten().chain(&self.exclude) {
+            if !protocol.interfaces.iter().any(|interface| interface.name == name.value()) {
+                return Err(syn::Error::new(name.span(), format!("No interface named {:?} in protocol {:?}", name.value(), protocol.name)))
+            }
+        }
+        if let Some(only) = &self.only {
+            protocol.interfaces.retain(|interface| only.iter().any(|name| interface.name == name.value()));
+        }
+        protocol.interfaces.retain(|interface| !self.exclude.iter().any(|name| interface.name == name.value()));
+        Ok(())
     }
 }
 
 #[proc_macro_attribute]
 /// Parses the wayland protocol specification, producing a set of interface traits inside a module named after the protocol
-/// ```rust
-/// use wl::{prelude::*, Result};
-/// protocol!("wayland.toml")
-/// 
-/// struct Display;
-/// #[dispatch]
-/// impl wayland::WlDisplay for Lease<WlDisplay> {
-///     fn sync(&mut self, client: &mut Client, id: NewId) -> Result<()> {
+/// ```rust,ignore
+/// use wl::{NewId, server::{Client, Lease, Result}};
+/// use wl_macro::{dispatch, server_protocol};
+///
+/// #[server_protocol("wayland.toml")]
+/// mod wayland {
+///     type WlDisplay = crate::Display;
+///     type WlRegistry = crate::Registry;
+///     type WlCallback = crate::Callback;
+/// }
+///
+/// #[derive(Default)]
+/// pub struct Display;
+/// #[dispatch("wayland.toml")]
+/// impl wayland::WlDisplay for Lease<Display> {
+///     fn sync(&mut self, client: &mut Client, wl_callback: NewId) -> Result<()> {
 ///         todo!()
 ///     }
-///     fn get_regsitry(&mut self, client: &mut Client, id: NewId) -> Result<()> {
+///     fn get_registry(&mut self, client: &mut Client, wl_registry: NewId) -> Result<()> {
 ///         todo!()
 ///     }
 /// }
+/// // `Registry` and `Callback` are implemented likewise
 /// ```
+///
+/// Several specifications may be given, e.g. `#[server_protocol("wayland.toml", "xdg-shell.toml")]`, to generate one
+/// module from their combined interfaces. Further arguments follow the paths:
+/// - `env = "VAR"` in place of the paths: read the specification's path from an environment variable set at compile time
+/// - `only = ["interface", ...]` and `exclude = ["interface", ...]`: narrow the protocol before generation
+/// - `crate = path`: refer to the runtime by `path` rather than `::wl`
+/// - `lock = "wayland.lock.toml"`: check the protocol's wire format against a lock file, written on the first build
+///
+/// The specification format, the generated items, and the flags, runtime options and binding options accepted in the
+/// module are described in the crate documentation.
 pub fn server_protocol(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let path = parse_macro_input!(attr as LitStr).value();
+    let attribute = parse_macro_input!(attr as ProtocolAttribute);
     let module = parse_macro_input!(item as ProtocolModule);
+    expand_server_protocol(attribute, module).into()
+}
+/// The expansion of `server_protocol`, separate from the macro so that it may run outside of the compiler
+fn expand_server_protocol(attribute: ProtocolAttribute, module: ProtocolModule) -> TokenStream {
+    let (protocols, mut protocol) = match attribute.load() {
+        Ok(loaded) => loaded,
+        Err(error) => return error.to_compile_error()
+    };
+    if let Err(error) = module.options.check_runtime() {
+        return error.to_compile_error()
+    }
+    let impl_binding_errors: Vec<_> = module.impl_bindings.iter().filter_map(|impl_binding| impl_binding.error(&protocol, &module.bindings)).collect();
+    if !impl_binding_errors.is_empty() {
+        return impl_binding_errors.iter().map(syn::Error::to_compile_error).collect()
+    }
+    if module.options.summaries_only {
+        protocol.drop_descriptions();
+    }
 
+    let wl = &attribute.runtime;
     let module_visibility = &module.visibility;
     let module_name = &module.ident;
     let bindings = &module.bindings;
 
-    let protocol = Protocol::load::<&str>(&path);
-    let protocol_name = protocol.name.to_snake_case();
-    let protocol_copyright = protocol.copyright.iter();
-    let interfaces = protocol.interfaces.iter()
-        .filter(|interface| bindings.get(&interface.name.to_snake_case()).map(|b| !b.is_external).unwrap_or(false))
-        .map(|interface| generate_interface(interface, bindings));
-    let enums = protocol.interfaces.iter()
-        .filter(|interface| bindings.get(&interface.name.to_snake_case())
-        .map(|b| !b.is_external).unwrap_or(false))
-        .map(|interface| generate_enums(interface));
+    let protocol_name = snake_case(&protocol.name);
+    let protocol_copyright = protocol.copyright.as_deref().unwrap_or_default();
+    let signature_hash = protocol.signature_hash();
+    let protocol_names = protocols.iter().map(|protocol| snake_case(&protocol.name));
+    let protocol_copyrights = protocols.iter().map(|protocol| protocol.copyright.as_deref().unwrap_or_default());
+    let bound_interfaces: Vec<&Interface> = protocol.interfaces.iter()
+        .filter(|interface| binding_of(bindings, &interface.name).map(|b| !b.is_external).unwrap_or(false))
+        .collect();
+    // Enums are generated for bound interfaces and for unbound interfaces whose enums the bound interfaces reference
+    let referenced_enums = Protocol::referenced_enums(&bound_interfaces);
+    // External bindings named after an enum rather than an interface alias an enum generated elsewhere
+    let enum_alias = |interface: &str, e: &str| {
+        let name = format!("{}{}", camel_case(interface), camel_case(e));
+        let is_interface = protocol.interfaces.iter().any(|interface| camel_case(&interface.name) == name);
+        bindings.get(&name).filter(|binding| binding.is_external && !is_interface)
+    };
+    let enum_aliases: BTreeMap<(String, String), &Binding> = protocol.interfaces.iter()
+        .flat_map(|interface| interface.enums.iter().map(move |e| (interface.name.clone(), e.name.clone())))
+        .chain(referenced_enums.iter().cloned())
+        .filter(|(interface, _)| !bound_interfaces.iter().any(|bound| bound.name == *interface))
+        .filter_map(|(interface, e)| enum_alias(&interface, &e).map(|binding| ((interface, e), binding)))
+        .collect();
+    let enum_alias_errors = bound_interfaces.iter()
+        .flat_map(|interface| interface.enums.iter().map(move |e| (interface, e)))
+        .filter_map(|(interface, e)| enum_alias(&interface.name, &e.name).map(|binding| {
+            let message = format!("Enum \"{}.{}\" cannot be aliased as interface {:?} is bound, so the enum is generated by this module", interface.name, e.name, interface.name);
+            syn::Error::new(binding.implementation.span(), message).to_compile_error()
+        }))
+        .collect::<Vec<_>>();
+    let enum_interfaces: Vec<(&Interface, Vec<&Enum>)> = protocol.interfaces.iter()
+        .filter_map(|interface| {
+            let enums: Vec<&Enum> = match binding_of(bindings, &interface.name) {
+                Some(binding) if !binding.is_external => interface.enums.iter().collect(),
+                Some(_) => Vec::new(),
+                None => interface.enums.iter().filter(|e| {
+                    let key = (interface.name.clone(), e.name.clone());
+                    referenced_enums.contains(&key) && !enum_aliases.contains_key(&key)
+                }).collect()
+            };
+            if enums.is_empty() {
+                None
+            } else {
+                Some((interface, enums))
+            }
+        })
+        .collect();
+    // Under the `doc_all` flag interfaces without a binding are documented by items generated only for rustdoc, so
+    // that they remain unbound in other builds
+    let documented_interfaces: Vec<&Interface> = protocol.interfaces.iter()
+        .filter(|interface| module.options.doc_all && !bindings.contains_key(&camel_case(&interface.name)))
+        .collect();
+    let doc_bindings: HashMap<String, Binding> = bindings.clone().into_iter()
+        .chain(documented_interfaces.iter().map(|interface| (camel_case(&interface.name), Binding::doc_only(interface, &module.options))))
+        .collect();
+    let documented_enums: Vec<(&Interface, Vec<&Enum>)> = documented_interfaces.iter()
+        .map(|interface| (*interface, interface.enums.iter().filter(|e| {
+            let key = (interface.name.clone(), e.name.clone());
+            !referenced_enums.contains(&key) && !enum_aliases.contains_key(&key)
+        }).collect::<Vec<_>>()))
+        .filter(|(_, enums)| !enums.is_empty())
+        .collect();
+    // Bindings matching several interfaces would otherwise be reported as collisions of the interfaces' names
+    let mut collisions = ambiguous_binding_errors(&protocol, bindings);
+    if collisions.is_empty() {
+        let interfaces: Vec<&Interface> = bound_interfaces.iter().chain(&documented_interfaces).copied().collect();
+        let enum_interfaces: Vec<(&Interface, Vec<&Enum>)> = enum_interfaces.iter().chain(&documented_enums).cloned().collect();
+        collisions = identifier_errors(module_name, &interfaces, &enum_interfaces, &doc_bindings, &module.options);
+    }
+    if !collisions.is_empty() {
+        let collisions = collisions.iter().map(syn::Error::to_compile_error);
+        return quote! {
+            #module_visibility mod #module_name {
+                #(#collisions)*
+            }
+        }
+    }
+    let dependency_errors = missing_dependency_errors(&bound_interfaces, bindings);
+    if !dependency_errors.is_empty() {
+        let dependency_errors = dependency_errors.iter().map(syn::Error::to_compile_error);
+        return quote! {
+            #module_visibility mod #module_name {
+                #(#dependency_errors)*
+            }
+        }
+    }
+    Manifest::new(&protocol, &protocols, module_name, bindings).write_if_requested();
+    let lock_errors = match &attribute.lock {
+        Some(path) => match Lock::new(&protocol).check(&path.value()) {
+            Ok(changes) => changes.iter().map(|change| syn::Error::new(path.span(), format!("Incompatible protocol change: {}", change))).collect(),
+            Err(error) => vec![syn::Error::new(path.span(), error)]
+        },
+        None => Vec::new()
+    };
+    let lock_errors = lock_errors.iter().map(syn::Error::to_compile_error);
+    let protocol_lints = lints::lint(&protocol);
+    let lint_errors = protocol_lints.iter()
+        .filter(|_| module.options.deny_lints)
+        .map(|lint| syn::Error::new(Span::call_site(), lint).to_compile_error());
+    let enum_representations = protocol.enum_representations();
+    let enum_examples = module.options.doc_examples.then(|| enum_examples(&protocol, &enum_representations));
+    // Traits are generated with the interfaces they depend on following them, rather than in specification order
+    let related_interfaces = Dependencies::new(&bound_interfaces).order();
+    let interfaces = related_interfaces.iter().map(|interface| generate_interface(interface, bindings, enum_examples.as_ref(), wl));
+    let documentation = documented_interfaces.iter().map(|interface| {
+        let traits = generate_traits(interface, &doc_bindings, enum_examples.as_ref(), "Not bound by this module, so only generated for documentation.".to_string(), wl);
+        let metadata = generate_metadata(interface, module.options.stats, module.options.runtime.message_size, wl);
+        doc_only(quote!{ #traits #metadata })
+    }).chain(documented_enums.iter().map(|(interface, enums)| doc_only(generate_enums(interface, enums, &enum_representations, module.options.legacy_entry_names, module.options.runtime.protocol_errors, wl))));
+    let metadata = related_interfaces.iter().map(|interface| generate_metadata(interface, module.options.stats, module.options.runtime.message_size, wl));
+    let enums = enum_interfaces.iter().map(|(interface, enums)| {
+        // Enums of bound interfaces are generated whether or not anything uses them, as they are part of the protocol
+        let (referenced, unreferenced): (Vec<&Enum>, Vec<&Enum>) = enums.iter()
+            .partition(|e| e.is_error_enum() || referenced_enums.contains(&(interface.name.clone(), e.name.clone())));
+        let referenced = generate_enums(interface, &referenced, &enum_representations, module.options.legacy_entry_names, module.options.runtime.protocol_errors, wl);
+        let unreferenced = allow_unreferenced(generate_enums(interface, &unreferenced, &enum_representations, module.options.legacy_entry_names, module.options.runtime.protocol_errors, wl));
+        quote!{ #referenced #unreferenced }
+    });
+    let raw_entries = generate_raw_entries(&enum_interfaces, &enum_representations);
+    let enum_alias_items = generate_enum_aliases(&enum_aliases);
+    let enum_mappings = match protocol.enum_mappings() {
+        Ok(mappings) => generate_enum_mappings(&mappings, &enum_interfaces, &enum_representations),
+        Err(errors) => {
+            let errors = errors.iter().map(|error| syn::Error::new(Span::call_site(), error).to_compile_error());
+            quote!{ #(#errors)* }
+        }
+    };
+    let implementations = bound_interfaces.iter().map(|interface| {
+        let name = &interface.name;
+        let binding = &bindings[&camel_case(&interface.name)];
+        let version = binding.supported_version(interface);
+        let implementor = binding.implementation_name();
+        let requests = interface.requests.len();
+        let events = interface.events.len();
+        quote! {
+            ImplementationInfo {
+                interface: #name,
+                version: #version,
+                implementor: #implementor,
+                requests: #requests,
+                events: #events
+            }
+        }
+    });
+    let prelude = generate_prelude(&bound_interfaces, &enum_interfaces, &enum_aliases, module.options.split_traits, wl);
+    let display_helpers = generate_display_helpers(bindings, module.options.runtime, wl);
+    let not_implemented = generate_not_implemented(&protocol, bindings, wl);
+    let construct = generate_construct(&bound_interfaces, bindings, wl);
+    let create = if module.options.delegate.is_none() && module.options.runtime.create {
+        generate_create(wl)
+    } else {
+        quote!{}
+    };
+    let bound_version = generate_bound_version(module.options.runtime.lease_version, wl);
+    let negotiate = generate_negotiate(&bound_interfaces, bindings);
+    let interface_of = generate_interface_of(&bound_interfaces, bindings, wl);
+    let broadcasts = generate_broadcasts(&bound_interfaces, bindings, wl);
+    let wire_dump = if module.options.no_debug || !module.options.runtime.wire_dumps {
+        quote!{}
+    } else {
+        generate_wire_dump(wl)
+    };
+    let check_event_string = if module.options.unchecked_strings || !module.options.runtime.interior_nul {
+        quote!{}
+    } else {
+        generate_check_event_string(wl)
+    };
+    let receiver_or = if allows_self(&bound_interfaces) {
+        generate_receiver_or()
+    } else {
+        quote!{}
+    };
+    let suppress_events = if module.options.skip_dead_clients.is_some() {
+        generate_suppress_events(module.options.no_debug, wl)
+    } else {
+        quote!{}
+    };
+    let batch_suppression = generate_dead_client_check(module.options.skip_dead_clients.is_some(), quote!{ self.messages.len() }, quote! {
+        return ::std::result::Result::Ok(())
+    });
+    let batch_send = if module.options.runtime.send_all {
+        quote!{ client.send_all(self.messages) }
+    } else {
+        quote!{ self.messages.into_iter().try_for_each(|message| client.send(message)) }
+    };
+    let (batch_send, classify_send) = if module.options.runtime.disconnects {
+        (quote!{ self::classify_send(#batch_send, client) }, generate_classify_send(wl))
+    } else {
+        (batch_send, quote!{})
+    };
+    let stats = if module.options.stats {
+        generate_stats(&bound_interfaces)
+    } else {
+        quote!{}
+    };
+    let index = generate_index(&protocols, &protocol, bindings, &documented_interfaces);
+    let runtime_assertions = generate_runtime_assertions(wl);
+    let file_tracking = generate_file_tracking(&protocol.files);
+    let module_attributes = &module.attributes;
+    let module_items = &module.items;
+    let mock = if module.options.testing {
+        generate_mock(&bound_interfaces, bindings, wl)
+    } else {
+        quote!{}
+    };
+    let roundtrip_tests = if module.options.roundtrip_tests {
+        generate_roundtrip_tests(&bound_interfaces, bindings, wl)
+    } else {
+        quote!{}
+    };
+    let api_snapshot = if module.options.api_snapshot {
+        generate_api_snapshot(&api::items(&bound_interfaces, &enum_interfaces, bindings))
+    } else {
+        quote!{}
+    };
 
     // TODO: Reenable this error for types not marked as extern or something
     let interface_not_found_errors = bindings.iter().filter_map(|(interface, binding)|
-        if protocol.interfaces.iter().find(|known_interface| interface.to_snake_case() == known_interface.name.to_snake_case()).is_some() || binding.is_external {
+        if protocol.interfaces.iter().any(|known_interface| *interface == camel_case(&known_interface.name) && binding.binds(&known_interface.name)) || binding.is_external {
             None
+        } else if let Some(name) = &binding.interface_name {
+            Some(syn::Error::new(name.span(), format!("No interface named {:?} with the CamelCase name {}", name.value(), interface)).to_compile_error())
         } else {
-            Some(syn::Error::new(binding.implementation.span(), format!("No interface named {:?}", interface.to_snake_case())).to_compile_error())
+            Some(syn::Error::new(binding.implementation.span(), format!("No interface named {:?}", snake_case(interface))).to_compile_error())
         }
     );
 
     quote! {
-        #[allow(unused_variables)]
         #module_visibility mod #module_name {
+            #(#module_attributes)*
+            #index
             #(#interface_not_found_errors)*
+            #(#lint_errors)*
+            #(#lock_errors)*
+            #(#enum_alias_errors)*
+            #runtime_assertions
+            #file_tracking
+            /// The first of the merged protocols
             pub const PROTOCOL: &'static str = #protocol_name;
-            #(pub const COPYRIGHT: &'static str = #protocol_copyright;)*
+            pub const PROTOCOLS: &'static [&'static str] = &[#(#protocol_names),*];
+            /// Likely mistakes in the protocol specification that do not prevent generation
+            pub const PROTOCOL_LINTS: &'static [&'static str] = &[#(#protocol_lints),*];
+            /// The copyright notice of the first of the merged protocols, empty if it has none
+            pub const COPYRIGHT: &'static str = #protocol_copyright;
+            /// The copyright notice of each protocol in `PROTOCOLS`, empty where a protocol has none
+            pub const COPYRIGHTS: &'static [&'static str] = &[#(#protocol_copyrights),*];
+            /// A hash of the wire format of every interface of the merged protocols, whether bound or not
+            pub const SIGNATURE_HASH: u64 = #signature_hash;
+            /// Introspection data for a request or event, indexed by opcode in the per-interface tables
+            #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone)]
+            pub struct MessageInfo {
+                pub name: &'static str,
+                pub opcode: u16,
+                /// The interface version the message was introduced in
+                pub since: u32,
+                /// The request sets pending state that is only applied once the object's state is latched
+                pub double_buffered: bool,
+                /// The request applies previously pending state
+                pub latching: bool,
+                /// The smallest size in bytes of the message on the wire, with its strings and arrays empty and its
+                /// 8 byte header included
+                pub min_size: u32,
+                /// The size in bytes of the message on the wire, if none of its arguments vary in length
+                pub fixed_size: ::std::option::Option<u32>
+            }
+            /// An interface implemented by this module's bindings, listed in `IMPLEMENTATIONS`
+            #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone)]
+            pub struct ImplementationInfo {
+                pub interface: &'static str,
+                /// The highest version the binding supports
+                pub version: u32,
+                /// The implementation as written in the binding
+                pub implementor: &'static str,
+                pub requests: usize,
+                pub events: usize
+            }
+            /// The interfaces implemented by this module in specification order
+            pub const IMPLEMENTATIONS: &'static [ImplementationInfo] = &[#(#implementations),*];
+            /// Events serialized by the `_into` event methods, sent together in a single write where the module uses the
+            /// runtime's `send_all`
+            #[derive(::std::default::Default)]
+            pub struct EventBatch {
+                messages: ::std::vec::Vec<#wl::Message>,
+                /// The first event rejected before it was serialized, failing the whole batch
+                error: ::std::option::Option<#wl::DispatchError>
+            }
+            impl EventBatch {
+                pub fn new() -> Self {
+                    Self::default()
+                }
+                pub fn push(&mut self, message: #wl::Message) {
+                    self.messages.push(message)
+                }
+                /// Fail the batch with `error` when it is sent, so that none of its events are written. Only the first
+                /// error is kept
+                pub fn reject(&mut self, error: #wl::DispatchError) {
+                    self.error.get_or_insert(error);
+                }
+                pub fn len(&self) -> usize {
+                    self.messages.len()
+                }
+                pub fn is_empty(&self) -> bool {
+                    self.messages.is_empty()
+                }
+                /// Send every collected event to the client, stopping at the first that fails
+                pub fn send(self, client: &mut #wl::server::Client) -> #wl::server::Result<()> {
+                    if let ::std::option::Option::Some(error) = self.error {
+                        return ::std::result::Result::Err(error.into())
+                    }
+                    #batch_suppression
+                    #batch_send
+                }
+            }
+            #classify_send
+            #check_event_string
+            #suppress_events
+            #receiver_or
             #(#interfaces)*
+            #(#metadata)*
+            #(#documentation)*
             #(#enums)*
+            #raw_entries
+            #enum_mappings
+            #(#enum_alias_items)*
+            #display_helpers
+            #not_implemented
+            #construct
+            #create
+            #bound_version
+            #negotiate
+            #interface_of
+            #broadcasts
+            #wire_dump
+            #stats
+            #mock
+            #roundtrip_tests
+            #api_snapshot
+            #prelude
+            #(#module_items)*
         }
+    }
+}
+
+/// Checks an implementation of a generated interface trait against the protocol, reporting requests that are missing or
+/// unknown with their expected signatures. The implementation is otherwise passed through unchanged.
+///
+/// The arguments name the protocol specification as for `server_protocol`, e.g. `#[dispatch("wayland.toml")]`. Under
+/// `#![split_traits]` it applies to the requests and events traits alike, checking only the methods each declares. As
+/// the bindings are not known to it, methods must have their snake_case names rather than those given by `rename`, and
+/// every request must be implemented even where the binding's `default_error` option would answer it.
+#[proc_macro_attribute]
+pub fn dispatch(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let attribute = parse_macro_input!(attr as ProtocolAttribute);
+    let implementation = parse_macro_input!(item as ItemImpl);
+    let errors = match attribute.load() {
+        Ok((_, protocol)) => dispatch_errors(&protocol, &implementation),
+        Err(error) => vec![error]
+    };
+    let errors = errors.iter().map(syn::Error::to_compile_error);
+    quote! {
+        #(#errors)*
+        #implementation
     }.into()
 }
+fn dispatch_errors(protocol: &Protocol, implementation: &ItemImpl) -> Vec<syn::Error> {
+    let trait_path = match &implementation.trait_ {
+        Some((_, path, _)) => path,
+        None => return vec![syn::Error::new(implementation.self_ty.span(), "#[dispatch] applies to implementations of generated interface traits")]
+    };
+    let trait_name = match trait_path.segments.last() {
+        Some(segment) => segment.ident.to_string(),
+        None => return Vec::new()
+    };
+    // Under the `split_traits` flag the request handlers and event senders are implemented separately
+    let interface_of = |suffix: &str| protocol.interfaces.iter().find(|interface| format!("{}{}", camel_case(&interface.name), suffix) == trait_name);
+    let (interface, has_requests, has_events) = match (interface_of(""), interface_of("Requests"), interface_of("Events")) {
+        (Some(interface), _, _) => (interface, true, true),
+        (None, Some(interface), _) => (interface, true, false),
+        (None, None, Some(interface)) => (interface, false, true),
+        (None, None, None) => return vec![syn::Error::new(trait_path.span(), format!("`{}` is not generated for any interface of protocol {:?}", trait_name, protocol.name))]
+    };
+    let methods: Vec<&syn::Ident> = implementation.items.iter().filter_map(|item| match item {
+        syn::ImplItem::Method(method) => Some(&method.sig.ident),
+        _ => None
+    }).collect();
+    let is_implemented = |name: &str| methods.iter().any(|method| method.unraw() == snake_case(name));
+    let mut errors = Vec::new();
+    let missing: Vec<String> = interface.requests.iter()
+        .filter(|request| has_requests && !is_implemented(&request.name))
+        .map(request_signature)
+        .collect();
+    if !missing.is_empty() {
+        errors.push(syn::Error::new(trait_path.span(), format!("Missing requests of interface {:?}:\n{}{}", interface.name, missing.join("\n"), interface.error_context())));
+    }
+    let provided = interface.requests.iter().filter(|_| has_requests).map(|request| snake_case(&request.name))
+        .chain(interface.events.iter().filter(|_| has_events).flat_map(|event| vec![snake_case(&event.name), format!("{}_into", snake_case(&event.name))]))
+        .chain(frame_guard_type(interface).filter(|_| has_events).map(|_| "begin_frame".to_string()))
+        .collect::<Vec<_>>();
+    let requests = interface.requests.iter().filter(|_| has_requests).map(|request| snake_case(&request.name)).collect::<Vec<_>>();
+    let events = interface.events.iter().filter(|_| has_events).map(|event| snake_case(&event.name)).collect::<Vec<_>>();
+    let mut listing = String::new();
+    if !requests.is_empty() {
+        listing += &format!(". Its requests are: {}", requests.join(", "));
+    }
+    if !events.is_empty() {
+        listing += &format!(". Its events are: {}", events.join(", "));
+    }
+    for method in methods {
+        if !provided.contains(&method.unraw().to_string()) {
+            errors.push(syn::Error::new(method.span(), format!("`{}` is not a request or event of interface {:?}{}{}", method.unraw(), interface.name, listing, interface.error_context())));
+        }
+    }
+    errors
+}
+/// The signature of a request method as it would be written in an implementation
+fn request_signature(request: &Request) -> String {
+    let parameters = request.args.iter().map(|arg| format!(", wl_{}: {}", snake_case(&arg.name), arg.signature_type())).collect::<String>();
+    format!("    fn {}(&mut self, client: &mut Client{}) -> Result<()>", ident(&snake_case(&request.name)), parameters)
+}
 
-fn generate_interface(interface: &Interface, bindings: &HashMap<String, Binding>) -> TokenStream {
-    let interface_name = format_ident!("{}", interface.name.to_camel_case());
-    let interface_description = interface.description.iter();
-    let interface_version = interface.version;
-    let interface_string = &interface.name;
-    let implementor_struct = &bindings[interface_string].implementation;
-    let events = interface.events.iter().enumerate().map(|(opcode, event)| generate_event(event, interface, opcode as u16));
-    let requests = interface.requests.iter().map(|request| generate_request(request, interface, bindings));
-    let request_dispatch = interface.requests.iter().enumerate().map(|(opcode, request)| generate_request_dispatch(request, opcode as u16, interface, bindings));
-    quote!{
-        #(#[doc = #interface_description])*
-        pub trait #interface_name: ::wl::Object {
-            const VERSION: u32 = #interface_version;
-            const INTERFACE: &'static str = #interface_string;
-            #(#events)*
-            #(#requests)*
-        }
-        impl ::wl::server::Dispatch for #implementor_struct {
-            const INTERFACE: &'static str = #interface_string;
-            const VERSION: u32 = #interface_version;
-            fn dispatch(lease: ::wl::server::Lease<dyn ::std::any::Any>, client: &mut ::wl::server::Client, message: ::wl::Message) -> ::wl::server::Result<()> {
-                use ::wl::Object;
-                use ::std::convert::Into;
-                let mut lease: ::wl::server::Lease<#implementor_struct> = lease.downcast().unwrap();
-                let mut args = message.args();
-                match message.opcode {
-                    #(#request_dispatch)*
-                    _ => ::std::result::Result::Err(::wl::DispatchError::InvalidRequest {
-                        opcode: message.opcode,
-                        object: lease.object(),
-                        interface: Self::INTERFACE
-                    }.into())
+/// Detect generated identifiers that are unusable keywords or collide after case conversion, naming the protocol items
+/// responsible
+fn identifier_errors(module: &Ident, interfaces: &[&Interface], enum_interfaces: &[(&Interface, Vec<&Enum>)], bindings: &HashMap<String, Binding>, options: &ModuleOptions) -> Vec<syn::Error> {
+    fn usable(errors: &mut Vec<syn::Error>, ident: &str, item: &str) {
+        if is_unusable(ident) {
+            errors.push(syn::Error::new(Span::call_site(), format!("Generated identifier `{}` for {} is a keyword that cannot be a raw identifier", ident, item)));
+        }
+    }
+    fn claim(names: &mut HashMap<String, String>, errors: &mut Vec<syn::Error>, ident: String, item: String) {
+        usable(errors, &ident, &item);
+        if let Some(existing) = names.get(&ident) {
+            errors.push(syn::Error::new(Span::call_site(), format!("Generated identifier `{}` for {} collides with {}", ident, item, existing)));
+        } else {
+            names.insert(ident, item);
+        }
+    }
+    let mut errors = Vec::new();
+    let mut types = HashMap::new();
+    claim(&mut types, &mut errors, "MessageInfo".into(), "the introspection struct".into());
+    claim(&mut types, &mut errors, "EventBatch".into(), "the event batch struct".into());
+    claim(&mut types, &mut errors, "ImplementationInfo".into(), "the implementation introspection struct".into());
+    claim(&mut types, &mut errors, "prelude".into(), "the prelude module".into());
+    claim(&mut types, &mut errors, "Construct".into(), "the construction trait".into());
+    claim(&mut types, &mut errors, "BoundVersion".into(), "the bound version trait".into());
+    if options.delegate.is_none() && options.runtime.create {
+        claim(&mut types, &mut errors, "Implementation".into(), "the implementation trait".into());
+        claim(&mut types, &mut errors, "sealed".into(), "the sealed implementation module".into());
+        claim(&mut types, &mut errors, "create".into(), "the object creation function".into());
+        claim(&mut types, &mut errors, "create_new".into(), "the constructing object creation function".into());
+    }
+    claim(&mut types, &mut errors, "negotiate".into(), "the version negotiation function".into());
+    claim(&mut types, &mut errors, "interface_of".into(), "the untyped object interface function".into());
+    claim(&mut types, &mut errors, "check_event_string".into(), "the event string check function".into());
+    claim(&mut types, &mut errors, "BroadcastError".into(), "the broadcast error struct".into());
+    claim(&mut types, &mut errors, "BroadcastFailure".into(), "the broadcast failure struct".into());
+    claim(&mut types, &mut errors, "mock".into(), "the mock module".into());
+    claim(&mut types, &mut errors, "raw".into(), "the raw enum entries module".into());
+    if allows_self(interfaces) {
+        claim(&mut types, &mut errors, "ReceiverOr".into(), "the receiver argument enum".into());
+    }
+    if options.skip_dead_clients.is_some() {
+        claim(&mut types, &mut errors, "SuppressedEvents".into(), "the suppressed event count struct".into());
+        claim(&mut types, &mut errors, "suppress_events".into(), "the dead client check function".into());
+        claim(&mut types, &mut errors, "suppressed_events".into(), "the suppressed event count function".into());
+    }
+    if options.stats {
+        claim(&mut types, &mut errors, "StatsSnapshot".into(), "the statistics snapshot struct".into());
+        claim(&mut types, &mut errors, "MessageCount".into(), "the message count struct".into());
+    }
+    for interface in interfaces {
+        let reported = errors.len();
+        claim(&mut types, &mut errors, camel_case(&interface.name), format!("interface {:?}", interface.name));
+        claim(&mut types, &mut errors, snake_case(&interface.name), format!("the metadata module of interface {:?}", interface.name));
+        claim(&mut types, &mut errors, as_trait(interface), format!("the conversion trait of interface {:?}", interface.name));
+        if options.split_traits {
+            claim(&mut types, &mut errors, format!("{}Requests", camel_case(&interface.name)), format!("the request trait of interface {:?}", interface.name));
+            claim(&mut types, &mut errors, format!("{}Events", camel_case(&interface.name)), format!("the event trait of interface {:?}", interface.name));
+        }
+        if let Some(field_events) = field_events_trait(interface) {
+            claim(&mut types, &mut errors, field_events, format!("the field event trait of interface {:?}", interface.name));
+        }
+        if let Some(frame_guard) = frame_guard_type(interface) {
+            claim(&mut types, &mut errors, frame_guard, format!("the frame guard of interface {:?}", interface.name));
+        }
+        for event in interface.events.iter().filter(|event| broadcasts(event)) {
+            claim(&mut types, &mut errors, broadcast_function(interface, event), format!("the broadcast function of event \"{}.{}\"", interface.name, event.name));
+        }
+        if options.delegate.is_some() {
+            claim(&mut types, &mut errors, dispatch_function(interface), format!("the dispatch function of interface {:?}", interface.name));
+        }
+        let binding = &bindings[&camel_case(&interface.name)];
+        for (name, _) in &binding.options.renames {
+            if !interface.requests.iter().any(|request| name.unraw() == request.name) && !interface.events.iter().any(|event| name.unraw() == event.name) {
+                let requests = interface.requests.iter().map(|request| request.name.as_str()).collect::<Vec<_>>().join(", ");
+                let events = interface.events.iter().map(|event| event.name.as_str()).collect::<Vec<_>>().join(", ");
+                errors.push(syn::Error::new(name.span(), format!("Cannot rename `{}` as it is not a request or event of interface {:?}. Its requests are: {}. Its events are: {}", name.unraw(), interface.name, requests, events)));
+            }
+        }
+        if let Some(version) = &binding.options.version {
+            if !(1..=interface.version).contains(&version.base10_parse().unwrap_or(0)) {
+                errors.push(syn::Error::new(version.span(), format!("Interface {:?} has versions 1 to {}, so its binding cannot support version {}", interface.name, interface.version, version)));
+            }
+        }
+        let mut methods = HashMap::new();
+        let mut encoders = HashMap::new();
+        for request in &interface.requests {
+            claim(&mut methods, &mut errors, binding.method_name(&request.name), format!("request \"{}.{}\"", interface.name, request.name));
+            claim(&mut encoders, &mut errors, format!("encode_{}", snake_case(&request.name)), format!("the encoder of request \"{}.{}\"", interface.name, request.name));
+        }
+        // Requests and events are methods of separate traits under `split_traits`
+        if options.split_traits {
+            methods.clear();
+        }
+        if frame_guard_type(interface).is_some() {
+            claim(&mut methods, &mut errors, "begin_frame".into(), format!("the frame method of interface {:?}", interface.name));
+        }
+        for event in &interface.events {
+            claim(&mut methods, &mut errors, binding.method_name(&event.name), format!("event \"{}.{}\"", interface.name, event.name));
+            claim(&mut methods, &mut errors, format!("{}_into", binding.method_name(&event.name)), format!("the batching method of event \"{}.{}\"", interface.name, event.name));
+            if event.since_version() > 1 {
+                claim(&mut methods, &mut errors, format!("try_{}", binding.method_name(&event.name)), format!("the version-checked method of event \"{}.{}\"", interface.name, event.name));
+            }
+            claim(&mut encoders, &mut errors, format!("encode_{}", snake_case(&event.name)), format!("the encoder of event \"{}.{}\"", interface.name, event.name));
+        }
+        let context = interface.error_context();
+        if !context.is_empty() {
+            for error in &mut errors[reported..] {
+                *error = syn::Error::new(error.span(), format!("{}{}", error, context));
+            }
+        }
+    }
+    let mut raw_modules = HashMap::new();
+    for (interface, enums) in enum_interfaces {
+        for e in enums {
+            claim(&mut types, &mut errors, format!("{}{}", camel_case(&interface.name), camel_case(&e.name)), format!("enum \"{}.{}\"", interface.name, e.name));
+            claim(&mut raw_modules, &mut errors, raw_module(interface, e), format!("the raw entries of enum \"{}.{}\"", interface.name, e.name));
+            let mut entries = HashMap::new();
+            claim(&mut entries, &mut errors, "ENUM_NAME".into(), "the ENUM_NAME constant".into());
+            for entry in &e.entries {
+                claim(&mut entries, &mut errors, normalise_entry_name(e, &entry.name), format!("entry \"{}.{}.{}\"", interface.name, e.name, entry.name));
+            }
+            for entry in e.entries.iter().filter(|_| options.legacy_entry_names) {
+                if let Some(legacy) = legacy_entry_name(interface, &entry.name) {
+                    claim(&mut entries, &mut errors, legacy, format!("the legacy name of entry \"{}.{}.{}\"", interface.name, e.name, entry.name));
                 }
             }
         }
     }
+    // Bindings are resolved inside the generated module, where a bare name refers to the generated item rather than a
+    // type of the same name outside the module
+    for binding in bindings.values().filter(|binding| !binding.is_external && !binding.doc_only) {
+        let name = match binding.implementation.get_ident() {
+            Some(name) => name.to_string(),
+            None => continue
+        };
+        if let Some(item) = types.get(&name) {
+            errors.push(syn::Error::new(binding.implementation.span(), format!("The implementation of {} names {} generated in this module. It must be a type defined outside the generated module, e.g. `crate::{}`, or be renamed", binding.interface, item, name)));
+        }
+    }
+    // A module named like an item generated inside it, such as `wl_surface` holding the `WlSurface` trait and the
+    // `wl_surface` metadata module, leads to ambiguous imports and confusing duplicate name errors in the code using it
+    let module_name = snake_case(&module.unraw().to_string());
+    let mut shared: Vec<&String> = types.iter()
+        .filter(|(ident, _)| snake_case(ident) == module_name)
+        .map(|(_, item)| item)
+        .collect();
+    shared.sort();
+    if !shared.is_empty() {
+        let shared = shared.iter().map(|item| item.as_str()).collect::<Vec<_>>().join(" and ");
+        errors.push(syn::Error::new(module.span(), format!("The module `{}` is named like {}, generated inside it. Please name the module differently, e.g. `{}_protocol`", module, shared, module_name)));
+    }
+    errors
 }
 
-fn generate_event(event: &Event, interface: &Interface, opcode: u16) -> TokenStream {
-    let event_name = format_ident!("r#{}", event.name.to_snake_case());
-    let event_summary = event.summary.iter();
-    let event_description = event.description.iter();
-    let parameters = event.args.iter().map(|arg| generate_event_parameter(arg));
-    let debug_print = generate_event_debug_print(event, interface);
-    let arg_pushers = event.args.iter().map(|arg| arg.pusher());
+/// The `stats` function copying out the message counters of every interface under the module's `stats` flag
+fn generate_stats(interfaces: &[&Interface]) -> TokenStream {
+    let names = interfaces.iter().map(|interface| &interface.name);
+    let metadata_modules: Vec<_> = interfaces.iter().map(|interface| ident(&snake_case(&interface.name))).collect();
     quote! {
-        #(#[doc = #event_summary])*
-        #[doc = "\n"]
-        #(#[doc = #event_description])*
-        fn #event_name(&mut self, client: &mut ::wl::server::Client, #(#parameters),*) -> ::wl::server::Result<()> {
-            use ::wl::Object;
-            if *::wl::DEBUG {
-                #debug_print
+        /// The number of times a request was dispatched or an event was sent
+        #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone)]
+        pub struct MessageCount {
+            pub interface: &'static str,
+            pub name: &'static str,
+            pub opcode: u16,
+            pub count: u64
+        }
+        /// The message counts of every interface, copied out by [`stats`]
+        #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::default::Default)]
+        pub struct StatsSnapshot {
+            /// Requests dispatched, in specification order
+            pub requests: ::std::vec::Vec<MessageCount>,
+            /// Events sent, in specification order
+            pub events: ::std::vec::Vec<MessageCount>
+        }
+        /// Copy out the number of requests dispatched and events sent by each interface since the program started
+        pub fn stats() -> StatsSnapshot {
+            fn counts(interface: &'static str, messages: &'static [MessageInfo], counters: &'static [::std::sync::atomic::AtomicU64]) -> impl ::std::iter::Iterator<Item = MessageCount> {
+                messages.iter().zip(counters).map(move |(message, counter)| MessageCount {
+                    interface,
+                    name: message.name,
+                    opcode: message.opcode,
+                    count: counter.load(::std::sync::atomic::Ordering::Relaxed)
+                })
             }
-            let mut message = ::wl::Message::new(self.object(), #opcode);
-            #(#arg_pushers;)*
-            client.send(message)
+            let mut snapshot = StatsSnapshot::default();
+            #(
+                snapshot.requests.extend(counts(#names, #metadata_modules::REQUESTS, &#metadata_modules::REQUESTS_DISPATCHED));
+                snapshot.events.extend(counts(#names, #metadata_modules::EVENTS, &#metadata_modules::EVENTS_SENT));
+            )*
+            snapshot
         }
     }
 }
-fn generate_event_parameter(arg: &Arg) -> TokenStream {
-    let arg_name = format_ident!("wl_{}", arg.name.to_snake_case());
-    let arg_type = arg.event_data_type();
-    quote! {
-        #arg_name: #arg_type
+/// Bindings written by a CamelCase name shared by several interfaces, such as `zwp_tablet_v2` and `zwp_tablet_v_2`, that
+/// do not name the interface they implement
+fn ambiguous_binding_errors(protocol: &Protocol, bindings: &HashMap<String, Binding>) -> Vec<syn::Error> {
+    let mut interfaces: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for interface in &protocol.interfaces {
+        interfaces.entry(camel_case(&interface.name)).or_default().push(&interface.name);
     }
+    interfaces.iter()
+        .filter(|(_, names)| names.len() > 1)
+        .filter_map(|(camel, names)| bindings.get(camel).filter(|binding| binding.interface_name.is_none()).map(|binding| (binding, names)))
+        .map(|(binding, names)| {
+            let listed = names.iter().map(|name| format!("{:?}", name)).collect::<Vec<_>>().join(" and ");
+            let message = format!("{} is the CamelCase name of interfaces {}. Name the interface being bound, e.g. `#[interface = {:?}]`", binding.interface, listed, names[0]);
+            syn::Error::new(binding.interface.span(), message)
+        })
+        .collect()
 }
-fn generate_event_debug_print(event: &Event, interface: &Interface) -> TokenStream {
-    let interface_name = &interface.name;
-    let event_name = &event.name;
-    let args = event.args.iter().map(|arg| {
-        let arg_name = format_ident!("wl_{}", arg.name);
-        quote!{#arg_name}
-    });
-    let mut format_string = "-> {}@{}.{}(".to_string();
-    let mut first = true;
-    for arg in &event.args {
-        if !first  {
-            format_string.push_str(", ")
-        } else {
-            first = false
+/// Report each object or `new_id` argument of a bound interface's request whose interface has no binding, once for each
+/// interface depending on it, with the chain of bound interfaces leading to it so that the binding missing is found
+/// without following each dependency by hand
+fn missing_dependency_errors(interfaces: &[&Interface], bindings: &HashMap<String, Binding>) -> Vec<syn::Error> {
+    let dependencies = Dependencies::new(interfaces);
+    let mut errors = Vec::new();
+    for interface in interfaces {
+        let mut missing: Vec<&str> = Vec::new();
+        for arg in interface.requests.iter().flat_map(|request| &request.args) {
+            let depends = match arg.kind {
+                DataType::Object => !arg.is_loose(&interface.name, bindings),
+                DataType::NewId => true,
+                _ => false
+            };
+            match &arg.interface {
+                Some(dependency) if depends && binding_of(bindings, dependency).is_none() && !missing.contains(&dependency.as_str()) => missing.push(dependency),
+                _ => ()
+            }
+        }
+        for dependency in missing {
+            let owner = camel_case(&interface.name);
+            let to_implement = camel_case(dependency);
+            let mut chain = dependencies.chain(&interface.name);
+            chain.push(dependency);
+            let message = format!("Interface {:?} depends on {:?}, which is missing a binding: {}. Please specify an implementation for {:?}.", owner, to_implement, chain.join(" → "), to_implement);
+            errors.push(syn::Error::new(bindings[&owner].implementation.span(), message));
         }
-        format_string.push_str(arg.debug_string());
     }
-    format_string.push(')');
+    errors
+}
+/// The `Construct` trait building implementations for new objects, by `Default` or by the `init` function of a binding
+fn generate_construct(interfaces: &[&Interface], bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let inits = interfaces.iter().map(|interface| &bindings[&camel_case(&interface.name)]).filter_map(|binding| {
+        let init = binding.options.init.as_ref()?;
+        let implementation = binding.implementation_type();
+        // Spanned at the binding so that a function of the wrong signature is reported there
+        let shim = quote_spanned!{init.span()=> let init: fn(&mut #wl::server::Client, #wl::NewId) -> Self = #init; };
+        Some(quote! {
+            impl Construct for #implementation {
+                fn construct(client: &mut #wl::server::Client, id: #wl::NewId) -> Self {
+                    #shim
+                    init(client, id)
+                }
+            }
+        })
+    });
     quote! {
-        ::std::eprintln!(#format_string, #interface_name, self.object(), #event_name, #(#args),*)
+        /// Constructs the implementation of an object created by a `new_id` argument
+        pub trait Construct: ::std::marker::Sized {
+            fn construct(client: &mut #wl::server::Client, id: #wl::NewId) -> Self;
+        }
+        impl<T: ::std::default::Default> Construct for T {
+            fn construct(_: &mut #wl::server::Client, _: #wl::NewId) -> Self {
+                ::std::default::Default::default()
+            }
+        }
+        #(#inits)*
     }
 }
-fn generate_request(request: &Request, interface: &Interface, bindings: &HashMap<String, Binding>) -> TokenStream {
-    let request_name = format_ident!("r#{}", request.name.to_snake_case());
-    let request_summary = request.summary.iter();
-    let request_description = request.description.iter();
-    let owning_interface = &interface.name.to_snake_case();
-    let parameters = request.args.iter().map(|arg| generate_parameter(arg, owning_interface, bindings));
+/// The `create` function inserting new objects with `Client::create`, limited by the sealed `Implementation` trait to
+/// the implementations of the module's bindings
+fn generate_create(wl: &Path) -> TokenStream {
     quote! {
-        #(#[doc = #request_summary])*
-        #[doc = "\n"]
-        #(#[doc = #request_description])*
-        fn #request_name(&mut self, client: &mut ::wl::server::Client, #(#parameters),*) -> ::wl::server::Result<()>;
+        /// Implemented for the implementation of each interface bound by this module, which are the only types `create`
+        /// accepts
+        pub trait Implementation: #wl::server::Dispatch + sealed::Sealed {}
+        mod sealed {
+            pub trait Sealed {}
+        }
+        /// Insert `value` into the client as the object created by `id`, at the version `id` was created with, returning
+        /// its lease so that initial events may be sent on it immediately
+        pub fn create<T: Implementation + ::std::any::Any>(client: &mut #wl::server::Client, id: #wl::NewId, value: T) -> #wl::server::Result<#wl::server::Lease<T>> {
+            client.create(id, value)
+        }
+        /// Construct the implementation of the object created by `id` with `Construct`, by `Default` or the binding's
+        /// `init` function, and insert it as `create` does
+        pub fn create_new<T: Implementation + Construct + ::std::any::Any>(client: &mut #wl::server::Client, id: #wl::NewId) -> #wl::server::Result<#wl::server::Lease<T>> {
+            let value = T::construct(client, id);
+            client.create(id, value)
+        }
     }
 }
-fn generate_parameter(arg: &Arg, owning_interface: &String, bindings: &HashMap<String, Binding>) -> TokenStream {
-    let arg_name = format_ident!("wl_{}", arg.name.to_snake_case());
-    let arg_type = arg.request_data_type(owning_interface, bindings);
+/// The `BoundVersion` trait giving the version of an object to the version-checked event methods, implemented for
+/// leases where the runtime provides `Lease::version`
+fn generate_bound_version(lease_version: bool, wl: &Path) -> TokenStream {
+    let lease_impl = lease_version.then(|| quote! {
+        impl<T: ?::std::marker::Sized> BoundVersion for #wl::server::Lease<T> {
+            fn bound_version(&self) -> u32 {
+                self.version()
+            }
+        }
+    });
     quote! {
-        #arg_name: #arg_type
+        /// The version an object was bound at, compared by the `try_` event methods to the version each event was
+        /// introduced in. Types other than leases sending events, such as handles, implement it to use those methods
+        pub trait BoundVersion {
+            fn bound_version(&self) -> u32;
+        }
+        #lease_impl
     }
 }
-fn generate_request_dispatch(request: &Request, opcode: u16, interface: &Interface, bindings: &HashMap<String, Binding>) -> TokenStream {
-    let mut request_name = format_ident!("r#{}", request.name.to_snake_case());
-    let interface_string = &interface.name;
-    request_name.set_span(bindings[interface_string].implementation.span());
-    let arg_names = request.args.iter().map(|arg| format_ident!("wl_{}", arg.name.to_snake_case()));
-    let arg_getters = request.args.iter().map(|arg| generate_arg_getter(arg, interface_string, bindings));
-    let debug_print = generate_request_debug_print(request, interface);
+/// The `negotiate` function clamping the version a client requests when binding a global to the version its binding
+/// supports
+fn generate_negotiate(interfaces: &[&Interface], bindings: &HashMap<String, Binding>) -> TokenStream {
+    let names = interfaces.iter().map(|interface| &interface.name);
+    let versions = interfaces.iter().map(|interface| bindings[&camel_case(&interface.name)].supported_version(interface));
+    quote! {
+        /// The version to create an object of `interface` with when a client requests `requested`, the lower of the
+        /// requested version and the highest version the interface's binding supports. `None` if the interface is not
+        /// bound by this module
+        pub fn negotiate(interface: &str, requested: u32) -> ::std::option::Option<u32> {
+            match interface {
+                #(#names => ::std::option::Option::Some(::std::cmp::min(requested, #versions)),)*
+                _ => ::std::option::Option::None
+            }
+        }
+    }
+}
+/// The `interface_of` function naming the interface of an untyped object by the type of its implementation
+fn generate_interface_of(interfaces: &[&Interface], bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let names = interfaces.iter().map(|interface| &interface.name);
+    let implementations = interfaces.iter().map(|interface| bindings[&camel_case(&interface.name)].implementation_type());
+    quote! {
+        /// The interface of an object received untyped, found by the type of its implementation, so that it may be
+        /// matched by name and downcast once. `None` if its type is not the implementation of any binding in this
+        /// module. A type bound to several interfaces is named as the first of them
+        pub fn interface_of(lease: &#wl::server::Lease<dyn ::std::any::Any>) -> ::std::option::Option<&'static str> {
+            static INTERFACES: &[(fn() -> ::std::any::TypeId, &str)] = &[#((::std::any::TypeId::of::<#implementations>, #names)),*];
+            let type_id = ::std::any::Any::type_id(&**lease);
+            INTERFACES.iter()
+                .find(|(implementation, _)| implementation() == type_id)
+                .map(|(_, interface)| *interface)
+        }
+    }
+}
+/// Compile-time checks of the runtime's types where a change in representation would corrupt the wire format rather
+/// than fail to build
+fn generate_runtime_assertions(wl: &Path) -> TokenStream {
+    quote! {
+        // `assert!` with a message expands to a bare `panic!` in older editions, which an item of the module may shadow
+        const _: () = if !(::std::mem::size_of::<#wl::Fixed>() == 4 && ::std::mem::align_of::<#wl::Fixed>() <= 4) {
+            ::std::panic!("The runtime's Fixed must be a single 24.8 fixed point word")
+        };
+        // Opcodes are matched and built as 16 bit values
+        const _: fn(&#wl::Message) -> u16 = |message| message.opcode;
+        // `new_id` arguments are received and sent as the runtime's `NewId`, checked against the interface created
+        const _: fn(&#wl::Message, &'static str, u32) -> #wl::server::Result<#wl::NewId> = |message, interface, version| message.args().next_new_id(interface, version);
+        const _: fn(&mut #wl::Message, #wl::NewId) = |message, id| { message.push_new_id(id); };
+        // File descriptors are passed out of band, received from the client and sent alongside a message as files
+        const _: fn(&mut #wl::server::Client) -> #wl::server::Result<::std::fs::File> = |client| client.next_file();
+        const _: fn(&mut #wl::Message, &::std::fs::File) = |message, file| { message.push_file(file); };
+    }
+}
+/// Include the bytes of every specification file read, including those included by others, so that the compiler
+/// rebuilds the module when any of them changes. The paths are made absolute as `include_bytes!` resolves relative
+/// paths against the source file rather than the directory the macro read them from
+fn generate_file_tracking(files: &[std::path::PathBuf]) -> TokenStream {
+    let paths = files.iter().filter_map(|file| std::fs::canonicalize(file).ok()).map(|file| file.display().to_string());
+    quote! {
+        #(const _: &[u8] = ::std::include_bytes!(#paths);)*
+    }
+}
+/// The `wl_display` binding, if the display is implemented by this module
+fn display_binding(bindings: &HashMap<String, Binding>) -> Option<&Binding> {
+    bindings.get("WlDisplay").filter(|binding| !binding.is_external)
+}
+/// Helpers for error posting and id recycling that route through the bound `wl_display` implementation
+fn generate_display_helpers(bindings: &HashMap<String, Binding>, runtime: RuntimeOptions, wl: &Path) -> TokenStream {
+    let (display, error, delete_id) = match display_binding(bindings) {
+        Some(binding) => {
+            let implementation = binding.implementation_type();
+            // Under the `delegate` flag the display is borrowed through the same function as requests dispatched to it
+            let display = match &binding.options.delegate {
+                Some(resolve) => quote!{ let mut display = #resolve::<#implementation>(client, 1)?; },
+                None => quote!{ let mut display: #wl::server::Lease<#implementation> = client.get(1)?; }
+            };
+            (display, ident(&binding.method_name("error")), ident(&binding.method_name("delete_id")))
+        },
+        None => return quote!{}
+    };
+    // Errors only raised by generated code using the runtime options adding them
+    let optional_errors = [
+        (runtime.duplicate_objects, quote!{ #wl::DispatchError::DuplicateObject { request, object } => (0, ::std::format!("object {} passed more than once to {}", object, request)), }),
+        (runtime.argument_limits, quote!{ #wl::DispatchError::ArgumentTooLong { request, argument, limit } => (1, ::std::format!("argument {} of {} is longer than {} bytes", argument, request, limit)), }),
+        (runtime.argument_ranges, quote!{ #wl::DispatchError::ArgumentOutOfRange { request, argument, value, min, max } => (1, ::std::format!("argument {} of {} is {}, outside of {} to {}", argument, request, value, min, max)), }),
+        (runtime.malformed_strings, quote!{ #wl::DispatchError::MalformedString { request, argument, .. } => (1, ::std::format!("argument {} of {} is not a nul-terminated UTF-8 string", argument, request)), }),
+        (runtime.access_denied, quote!{ #wl::DispatchError::AccessDenied { request, .. } => (1, ::std::format!("{} may only be sent by privileged clients", request)), }),
+        // Errors of the object's own interface, raised by handlers with `protocol_error`
+        (runtime.protocol_errors, quote!{ #wl::DispatchError::Protocol { code, message, .. } => (*code, message.clone()), })
+    ];
+    let optional_errors = optional_errors.iter().filter(|(enabled, _)| *enabled).map(|(_, arm)| arm);
+    quote! {
+        /// Post a `wl_display.error` event for `object`, using an error code from the object's interface
+        pub fn post_error<E: ::std::convert::Into<u32>>(client: &mut #wl::server::Client, object: &dyn #wl::Object, code: E, message: &str) -> #wl::server::Result<()> {
+            #display
+            display.#error(client, object, code.into(), message)
+        }
+        /// Notify the client that `id` has been destroyed and may be reused
+        pub fn delete_id(client: &mut #wl::server::Client, id: u32) -> #wl::server::Result<()> {
+            #display
+            display.#delete_id(client, id)
+        }
+        /// Post the `wl_display.error` event for an error raised by generated code or returned by a handler with
+        /// `protocol_error` while dispatching to `object`, returning whether it was reported. Other errors are left to
+        /// the caller
+        pub fn report(client: &mut #wl::server::Client, object: u32, error: &#wl::DispatchError) -> #wl::server::Result<bool> {
+            struct Id(u32);
+            impl #wl::Object for Id {
+                fn object(&self) -> u32 {
+                    self.0
+                }
+            }
+            // The `invalid_object` and `invalid_method` codes of `wl_display.error`
+            let (code, message) = match error {
+                #wl::DispatchError::InvalidRequest { opcode, object, interface } => (1, ::std::format!("invalid opcode {} for {}@{}", opcode, interface, object)),
+                #wl::DispatchError::NoVariant { name, variant } => (1, ::std::format!("{} is not a value of {}", variant, name)),
+                #wl::DispatchError::TrailingData { opcode, object, interface } => (1, ::std::format!("opcode {} for {}@{} has trailing data", opcode, interface, object)),
+                #wl::DispatchError::InvalidSince { opcode, object, interface, version } => (1, ::std::format!("opcode {} for {}@{} is not in version {}", opcode, interface, object, version)),
+                #(#optional_errors)*
+                _ => return ::std::result::Result::Ok(false)
+            };
+            #display
+            display.#error(client, &Id(object), code, &message)?;
+            ::std::result::Result::Ok(true)
+        }
+    }
+}
+
+/// The function posting `wl_display.error` for the requests of `default_error` bindings: through the bound display as
+/// `post_error` does, or where `wl_display` is not bound by encoding and sending the event as its event method would
+fn generate_not_implemented(protocol: &Protocol, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let binding = match bindings.values().find(|binding| binding.options.default_error) {
+        Some(binding) => binding,
+        None => return quote!{}
+    };
+    let send = if display_binding(bindings).is_some() {
+        quote!{ self::post_error(client, &Id(object), 1u32, message) }
+    } else {
+        // wl_display is always object 1 and error is its first event, so the event is known without the core protocol
+        let display = protocol.interfaces.iter()
+            .find(|interface| interface.name == "wl_display" && interface.events.first().map(|event| event.name == "error").unwrap_or(false))
+            .cloned()
+            .unwrap_or_else(Interface::display_errors);
+        let (display, event) = (&display, &display.events[0]);
+        let encoder = generate_encoder(&event.name, &event.args, 0, "Encode `wl_display.error`".to_string(), None, wl);
+        let arg_names: Vec<_> = event.args.iter().map(|arg| arg.parameter()).collect();
+        let (object_id, code, error_message) = (&arg_names[0], &arg_names[1], &arg_names[2]);
+        let string_checks = generate_event_string_checks(event, display, binding, quote! {
+            return ::std::result::Result::Err(error.into())
+        });
+        let debug_print = generate_debug(binding, quote!{ #wl::DEBUG }, generate_event_debug_print(event, display, quote!{ 1u32 }, wl));
+        let wire_dump = generate_event_wire_dump(display, binding, quote!{ 1u32 }, wl);
+        let dead_client_check = generate_dead_client_check(binding.options.skip_dead_clients, quote!{ 1 }, quote! {
+            return ::std::result::Result::Ok(())
+        });
+        let send = generate_send(event, display, binding, quote!{ client.send(message) });
+        quote! {
+            #encoder
+            let (#object_id, #code, #error_message) = (&Id(object) as &dyn #wl::Object, 1u32, message);
+            #dead_client_check
+            #string_checks
+            #debug_print
+            let message = encode_error(1 #(, #arg_names)*);
+            #wire_dump
+            #send
+        }
+    };
+    quote! {
+        /// Post `wl_display.error` with the `invalid_method` code for a request `object` received that its binding
+        /// leaves unimplemented under the `default_error` option
+        fn not_implemented(client: &mut #wl::server::Client, object: u32, message: &str) -> #wl::server::Result<()> {
+            struct Id(u32);
+            impl #wl::Object for Id {
+                fn object(&self) -> u32 {
+                    self.0
+                }
+            }
+            #send
+        }
+    }
+}
+/// Reject string event arguments containing a nul, which the wire format would read as the end of the string
+fn generate_check_event_string(wl: &Path) -> TokenStream {
+    quote! {
+        /// Check a string argument of an event before it is serialized, failing with `DispatchError::InteriorNul` if
+        /// it contains a nul, as the client would read the string as ending there
+        pub fn check_event_string(event: &'static str, argument: &'static str, value: &str) -> ::std::result::Result<(), #wl::DispatchError> {
+            if value.contains('\0') {
+                ::std::result::Result::Err(#wl::DispatchError::InteriorNul { event, argument })
+            } else {
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+}
+/// Checks of the string arguments of an event before it is encoded, running `reject` with the `error` of the first
+/// argument containing a nul. Omitted under the module's `unchecked_strings` flag, or where the runtime cannot report
+/// the error
+fn generate_event_string_checks(event: &Event, interface: &Interface, binding: &Binding, reject: TokenStream) -> TokenStream {
+    if binding.options.unchecked_strings || !binding.options.runtime.interior_nul {
+        return quote!{}
+    }
+    let event_string = format!("{}.{}", interface.name, event.name);
+    let checks = event.args.iter().filter(|arg| arg.kind == DataType::String).map(|arg| {
+        let arg_name = arg.parameter();
+        let arg_string = &arg.name;
+        quote! {
+            if let ::std::result::Result::Err(error) = self::check_event_string(#event_string, #arg_string, #arg_name) {
+                #reject
+            }
+        }
+    });
+    quote!{ #(#checks)* }
+}
+/// Count and trace the events dropped for clients marked dead under the module's `skip_dead_clients` flag
+fn generate_suppress_events(no_debug: bool, wl: &Path) -> TokenStream {
+    let trace = if no_debug {
+        quote!{}
+    } else {
+        quote! {
+            /// Traces the events dropped once, as the client's data is dropped along with the client, rather than a
+            /// line for each event
+            impl ::std::ops::Drop for SuppressedEvents {
+                fn drop(&mut self) {
+                    if *#wl::DEBUG {
+                        ::std::eprintln!("client marked dead, suppressed {} events", self.0);
+                    }
+                }
+            }
+        }
+    };
+    quote! {
+        /// The number of events dropped for a client marked dead, kept in the client's data
+        struct SuppressedEvents(u64);
+        #trace
+        /// Whether `events` for `client` are to be dropped as the runtime has marked the client dead, counting them if
+        /// so
+        fn suppress_events(client: &mut #wl::server::Client, events: usize) -> bool {
+            if !client.is_dead() {
+                return false
+            }
+            let mut suppressed = client.take_data::<SuppressedEvents>()
+                .unwrap_or_else(|_| ::std::boxed::Box::new(SuppressedEvents(0)));
+            suppressed.0 += events as u64;
+            client.put_data(suppressed);
+            true
+        }
+        /// The number of events this module's event methods have dropped for `client` since it was marked dead
+        pub fn suppressed_events(client: &mut #wl::server::Client) -> u64 {
+            match client.take_data::<SuppressedEvents>() {
+                ::std::result::Result::Ok(suppressed) => {
+                    let events = suppressed.0;
+                    client.put_data(suppressed);
+                    events
+                },
+                ::std::result::Result::Err(_) => 0
+            }
+        }
+    }
+}
+/// Leave with `exit` when `events` would be sent to a client marked dead, if the module's `skip_dead_clients` flag is
+/// set
+fn generate_dead_client_check(skip_dead_clients: bool, events: TokenStream, exit: TokenStream) -> TokenStream {
+    if skip_dead_clients {
+        quote! {
+            if self::suppress_events(client, #events) {
+                #exit
+            }
+        }
+    } else {
+        quote!{}
+    }
+}
+/// Report a failed send to a client that has hung up as `DispatchError::Disconnected`
+fn generate_classify_send(wl: &Path) -> TokenStream {
+    quote! {
+        /// Report a failed send to a client that has hung up as `DispatchError::Disconnected`, so that code sending
+        /// to many clients can skip it, while other failures are returned as they are
+        fn classify_send(result: #wl::server::Result<()>, client: &#wl::server::Client) -> #wl::server::Result<()> {
+            match result {
+                ::std::result::Result::Err(_) if client.is_disconnected() => ::std::result::Result::Err(#wl::DispatchError::Disconnected.into()),
+                result => result
+            }
+        }
+    }
+}
+/// Write a message to stderr as its header followed by a hex dump of its bytes in rows of 16 with an ASCII gutter
+fn generate_wire_dump(wl: &Path) -> TokenStream {
+    quote! {
+        /// Dump the wire encoding of a request (`<-`) or event (`->`) to stderr, as done for every message while
+        /// `wl::DEBUG_WIRE` is set
+        pub fn dump_wire(direction: &str, interface: &str, object: u32, message: &#wl::Message) {
+            let bytes = message.to_bytes();
+            ::std::eprintln!("{} {}@{} opcode {}, {} bytes", direction, interface, object, message.opcode, bytes.len());
+            for (row, chunk) in bytes.chunks(16).enumerate() {
+                let hex: ::std::vec::Vec<::std::string::String> = chunk.iter().map(|byte| ::std::format!("{:02x}", byte)).collect();
+                let ascii: ::std::string::String = chunk.iter().map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' }).collect();
+                ::std::eprintln!("    {:04x}  {:<47}  |{}|", row * 16, hex.join(" "), ascii);
+            }
+        }
+    }
+}
+
+/// Helpers that encode requests from their arguments and dispatch them, exercising argument decoding
+fn generate_mock(interfaces: &[&Interface], bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let interfaces = interfaces.iter().map(|interface| {
+        let interface_module = ident(&snake_case(&interface.name));
+        let binding = &bindings[&camel_case(&interface.name)];
+        let implementation = binding.implementation_type_in(2);
+        let dispatch = match binding.options.delegate {
+            Some(_) => {
+                let function = ident(&dispatch_function(interface));
+                quote!{ super::super::#function(client, object, message) }
+            },
+            None => quote! {
+                let lease = client.get_any(object)?;
+                <#implementation as #wl::server::Dispatch>::dispatch(lease, client, message)
+            }
+        };
+        let requests = interface.requests.iter().map(|request| {
+            let call = format_ident!("call_{}", snake_case(&request.name));
+            let parameters = request.args.iter().map(|arg| generate_event_parameter(arg, wl));
+            let encoder = format_ident!("encode_{}", snake_case(&request.name));
+            let arg_names = request.args.iter().map(|arg| arg.parameter());
+            let doc = format!("Dispatch `{}.{}` to `object` as though it were received from the client", interface.name, request.name);
+            quote! {
+                #[doc = #doc]
+                pub fn #call(client: &mut #wl::server::Client, object: u32, #(#parameters),*) -> #wl::server::Result<()> {
+                    let message = super::super::#interface_module::#encoder(object #(, #arg_names)*);
+                    #dispatch
+                }
+            }
+        });
+        quote! {
+            pub mod #interface_module {
+                #(#requests)*
+            }
+        }
+    });
+    quote! {
+        /// Request helpers for testing handlers through the generated argument decoding and dispatch
+        pub mod mock {
+            #(#interfaces)*
+        }
+    }
+}
+
+/// Tests encoding each event with representative values and decoding it again with the request argument getters.
+/// File descriptors are passed out of band so are skipped, while events creating objects are not tested as their new
+/// ids cannot be decoded without the client
+fn generate_roundtrip_tests(interfaces: &[&Interface], bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let tests = interfaces.iter().flat_map(|interface| interface.events.iter().enumerate().map(move |(opcode, event)| (interface, protocol::opcode(opcode), event)))
+        .filter(|(_, _, event)| event.args.iter().all(|arg| arg.kind != DataType::NewId))
+        .map(|(interface, opcode, event)| {
+            let test_name = format_ident!("{}_{}", snake_case(&interface.name), snake_case(&event.name));
+            let args: Vec<&Arg> = event.args.iter().filter(|arg| arg.kind != DataType::Fd).collect();
+            let cases = [false, true].iter().map(|&extreme| {
+                let values = args.iter().map(|arg| {
+                    let arg_name = arg.parameter();
+                    let value = match (arg.kind, extreme) {
+                        (DataType::Int, false) => quote!{ 0i32 },
+                        (DataType::Int, true) => quote!{ i32::MAX },
+                        (DataType::Uint, false) => quote!{ 0u32 },
+                        (DataType::Uint, true) => quote!{ u32::MAX },
+                        (DataType::String, false) => quote!{ "" },
+                        (DataType::String, true) => quote!{ "ünïcødé ✓" },
+                        (DataType::Object, false) => quote!{ &Id(1) as &dyn #wl::Object },
+                        (DataType::Object, true) => quote!{ &Id(u32::MAX) as &dyn #wl::Object },
+                        _ => quote!{ ::std::default::Default::default() }
+                    };
+                    quote!{ let #arg_name = #value; }
+                });
+                let pushers = args.iter().map(|arg| arg.pusher(wl));
+                let checks = args.iter().map(|arg| {
+                    let arg_name = arg.parameter();
+                    let (getter, expected) = match arg.kind {
+                        DataType::Object => (quote!{ args.next_u32()? }, quote!{ #arg_name.object() }),
+                        // The default array pushed is empty
+                        DataType::Array if bindings[&camel_case(&interface.name)].options.borrowed_args => {
+                            (arg.getter(&interface.name, bindings, wl), quote!{ &[] as &[u8] })
+                        },
+                        // Pushing may consume the value, so compare against a fresh default
+                        DataType::Fixed | DataType::Array => {
+                            let arg_type = arg.event_data_type(wl);
+                            (arg.getter(&interface.name, bindings, wl), quote!{ <#arg_type as ::std::default::Default>::default() })
+                        },
+                        _ => (arg.getter(&interface.name, bindings, wl), quote!{ #arg_name })
+                    };
+                    let name = &arg.name;
+                    quote!{ ::std::assert_eq!(#getter, #expected, "argument {}", #name); }
+                });
+                // Events without arguments on the wire have nothing to push or decode
+                let (message_binding, args_binding) = if args.is_empty() {
+                    (quote!{ let message }, quote!{})
+                } else {
+                    (quote!{ let mut message }, quote!{ let mut args = message.args(); })
+                };
+                quote! {
+                    {
+                        #(#values)*
+                        #message_binding = #wl::Message::new(1, #opcode);
+                        #(#pushers;)*
+                        #args_binding
+                        (|| -> #wl::server::Result<()> {
+                            #(#checks)*
+                            ::std::result::Result::Ok(())
+                        })().unwrap();
+                    }
+                }
+            });
+            quote! {
+                #[test]
+                fn #test_name() {
+                    #(#cases)*
+                }
+            }
+        });
+    quote! {
+        #[cfg(test)]
+        mod roundtrip_tests {
+            /// An object argument standing in for a real object, as only its id is encoded
+            #[allow(dead_code)]
+            struct Id(u32);
+            impl #wl::Object for Id {
+                fn object(&self) -> u32 {
+                    self.0
+                }
+            }
+            #(#tests)*
+        }
+    }
+}
+/// The API listing and hash with a function comparing the listing against a snapshot
+fn generate_api_snapshot(items: &[String]) -> TokenStream {
+    let hash = api::hash(items);
+    quote! {
+        /// A hash of `API_ITEMS`, changing whenever the generated API does
+        pub const API_HASH: &'static str = #hash;
+        /// The generated traits, request and event methods, enums and entries, one item per string
+        pub const API_ITEMS: &'static [&'static str] = &[#(#items),*];
+        /// Compare `API_ITEMS` against a snapshot of one item per line, such as a checked-in file read with
+        /// `include_str!`, returning the added (`+`) and removed (`-`) items one per line if they differ
+        pub fn check_api_snapshot(snapshot: &str) -> ::std::result::Result<(), ::std::string::String> {
+            let snapshot: ::std::vec::Vec<&str> = snapshot.lines().map(str::trim).filter(|item| !item.is_empty()).collect();
+            let added = API_ITEMS.iter().filter(|item| !snapshot.contains(item)).map(|item| ::std::format!("+ {}", item));
+            let removed = snapshot.iter().filter(|item| !API_ITEMS.contains(item)).map(|item| ::std::format!("- {}", item));
+            let changes: ::std::vec::Vec<::std::string::String> = added.chain(removed).collect();
+            if changes.is_empty() {
+                ::std::result::Result::Ok(())
+            } else {
+                ::std::result::Result::Err(changes.join("\n"))
+            }
+        }
+    }
+}
+/// Re-exports of the existing types aliased for enums generated elsewhere
+fn generate_enum_aliases(enum_aliases: &BTreeMap<(String, String), &Binding>) -> Vec<TokenStream> {
+    enum_aliases.iter().map(|((interface, e), binding)| {
+        let implementation = &binding.implementation;
+        let name = &binding.interface;
+        let doc = format!("The `{}.{}` enum, generated elsewhere", interface, e);
+        quote! {
+            #[doc = #doc]
+            pub use #implementation as #name;
+        }
+    }).collect()
+}
+fn generate_prelude(interfaces: &[&Interface], enum_interfaces: &[(&Interface, Vec<&Enum>)], enum_aliases: &BTreeMap<(String, String), &Binding>, split_traits: bool, wl: &Path) -> TokenStream {
+    let split = interfaces.iter()
+        .filter(|_| split_traits)
+        .flat_map(|interface| vec![format_ident!("{}Requests", camel_case(&interface.name)), format_ident!("{}Events", camel_case(&interface.name))]);
+    let traits = interfaces.iter().map(|interface| ident(&camel_case(&interface.name)))
+        .chain(split)
+        .chain(interfaces.iter().filter_map(|interface| field_events_trait(interface)).map(|name| ident(&name)))
+        .chain(interfaces.iter().map(|interface| ident(&as_trait(interface))));
+    let enums = enum_interfaces.iter().flat_map(|(interface, enums)| enums.iter().map(move |e| ident(&format!("{}{}", camel_case(&interface.name), camel_case(&e.name)))))
+        .chain(enum_aliases.values().map(|binding| binding.interface.clone()));
+    let receiver_or = allows_self(interfaces).then(|| quote!{ ReceiverOr, });
+    quote! {
+        /// Re-exports of the generated traits and enums along with the runtime types used in their signatures
+        #[allow(deprecated)]
+        pub mod prelude {
+            pub use #wl::server::{Client, Lease, Result};
+            pub use #wl::{Array, Fixed, NewId, Nullable, Object};
+            pub use super::{BoundVersion, Construct, EventBatch, #receiver_or #(#traits,)* #(#enums),*};
+        }
+    }
+}
+
+/// The trait of an interface and its frame guard, documenting the implementation with `implementation_doc`
+fn generate_traits(interface: &Interface, bindings: &HashMap<String, Binding>, enum_examples: Option<&EnumExamples>, implementation_doc: String, wl: &Path) -> TokenStream {
+    let interface_name = ident(&camel_case(&interface.name));
+    let interface_description = interface.description.iter();
+    let binding = &bindings[&camel_case(&interface.name)];
+    let mut overview = generate_interface_overview(interface, binding);
+    overview.push("\n# Implementation\n".to_string());
+    overview.push(implementation_doc);
+    let interface_version = interface.version;
+    let interface_string = &interface.name;
+    let metadata_module = ident(&snake_case(&interface.name));
+    let events = interface.events.iter().map(|event| generate_event(event, interface, bindings, enum_examples, wl));
+    let (begin_frame, frame_guard) = generate_frame_guard(interface, binding, wl);
+    let requests = interface.requests.iter().map(|request| generate_request(request, interface, bindings, enum_examples, wl));
+    let deprecation: Vec<_> = interface.deprecation_note().iter().map(|note| quote!{ #[deprecated(note = #note)] }).collect();
+    let (attributes, attribute_errors) = extension_attributes(&interface.attrs, &format!("interface {:?}", interface.name));
+    let constants = quote! {
+        const VERSION: u32 = #interface_version;
+        const INTERFACE: &'static str = #interface_string;
+        const REQUESTS: &'static [MessageInfo] = #metadata_module::REQUESTS;
+        const EVENTS: &'static [MessageInfo] = #metadata_module::EVENTS;
+    };
+    let traits = if binding.options.split_traits {
+        let requests_trait = ident(&requests_trait(interface, binding));
+        let events_trait = ident(&events_trait(interface, binding));
+        let requests_doc = format!("The request handlers of [`{}`], called when its requests are dispatched", interface_name);
+        let events_doc = format!("The event senders of [`{}`], which may be implemented by any handle to the object rather than only the implementation", interface_name);
+        quote! {
+            #attribute_errors
+            #(#[doc = #interface_description])*
+            #(#[doc = #overview])*
+            #(#deprecation)*
+            #(#attributes)*
+            #[allow(deprecated)]
+            pub trait #interface_name: #requests_trait + #events_trait {
+                #constants
+            }
+            #(#attributes)*
+            #[allow(deprecated)]
+            impl<T: #requests_trait + #events_trait + ?::std::marker::Sized> #interface_name for T {}
+            #[doc = #requests_doc]
+            #(#deprecation)*
+            #(#attributes)*
+            pub trait #requests_trait: #wl::Object {
+                #(#requests)*
+            }
+            #[doc = #events_doc]
+            #(#deprecation)*
+            #(#attributes)*
+            pub trait #events_trait: #wl::Object {
+                #(#events)*
+                #begin_frame
+            }
+        }
+    } else {
+        quote! {
+            #attribute_errors
+            #(#[doc = #interface_description])*
+            #(#[doc = #overview])*
+            #(#deprecation)*
+            #(#attributes)*
+            pub trait #interface_name: #wl::Object {
+                #constants
+                #(#events)*
+                #begin_frame
+                #(#requests)*
+            }
+        }
+    };
+    quote! {
+        #traits
+        #frame_guard
+    }
+}
+fn generate_interface(interface: &Interface, bindings: &HashMap<String, Binding>, enum_examples: Option<&EnumExamples>, wl: &Path) -> TokenStream {
+    let interface_name = ident(&camel_case(&interface.name));
+    let interface_string = &interface.name;
+    let implementor_struct = bindings[&camel_case(interface_string)].implementation_type();
+    let binding = &bindings[&camel_case(interface_string)];
+    let supported_version = binding.supported_version(interface);
+    let traits = generate_traits(interface, bindings, enum_examples, binding.implementation_doc(), wl);
+    let request_dispatch = interface.requests.iter().enumerate().map(|(opcode, request)| generate_request_dispatch(request, protocol::opcode(opcode), interface, bindings, wl));
+    // Under the `delegate` flag requests are dispatched by a function taking the object's id rather than by the runtime
+    let (interface_expression, object) = match &binding.options.delegate {
+        Some(_) => (quote!{ #interface_string }, quote!{ object }),
+        None => (quote!{ Self::INTERFACE }, quote!{ lease.object() })
+    };
+    // Interfaces without requests must not emit a binding that trips unused_mut, nor leave the client unused
+    let lease_binding = match &binding.options.delegate {
+        Some(_) if interface.requests.is_empty() => quote!{ let _ = client; },
+        Some(resolve) => quote!{ let mut lease = #resolve::<#implementor_struct>(client, object)?; },
+        None if interface.requests.is_empty() => quote! {
+            let _ = client;
+            let lease: #wl::server::Lease<#implementor_struct> = lease.downcast().unwrap();
+        },
+        None => quote!{ let mut lease: #wl::server::Lease<#implementor_struct> = lease.downcast().unwrap(); }
+    };
+    let field_events = generate_field_events(interface, bindings, wl);
+    let as_implementation = generate_as_trait(interface, binding, wl);
+    let wire_dump = binding.options.runtime.wire_dumps.then(|| generate_debug(binding, quote!{ #wl::DEBUG_WIRE }, quote! {
+        self::dump_wire("<-", #interface_expression, #object, &message);
+    }));
+    let destroyed_trace = generate_debug(binding, quote!{ #wl::DEBUG }, quote! {
+        ::std::eprintln!("Ignoring opcode {} sent to {}@{} after it was destroyed", message.opcode, #interface_expression, #object);
+    });
+    // Requests the client sent before it processed `delete_id` for a destroyed object are ignored
+    let destroyed_check = binding.options.runtime.destroyed_objects.then(|| quote! {
+        if client.is_destroyed(#object) {
+            #destroyed_trace
+            return ::std::result::Result::Ok(())
+        }
+    });
+    let deprecation_note = interface.deprecation_note();
+    // The trait is only deprecated for users, so a marker named after the binding reports its use at the binding
+    let binding_deprecation = deprecation_note.iter().map(|note| {
+        let binding = &bindings[&camel_case(interface_string)].interface;
+        quote! {
+            const _: () = {
+                #[deprecated(note = #note)]
+                struct #interface_name;
+                let _ = #binding;
+            };
+        }
+    });
+    // Leases own their implementation and are downcast from `dyn Any`, so borrowed or unsized implementations would
+    // otherwise fail deep inside the generated impls rather than at the binding
+    let binding_requirement = match &binding.options.delegate {
+        Some(_) => quote!{},
+        None => quote_spanned! {binding.implementation.span()=>
+            const _: () = {
+                #[allow(dead_code)]
+                trait ImplementationMustBeSizedAndStatic: ::std::any::Any + ::std::marker::Sized {}
+                impl ImplementationMustBeSizedAndStatic for #implementor_struct {}
+            };
+        }
+    };
+    let implementation = binding.options.runtime.create.then(|| quote! {
+        impl self::sealed::Sealed for #implementor_struct {}
+        impl self::Implementation for #implementor_struct {}
+    });
+    let privilege_error = interface.requests.iter().find(|request| request.is_privileged(interface)).and_then(|request| {
+        let message = match &binding.options.privileged {
+            None => format!("Request \"{}.{}\" is privileged, but the module declares no predicate deciding which clients may send it. Please declare one with `#![privileged(path)]`{}", interface.name, request.name, interface.error_context()),
+            Some(_) if !binding.options.runtime.access_denied => format!("Request \"{}.{}\" is privileged, which requires `#![runtime(access_denied)]`{}", interface.name, request.name, interface.error_context()),
+            Some(_) => return None
+        };
+        Some(syn::Error::new(binding.implementation.span(), message).to_compile_error())
+    });
+    let context_error = binding.options.context.as_ref().filter(|_| !binding.options.runtime.client_data).map(|context| {
+        let message = format!("Interface {:?} takes a context from the client, which requires `#![runtime(client_data)]`{}", interface.name, interface.error_context());
+        syn::Error::new(context.span(), message).to_compile_error()
+    });
+    let dispatch_doc = format!("Dispatches requests to `{}` objects to the methods of [`{}`]", interface.name, requests_trait(interface, binding));
+    let dispatch_body = quote! {
+        use #wl::Object;
+        use ::std::convert::Into;
+        #wire_dump
+        #destroyed_check
+        #lease_binding
+        match message.opcode {
+            #(#request_dispatch)*
+            _ => ::std::result::Result::Err(#wl::DispatchError::InvalidRequest {
+                opcode: message.opcode,
+                object: #object,
+                interface: #interface_expression
+            }.into())
+        }
+    };
+    let dispatch = match &binding.options.delegate {
+        Some(resolve) => {
+            let function = ident(&dispatch_function(interface));
+            let doc = format!("Dispatches a request to the `{}` object `object` to the methods of [`{}`], implemented by the receiver `{}` returns", interface.name, requests_trait(interface, binding), path_name(resolve));
+            quote! {
+                #[doc = #doc]
+                #[allow(deprecated)]
+                pub fn #function(client: &mut #wl::server::Client, object: u32, message: #wl::Message) -> #wl::server::Result<()> {
+                    #dispatch_body
+                }
+            }
+        },
+        None => quote! {
+            #[doc = #dispatch_doc]
+            #[allow(deprecated)]
+            impl #wl::server::Dispatch for #implementor_struct {
+                const INTERFACE: &'static str = #interface_string;
+                const VERSION: u32 = #supported_version;
+                fn dispatch(lease: #wl::server::Lease<dyn ::std::any::Any>, client: &mut #wl::server::Client, message: #wl::Message) -> #wl::server::Result<()> {
+                    #dispatch_body
+                }
+            }
+            #implementation
+        }
+    };
+    quote!{
+        #traits
+        #(#binding_deprecation)*
+        #privilege_error
+        #context_error
+        #binding_requirement
+        #dispatch
+        #field_events
+        #as_implementation
+    }
+}
+
+/// The module documentation: a table per merged protocol listing each interface's version, summary and how this module
+/// implements it, linking to the traits of bound and documented interfaces
+fn generate_index(protocols: &[Protocol], protocol: &Protocol, bindings: &HashMap<String, Binding>, documented: &[&Interface]) -> TokenStream {
+    // Summaries are single lines of prose, so only characters with meaning in a table cell or link need escaping
+    let escape = |text: &str| text.replace('|', "\\|").replace('[', "\\[").replace(']', "\\]");
+    let mut index = match &protocol.summary {
+        Some(summary) => format!("{}\n\n", escape(summary)),
+        None => String::new()
+    };
+    for source in protocols {
+        if protocols.len() > 1 {
+            index += &format!("## `{}`\n\n", source.name);
+        } else {
+            index += &format!("Interfaces of the `{}` protocol:\n\n", source.name);
+        }
+        index += "| Interface | Version | Implementation | Summary |\n|---|---|---|---|\n";
+        // Interfaces are listed followed by those they depend on, so that related interfaces are listed together
+        let interfaces: Vec<&Interface> = source.interfaces.iter().collect();
+        for interface in Dependencies::new(&interfaces).order() {
+            let generated = protocol.interfaces.iter().find(|generated| generated.name == interface.name);
+            let (name, implementation) = match (generated, binding_of(bindings, &interface.name)) {
+                (None, _) => (format!("`{}`", interface.name), "excluded".to_string()),
+                (Some(_), None) if documented.iter().any(|documented| documented.name == interface.name) => {
+                    (format!("[`{}`]({})", interface.name, camel_case(&interface.name)), "not bound".to_string())
+                },
+                (Some(_), None) => (format!("`{}`", interface.name), "not bound".to_string()),
+                (Some(_), Some(binding)) if binding.is_external => (format!("`{}`", interface.name), format!("external, `{}`", binding.implementation_name())),
+                (Some(_), Some(binding)) => {
+                    let trait_name = camel_case(&interface.name);
+                    (format!("[`{}`]({})", interface.name, trait_name), format!("`{}`", binding.implementation_name()))
+                }
+            };
+            let summary = generated.unwrap_or(interface).summary.as_deref().map(escape).unwrap_or_default();
+            index += &format!("| {} | {} | {} | {} |\n", name, interface.version, implementation, summary);
+        }
+        index += "\n";
+    }
+    quote!{ #![doc = #index] }
+}
+/// The outer attributes given to a protocol item by the `attrs` of the specification, and errors naming `item` for any
+/// string that is not only outer attributes, to be emitted once where the attributes may be applied to several items
+fn extension_attributes(attrs: &[String], item: &str) -> (Vec<Attribute>, TokenStream) {
+    let mut attributes = Vec::new();
+    let mut errors = TokenStream::new();
+    for attr in attrs {
+        match Attribute::parse_outer.parse_str(attr) {
+            Ok(parsed) if !parsed.is_empty() => attributes.extend(parsed),
+            Ok(_) => errors.extend(syn::Error::new(Span::call_site(), format!("Attribute {:?} of {} contains no attribute. Attributes are written as in source, e.g. `#[my_tool::audit]`", attr, item)).to_compile_error()),
+            Err(error) => errors.extend(syn::Error::new(Span::call_site(), format!("Attribute {:?} of {} is not an outer attribute: {}", attr, item, error)).to_compile_error())
+        }
+    }
+    (attributes, errors)
+}
+/// Limit generated items to rustdoc builds
+fn doc_only(items: TokenStream) -> TokenStream {
+    match syn::parse2::<syn::File>(items) {
+        Ok(file) => {
+            let items = file.items;
+            quote!{ #(#[cfg(doc)] #items)* }
+        },
+        Err(error) => error.to_compile_error()
+    }
+}
+/// Allow the items of enums that no generated signature uses to go unused in a private module, noting in the type's
+/// documentation that it is generated for the completeness of the protocol
+fn allow_unreferenced(items: TokenStream) -> TokenStream {
+    match syn::parse2::<syn::File>(items) {
+        Ok(file) => {
+            let mut documented = false;
+            let items = file.items.into_iter().map(|mut item| {
+                // Enums are documented on their inherent impl, where the note ends the documentation
+                match &mut item {
+                    Item::Impl(item) if item.trait_.is_none() && !documented => {
+                        documented = true;
+                        let docs = item.attrs.iter().rposition(|attr| attr.path.is_ident("doc")).map_or(0, |last| last + 1);
+                        item.attrs.splice(docs..docs, vec![
+                            syn::parse_quote!{ #[doc = ""] },
+                            syn::parse_quote!{ #[doc = "No request or event of the interfaces bound by this module uses this enum, which is generated for the completeness of the protocol."] }
+                        ]);
+                    },
+                    _ => ()
+                }
+                // Trait implementations are never reported as unused, so only the type and its own items are allowed
+                match &item {
+                    Item::Struct(_) => quote!{ #[allow(dead_code)] #item },
+                    Item::Impl(implementation) if implementation.trait_.is_none() => quote!{ #[allow(dead_code)] #item },
+                    _ => quote!{ #item }
+                }
+            }).collect::<Vec<_>>();
+            quote!{ #(#items)* }
+        },
+        Err(error) => error.to_compile_error()
+    }
+}
+/// The function dispatching requests to objects of an interface under the module's `delegate` flag
+fn dispatch_function(interface: &Interface) -> String {
+    format!("dispatch_{}", snake_case(&interface.name))
+}
+/// The name of the trait of an interface's request handlers, which is the interface trait unless the module's
+/// `split_traits` flag is set
+pub(crate) fn requests_trait(interface: &Interface, binding: &Binding) -> String {
+    if binding.options.split_traits {
+        format!("{}Requests", camel_case(&interface.name))
+    } else {
+        camel_case(&interface.name)
+    }
+}
+/// The name of the trait of an interface's event senders, which is the interface trait unless the module's
+/// `split_traits` flag is set
+pub(crate) fn events_trait(interface: &Interface, binding: &Binding) -> String {
+    if binding.options.split_traits {
+        format!("{}Events", camel_case(&interface.name))
+    } else {
+        camel_case(&interface.name)
+    }
+}
+/// The name of the trait borrowing the implementation of an interface from either a lease or the implementation itself
+fn as_trait(interface: &Interface) -> String {
+    format!("As{}", camel_case(&interface.name))
+}
+/// A trait implemented for the binding and its lease, so that helpers may take either as `&mut impl AsWlSurface`
+fn generate_as_trait(interface: &Interface, binding: &Binding, wl: &Path) -> TokenStream {
+    let trait_name = ident(&as_trait(interface));
+    let implementation = binding.implementation_type();
+    let as_ref = format_ident!("as_{}", snake_case(&interface.name));
+    let as_mut = format_ident!("as_{}_mut", snake_case(&interface.name));
+    let trait_doc = format!("Borrows the implementation of `{}` from its lease or the implementation itself", interface.name);
+    quote! {
+        #[doc = #trait_doc]
+        pub trait #trait_name {
+            fn #as_ref(&self) -> &#implementation;
+            fn #as_mut(&mut self) -> &mut #implementation;
+        }
+        impl #trait_name for #implementation {
+            fn #as_ref(&self) -> &#implementation {
+                self
+            }
+            fn #as_mut(&mut self) -> &mut #implementation {
+                self
+            }
+        }
+        impl #trait_name for #wl::server::Lease<#implementation> {
+            fn #as_ref(&self) -> &#implementation {
+                self
+            }
+            fn #as_mut(&mut self) -> &mut #implementation {
+                self
+            }
+        }
+    }
+}
+/// The name of the guard grouping events into a frame, if the interface has an argumentless `frame` event
+fn frame_guard_type(interface: &Interface) -> Option<String> {
+    interface.events.iter()
+        .any(|event| event.name == "frame" && event.args.is_empty())
+        .then(|| format!("{}Frame", camel_case(&interface.name)))
+}
+/// The `begin_frame` trait method and the guard it returns, through which events are collected and sent together
+/// ending with the `frame` event when the guard is finished or dropped
+fn generate_frame_guard(interface: &Interface, binding: &Binding, wl: &Path) -> (TokenStream, TokenStream) {
+    let guard = match frame_guard_type(interface) {
+        Some(name) => ident(&name),
+        None => return (quote!{}, quote!{})
+    };
+    let interface_name = ident(&events_trait(interface, binding));
+    let frame = interface.events.iter().find(|event| event.name == "frame").unwrap();
+    let send = generate_send(frame, interface, binding, quote!{ batch.send(client) });
+    let frame_into = format_ident!("{}_into", binding.method_name("frame"));
+    let methods = interface.events.iter().filter(|event| event.name != "frame").map(|event| {
+        let event_name = ident(&binding.method_name(&event.name));
+        let batched_event_name = format_ident!("{}_into", binding.method_name(&event.name));
+        let parameters = event.args.iter().map(|arg| generate_event_parameter(arg, wl));
+        let arg_names = event.args.iter().map(|arg| arg.parameter());
+        let doc = format!("Add the `{}` event to the frame", event.name);
+        quote! {
+            #[doc = #doc]
+            pub fn #event_name(&mut self, #(#parameters),*) -> &mut Self {
+                if let ::std::option::Option::Some(batch) = &mut self.batch {
+                    self.object.#batched_event_name(batch #(, #arg_names)*);
+                }
+                self
+            }
+        }
+    });
+    let guard_doc = format!("Events of `{}` grouped into a frame, returned by [`{}::begin_frame`]. The events are sent together ending with the `frame` event once the frame is finished or dropped", interface.name, interface_name);
+    let begin_frame = quote! {
+        /// Begin a frame of events, sent ending with the `frame` event once the returned guard is finished or dropped
+        fn begin_frame<'a>(&'a mut self, client: &'a mut #wl::server::Client) -> #guard<'a, Self> where Self: ::std::marker::Sized {
+            #guard {
+                object: self,
+                client,
+                batch: ::std::option::Option::Some(self::EventBatch::new())
+            }
+        }
+    };
+    let frame_guard = quote! {
+        #[doc = #guard_doc]
+        #[allow(deprecated)]
+        pub struct #guard<'a, T: #interface_name> {
+            object: &'a mut T,
+            client: &'a mut #wl::server::Client,
+            /// Taken once the frame is sent
+            batch: ::std::option::Option<EventBatch>
+        }
+        #[allow(deprecated)]
+        impl<'a, T: #interface_name> #guard<'a, T> {
+            #(#methods)*
+            /// End the frame, sending its events followed by the `frame` event
+            pub fn finish(mut self) -> #wl::server::Result<()> {
+                self.send()
+            }
+            fn send(&mut self) -> #wl::server::Result<()> {
+                match self.batch.take() {
+                    ::std::option::Option::Some(mut batch) => {
+                        self.object.#frame_into(&mut batch);
+                        let client = &mut *self.client;
+                        #send
+                    },
+                    ::std::option::Option::None => ::std::result::Result::Ok(())
+                }
+            }
+        }
+        #[allow(deprecated)]
+        impl<'a, T: #interface_name> ::std::ops::Drop for #guard<'a, T> {
+            /// Frames that are not finished are sent when dropped, where errors cannot be reported
+            fn drop(&mut self) {
+                let _ = self.send();
+            }
+        }
+    };
+    (begin_frame, frame_guard)
+}
+/// The name of the trait of `send_` methods for events with arguments taken from fields, if the interface has any
+fn field_events_trait(interface: &Interface) -> Option<String> {
+    interface.events.iter().flat_map(|event| &event.args)
+        .any(|arg| arg.from_field.is_some())
+        .then(|| format!("{}FieldEvents", camel_case(&interface.name)))
+}
+/// A trait implemented for the binding with a `send_` method per event sending arguments marked `from_field` straight
+/// from fields of the implementation, taking the remaining arguments as parameters
+fn generate_field_events(interface: &Interface, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let trait_name = match field_events_trait(interface) {
+        Some(name) => ident(&name),
+        None => return quote!{}
+    };
+    let binding = &bindings[&camel_case(&interface.name)];
+    let interface_name = ident(&events_trait(interface, binding));
+    let implementation = &binding.implementation;
+    if binding.is_dyn {
+        return syn::Error::new(implementation.span(), format!("Events of {:?} cannot be sent from fields of a `dyn` implementation{}", interface.name, interface.error_context())).to_compile_error()
+    }
+    if binding.options.delegate.is_some() {
+        return syn::Error::new(implementation.span(), format!("Events of {:?} cannot be sent from fields under the `delegate` flag, as the implementation is not received in a lease{}", interface.name, interface.error_context())).to_compile_error()
+    }
+    let metadata_module = ident(&snake_case(&interface.name));
+    let events: Vec<&Event> = interface.events.iter().filter(|event| event.args.iter().any(|arg| arg.from_field.is_some())).collect();
+    let signatures: Vec<TokenStream> = events.iter().map(|event| {
+        let send = format_ident!("send_{}", snake_case(&event.name));
+        let parameters = event.args.iter().filter(|arg| arg.from_field.is_none()).map(|arg| generate_event_parameter(arg, wl));
+        let doc = format!("Send the `{}` event with arguments taken from fields of the implementation", event.name);
+        quote! {
+            #[doc = #doc]
+            fn #send(&mut self, client: &mut #wl::server::Client, #(#parameters),*) -> #wl::server::Result<()>
+        }
+    }).collect();
+    let methods = events.iter().zip(&signatures).map(|(event, signature)| {
+        let fields = event.args.iter().filter_map(|arg| arg.from_field.as_ref().map(|field| (arg, field))).map(|(arg, field)| {
+            let arg_name = arg.parameter();
+            let arg_type = arg.event_data_type(wl);
+            // Unknown fields are reported at the binding
+            let field = Ident::new(field, implementation.span());
+            let value = match arg.kind {
+                DataType::Int | DataType::Uint | DataType::Fixed | DataType::NewId => quote!{ self.#field },
+                DataType::String | DataType::Fd => quote!{ &self.#field },
+                DataType::Object if !arg.nullable => quote!{ &self.#field },
+                DataType::Array => quote!{ ::std::clone::Clone::clone(&self.#field) },
+                DataType::Object => syn::Error::new(implementation.span(), format!("Nullable argument {:?} of {}.{} cannot be sent from a field{}", arg.name, interface.name, event.name, interface.error_context())).to_compile_error()
+            };
+            quote!{ let #arg_name: #arg_type = #value; }
+        });
+        let encoder = format_ident!("encode_{}", snake_case(&event.name));
+        let arg_names = event.args.iter().map(|arg| arg.parameter());
+        let debug_print = generate_debug(binding, quote!{ #wl::DEBUG }, generate_event_debug_print(event, interface, quote!{ self.object() }, wl));
+        let wire_dump = generate_event_wire_dump(interface, binding, quote!{ self.object() }, wl);
+        let count = generate_event_count(event, interface, binding);
+        let send = generate_send(event, interface, binding, quote!{ client.send(message) });
+        let dead_client_check = generate_dead_client_check(binding.options.skip_dead_clients, quote!{ 1 }, quote! {
+            return ::std::result::Result::Ok(())
+        });
+        let string_checks = generate_event_string_checks(event, interface, binding, quote! {
+            return ::std::result::Result::Err(error.into())
+        });
+        // The fields are only borrowed, so the message is encoded here rather than through the `&mut self` event methods
+        quote! {
+            #signature {
+                use #wl::Object;
+                #dead_client_check
+                #(#fields)*
+                #string_checks
+                #debug_print
+                let message = #metadata_module::#encoder(self.object() #(, #arg_names)*);
+                #wire_dump
+                #count
+                #send
+            }
+        }
+    });
+    let trait_doc = format!("Events of [`{}`] sent from fields of the implementation", interface_name);
+    quote! {
+        #[doc = #trait_doc]
+        pub trait #trait_name: #interface_name {
+            #(#signatures;)*
+        }
+        #[allow(deprecated)]
+        impl #trait_name for #wl::server::Lease<#implementation> {
+            #(#methods)*
+        }
+    }
+}
+
+/// Doc lines listing the requests and events of an interface, linking to their methods. Under the `split_traits` flag
+/// the methods belong to the requests and events traits rather than the interface trait documented
+fn generate_interface_overview(interface: &Interface, binding: &Binding) -> Vec<String> {
+    let (requests_trait, events_trait) = if binding.options.split_traits {
+        (requests_trait(interface, binding), events_trait(interface, binding))
+    } else {
+        ("Self".to_string(), "Self".to_string())
+    };
+    let entry = |owner: &str, name: &str, args: &[Arg], opcode: usize, since: u32, summary: &Option<String>| {
+        let args = args.iter().map(|arg| arg.name.as_str()).collect::<Vec<_>>().join(", ");
+        // rustdoc resolves link targets naming keyword methods without the `r#` prefix of their raw identifier
+        let mut line = format!("- [`{}({})`]({}::{}) (opcode {}, since {})", name, args, owner, binding.method_name(name), opcode, since);
+        if let Some(summary) = summary {
+            line.push_str(": ");
+            line.push_str(summary.trim());
+        }
+        line
+    };
+    let mut overview = Vec::new();
+    if !interface.requests.is_empty() {
+        overview.push("\n# Requests\n".to_string());
+        overview.extend(interface.requests.iter().enumerate().map(|(opcode, request)| entry(&requests_trait, &request.name, &request.args, opcode, request.since_version(), &request.summary)));
+    }
+    if !interface.events.is_empty() {
+        overview.push("\n# Events\n".to_string());
+        overview.extend(interface.events.iter().enumerate().map(|(opcode, event)| entry(&events_trait, &event.name, &event.args, opcode, event.since_version(), &event.summary)));
+    }
+    overview
+}
+fn generate_metadata(interface: &Interface, stats: bool, message_size: bool, wl: &Path) -> TokenStream {
+    let metadata_module = ident(&snake_case(&interface.name));
+    let requests = interface.requests.iter().enumerate().map(|(opcode, request)| {
+        let opcode = protocol::opcode(opcode);
+        let name = &request.name;
+        let since = request.since_version();
+        let double_buffered = request.double_buffered;
+        let latching = request.latching;
+        let min_size = protocol::min_size(&request.args);
+        let fixed_size = generate_fixed_size(&request.args);
+        quote!{
+            super::MessageInfo { name: #name, opcode: #opcode, since: #since, double_buffered: #double_buffered, latching: #latching, min_size: #min_size, fixed_size: #fixed_size }
+        }
+    });
+    let events = interface.events.iter().enumerate().map(|(opcode, event)| {
+        let opcode = protocol::opcode(opcode);
+        let name = &event.name;
+        let since = event.since_version();
+        let min_size = protocol::min_size(&event.args);
+        let fixed_size = generate_fixed_size(&event.args);
+        quote!{
+            super::MessageInfo { name: #name, opcode: #opcode, since: #since, double_buffered: false, latching: false, min_size: #min_size, fixed_size: #fixed_size }
+        }
+    });
+    let request_since = interface.requests.iter().map(Request::since_version);
+    let event_since = interface.events.iter().map(Event::since_version);
+    let double_buffered: Vec<_> = interface.requests.iter().enumerate()
+        .filter(|(_, request)| request.double_buffered)
+        .map(|(opcode, _)| protocol::opcode(opcode))
+        .collect();
+    let is_double_buffered = if double_buffered.is_empty() {
+        quote!{ let _ = opcode; false }
+    } else {
+        quote!{ ::std::matches!(opcode, #(#double_buffered)|*) }
+    };
+    let request_encoders = interface.requests.iter().enumerate()
+        .map(|(opcode, request)| generate_encoder(&request.name, &request.args, protocol::opcode(opcode), format!("Serialize the `{}.{}` request sent to `object`", interface.name, request.name), None, wl));
+    let event_encoders = interface.events.iter().enumerate()
+        .map(|(opcode, event)| generate_encoder(&event.name, &event.args, protocol::opcode(opcode), format!("Serialize the `{}.{}` event sent from `object`", interface.name, event.name), message_size.then(|| format!("{}.{}", interface.name, event.name)), wl));
+    let counters = if stats {
+        let requests = interface.requests.iter().map(|_| quote!{ ::std::sync::atomic::AtomicU64::new(0) });
+        let events = interface.events.iter().map(|_| quote!{ ::std::sync::atomic::AtomicU64::new(0) });
+        quote! {
+            /// The number of requests dispatched, indexed by opcode
+            pub static REQUESTS_DISPATCHED: [::std::sync::atomic::AtomicU64; REQUESTS.len()] = [#(#requests),*];
+            /// The number of events sent, indexed by opcode
+            pub static EVENTS_SENT: [::std::sync::atomic::AtomicU64; EVENTS.len()] = [#(#events),*];
+        }
+    } else {
+        quote!{}
+    };
+    let signature_hash = interface.signature_hash();
+    let largest_fixed_message = interface.largest_fixed_message();
+    let module_doc = format!("Introspection metadata and message encoders for the `{}` interface", interface.name);
+    quote! {
+        #[doc = #module_doc]
+        pub mod #metadata_module {
+            /// A hash of the interface's wire format, changing whenever its version, or the order, version or argument
+            /// types of its messages do
+            pub const SIGNATURE_HASH: u64 = #signature_hash;
+            /// The largest `min_size` of the interface's requests and events, the size in bytes its largest message
+            /// takes on the wire with its strings and arrays empty
+            pub const LARGEST_FIXED_MESSAGE: u32 = #largest_fixed_message;
+            pub const REQUESTS: &[super::MessageInfo] = &[#(#requests),*];
+            pub const EVENTS: &[super::MessageInfo] = &[#(#events),*];
+            #counters
+            /// The version each request was introduced in, indexed by opcode
+            pub const REQUEST_SINCE: &[u32] = &[#(#request_since),*];
+            /// The version each event was introduced in, indexed by opcode
+            pub const EVENT_SINCE: &[u32] = &[#(#event_since),*];
+            /// The version the request with the given opcode was introduced in, or 0 if there is no such request
+            pub const fn request_since(opcode: u16) -> u32 {
+                if (opcode as usize) < REQUEST_SINCE.len() {
+                    REQUEST_SINCE[opcode as usize]
+                } else {
+                    0
+                }
+            }
+            /// The version the event with the given opcode was introduced in, or 0 if there is no such event
+            pub const fn event_since(opcode: u16) -> u32 {
+                if (opcode as usize) < EVENT_SINCE.len() {
+                    EVENT_SINCE[opcode as usize]
+                } else {
+                    0
+                }
+            }
+            /// Whether the request with the given opcode sets double-buffered state
+            pub const fn is_double_buffered(opcode: u16) -> bool {
+                #is_double_buffered
+            }
+            #(#request_encoders)*
+            #(#event_encoders)*
+        }
+    }
+}
+/// The `fixed_size` of a message's introspection data
+fn generate_fixed_size(args: &[Arg]) -> TokenStream {
+    match protocol::fixed_size(args) {
+        Some(size) => quote!{ ::std::option::Option::Some(#size) },
+        None => quote!{ ::std::option::Option::None }
+    }
+}
+/// A function building the message for a request or event without a client, for recording and replaying messages.
+/// Where the message is named by `checked`, debug builds check that it is no smaller than its `min_size`, catching
+/// argument pushers that have drifted from the specification
+fn generate_encoder(name: &str, args: &[Arg], opcode: u16, doc: String, checked: Option<String>, wl: &Path) -> TokenStream {
+    let encoder = format_ident!("encode_{}", snake_case(name));
+    let parameters = args.iter().map(|arg| generate_event_parameter(arg, wl));
+    let arg_pushers = args.iter().map(|arg| arg.pusher(wl));
+    let size_check = checked.map(|message| {
+        let min_size = protocol::min_size(args) as usize;
+        let error = format!("`{}` was encoded to {{}} bytes, less than its minimum of {} bytes", message, min_size);
+        // Like `assert!`, `debug_assert!` with a message expands to a bare `panic!` in older editions
+        quote! {
+            if ::std::cfg!(debug_assertions) && message.size() < #min_size {
+                ::std::panic!(#error, message.size());
+            }
+        }
+    });
+    let body = if args.is_empty() {
+        quote!{ #wl::Message::new(object, #opcode) }
+    } else {
+        quote! {
+            let mut message = #wl::Message::new(object, #opcode);
+            #(#arg_pushers;)*
+            #size_check
+            message
+        }
+    };
+    quote! {
+        #[doc = #doc]
+        pub fn #encoder(object: u32, #(#parameters),*) -> #wl::Message {
+            #body
+        }
+    }
+}
+
+fn generate_event(event: &Event, interface: &Interface, bindings: &HashMap<String, Binding>, enum_examples: Option<&EnumExamples>, wl: &Path) -> TokenStream {
+    let binding = &bindings[&camel_case(&interface.name)];
+    let event_name = ident(&binding.method_name(&event.name));
+    let alias = binding.method_alias(&event.name);
+    let event_summary = event.summary.iter();
+    let event_description = event.description.iter();
+    let example = enum_examples.map(|enum_examples| doc_example(&binding.method_name(&event.name), &event.args, interface, None, enum_examples)).into_iter();
+    let debug_print = generate_debug(binding, quote!{ #wl::DEBUG }, generate_event_debug_print(event, interface, quote!{ self.object() }, wl));
+    let wire_dump = generate_event_wire_dump(interface, binding, quote!{ self.object() }, wl);
+    let count = generate_event_count(event, interface, binding);
+    let metadata_module = ident(&snake_case(&interface.name));
+    let encoder = format_ident!("encode_{}", snake_case(&event.name));
+    let batched_event_name = format_ident!("{}_into", binding.method_name(&event.name));
+    let batched_doc = format!("Serialize the `{}` event into `batch` to be sent along with other events", event.name);
+    let parameters: Vec<_> = event.args.iter().map(|arg| generate_event_parameter(arg, wl)).collect();
+    let arg_names: Vec<_> = event.args.iter().map(|arg| arg.parameter()).collect();
+    let send = generate_send(event, interface, binding, quote!{ client.send(message) });
+    let dead_client_check = generate_dead_client_check(binding.options.skip_dead_clients, quote!{ 1 }, quote! {
+        return ::std::result::Result::Ok(())
+    });
+    let string_checks = generate_event_string_checks(event, interface, binding, quote! {
+        return ::std::result::Result::Err(error.into())
+    });
+    let batched_string_checks = generate_event_string_checks(event, interface, binding, quote! {
+        batch.reject(error);
+        return
+    });
+    let (attributes, attribute_errors) = extension_attributes(&event.attrs, &format!("event \"{}.{}\"", interface.name, event.name));
+    let since = event.since_version();
+    let versioned = (since > 1).then(|| {
+        let try_event_name = format_ident!("try_{}", binding.method_name(&event.name));
+        let try_doc = format!("Send the `{}` event if the object was bound at version {} or later, returning whether it was sent. Older clients do not know the event, so it is skipped for them", event.name, since);
+        quote! {
+            #[doc = #try_doc]
+            #(#attributes)*
+            fn #try_event_name(&mut self, client: &mut #wl::server::Client, #(#parameters),*) -> #wl::server::Result<bool> where Self: BoundVersion {
+                if self.bound_version() < #since {
+                    return ::std::result::Result::Ok(false)
+                }
+                self.#event_name(client #(, #arg_names)*)?;
+                ::std::result::Result::Ok(true)
+            }
+        }
+    });
+    quote! {
+        #attribute_errors
+        #(#[doc = #event_summary])*
+        #[doc = "\n"]
+        #(#[doc = #event_description])*
+        #(#[doc = "\n"] #[doc = #example])*
+        #alias
+        #(#attributes)*
+        fn #event_name(&mut self, client: &mut #wl::server::Client, #(#parameters),*) -> #wl::server::Result<()> {
+            #dead_client_check
+            #string_checks
+            #debug_print
+            let message = #metadata_module::#encoder(self.object() #(, #arg_names)*);
+            #wire_dump
+            #count
+            #send
+        }
+        #[doc = #batched_doc]
+        #(#attributes)*
+        fn #batched_event_name(&mut self, batch: &mut EventBatch, #(#parameters),*) {
+            #batched_string_checks
+            #debug_print
+            let message = #metadata_module::#encoder(self.object() #(, #arg_names)*);
+            #wire_dump
+            #count
+            batch.push(message)
+        }
+        #versioned
+    }
+}
+/// Send an event with `send`, following it with a flush of the client if the event [flushes](Event::flushes) and the
+/// runtime can flush. Where the runtime reports disconnects, failures once the client has disconnected are reported as
+/// `DispatchError::Disconnected`
+fn generate_send(event: &Event, interface: &Interface, binding: &Binding, send: TokenStream) -> TokenStream {
+    let send = if binding.options.runtime.flush && event.flushes(interface) {
+        quote!{ #send.and_then(|()| client.flush()) }
+    } else {
+        send
+    };
+    if binding.options.runtime.disconnects {
+        quote!{ self::classify_send(#send, client) }
+    } else {
+        send
+    }
+}
+/// Whether a `broadcast_` function is generated for the event. Objects are specific to a client, so events with object
+/// arguments cannot be sent to many clients alike
+fn broadcasts(event: &Event) -> bool {
+    event.args.iter().all(|arg| !matches!(arg.kind, DataType::Object | DataType::NewId))
+}
+fn broadcast_function(interface: &Interface, event: &Event) -> String {
+    format!("broadcast_{}_{}", snake_case(&interface.name), snake_case(&event.name))
+}
+/// A function per event without object arguments sending it to the object chosen by `object_for` in each of many
+/// clients, along with the error collecting the clients it could not be sent to
+fn generate_broadcasts(interfaces: &[&Interface], bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let functions = interfaces.iter().flat_map(|interface| interface.events.iter().filter(|event| broadcasts(event)).map(move |event| (interface, event))).map(|(interface, event)| {
+        let binding = &bindings[&camel_case(&interface.name)];
+        let function = ident(&broadcast_function(interface, event));
+        let skipped = if binding.options.runtime.disconnects { "clients without one and those that have disconnected" } else { "clients without one" };
+        let doc = format!("Send the `{}.{}` event to the object chosen by `object_for` in each client, skipping {}. Returns the number of clients the event was sent to", interface.name, event.name, skipped);
+        let event_string = format!("{}.{}", interface.name, event.name);
+        let metadata_module = ident(&snake_case(&interface.name));
+        let encoder = format_ident!("encode_{}", snake_case(&event.name));
+        let parameters = event.args.iter().map(|arg| generate_event_parameter(arg, wl));
+        // Arrays are consumed by encoding, so each client is sent a copy
+        let arg_values = event.args.iter().map(|arg| {
+            let arg_name = arg.parameter();
+            match arg.kind {
+                DataType::Array => quote!{ ::std::clone::Clone::clone(&#arg_name) },
+                _ => quote!{ #arg_name }
+            }
+        });
+        let debug_print = generate_debug(binding, quote!{ #wl::DEBUG }, generate_event_debug_print(event, interface, quote!{ object }, wl));
+        let wire_dump = generate_event_wire_dump(interface, binding, quote!{ object }, wl);
+        let count = generate_event_count(event, interface, binding);
+        let send = generate_send(event, interface, binding, quote!{ client.send(message) });
+        let disconnected = binding.options.runtime.disconnects.then(|| quote! {
+            ::std::result::Result::Err(_) if client.is_disconnected() => (),
+        });
+        let dead_client_check = generate_dead_client_check(binding.options.skip_dead_clients, quote!{ 1 }, quote! {
+            continue
+        });
+        let string_checks = generate_event_string_checks(event, interface, binding, quote! {
+            failures.push(BroadcastFailure { client: index, object, error });
+            continue
+        });
+        quote! {
+            #[doc = #doc]
+            pub fn #function<'a>(clients: impl ::std::iter::IntoIterator<Item = &'a mut #wl::server::Client>, object_for: impl ::std::ops::Fn(&#wl::server::Client) -> ::std::option::Option<u32>, #(#parameters),*) -> ::std::result::Result<usize, BroadcastError> {
+                let mut sent = 0;
+                let mut failures = ::std::vec::Vec::new();
+                for (index, client) in clients.into_iter().enumerate() {
+                    #dead_client_check
+                    let object = match object_for(client) {
+                        ::std::option::Option::Some(object) => object,
+                        ::std::option::Option::None => continue
+                    };
+                    #string_checks
+                    #debug_print
+                    let message = #metadata_module::#encoder(object #(, #arg_values)*);
+                    #wire_dump
+                    #count
+                    match #send {
+                        ::std::result::Result::Ok(()) => sent += 1,
+                        #disconnected
+                        ::std::result::Result::Err(error) => failures.push(BroadcastFailure { client: index, object, error })
+                    }
+                }
+                if failures.is_empty() {
+                    ::std::result::Result::Ok(sent)
+                } else {
+                    ::std::result::Result::Err(BroadcastError { event: #event_string, sent, failures })
+                }
+            }
+        }
+    });
+    quote! {
+        /// A client a broadcast event could not be sent to
+        #[derive(::std::fmt::Debug)]
+        pub struct BroadcastFailure {
+            /// The position of the client among those broadcast to
+            pub client: usize,
+            pub object: u32,
+            pub error: #wl::DispatchError
+        }
+        /// The clients a `broadcast_` function could not send its event to
+        #[derive(::std::fmt::Debug)]
+        pub struct BroadcastError {
+            pub event: &'static str,
+            /// The number of clients the event was sent to
+            pub sent: usize,
+            pub failures: ::std::vec::Vec<BroadcastFailure>
+        }
+        impl ::std::fmt::Display for BroadcastError {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::write!(f, "{} could not be sent to {} clients, and was sent to {}", self.event, self.failures.len(), self.sent)
+            }
+        }
+        impl ::std::error::Error for BroadcastError {}
+        #(#functions)*
+    }
+}
+/// Dump an encoded event under `wl::DEBUG_WIRE`, if the module's runtime provides it
+fn generate_event_wire_dump(interface: &Interface, binding: &Binding, object: TokenStream, wl: &Path) -> TokenStream {
+    if !binding.options.runtime.wire_dumps {
+        return quote!{}
+    }
+    let interface_name = &interface.name;
+    generate_debug(binding, quote!{ #wl::DEBUG_WIRE }, quote! {
+        self::dump_wire("->", #interface_name, #object, &message);
+    })
+}
+/// Debug output run while the runtime `flag` is set, omitted entirely under the module's `no_debug` flag
+fn generate_debug(binding: &Binding, flag: TokenStream, output: TokenStream) -> TokenStream {
+    if binding.options.no_debug {
+        quote!{}
+    } else {
+        quote! {
+            if *#flag {
+                #output
+            }
+        }
+    }
+}
+/// A message traced to stderr, with each placeholder of the format strings written along with the argument it formats
+/// so that the two cannot diverge however many arguments a message has. The verbose format labels each argument
+#[derive(Default)]
+struct Trace {
+    format: String,
+    labelled: String,
+    args: Vec<TokenStream>
+}
+impl Trace {
+    /// Append text printed as it is
+    fn text(&mut self, text: &str) {
+        let text = text.replace('{', "{{").replace('}', "}}");
+        self.format.push_str(&text);
+        self.labelled.push_str(&text);
+    }
+    /// Append a value formatted by `placeholder`, such as `{:?}` or `dyn {}`, which must hold a single placeholder
+    fn value(&mut self, placeholder: &str, value: TokenStream) {
+        self.format.push_str(placeholder);
+        self.labelled.push_str(placeholder);
+        self.args.push(value);
+    }
+    /// Append an argument of the message, labelled by its name in the verbose format
+    fn arg(&mut self, arg: &Arg, placeholder: &str) {
+        self.labelled.push_str(&arg.label());
+        self.labelled.push_str(": ");
+        let parameter = arg.parameter();
+        self.value(placeholder, quote!{ #parameter });
+    }
+    /// Append the `interface@object.message(` heading every message
+    fn message(&mut self, interface: TokenStream, object: TokenStream, message: TokenStream) {
+        self.value("{}", interface);
+        self.text("@");
+        self.value("{}", object);
+        self.text(".");
+        self.value("{}", message);
+        self.text("(");
+    }
+    /// Check that the format strings take as many arguments as were given, which would otherwise be reported by the
+    /// compiler deep inside the expansion of `eprintln!`
+    fn check(&self) -> Result<(), String> {
+        for format in &[&self.format, &self.labelled] {
+            let placeholders = placeholder_count(format);
+            if placeholders != self.args.len() {
+                return Err(format!("Trace {:?} has {} placeholders for {} arguments. This is a bug in wl-macro", format, placeholders, self.args.len()))
+            }
+        }
+        Ok(())
+    }
+    /// Print the trace, labelling the arguments while `wl::DEBUG_VERBOSE` is set
+    fn eprintln(self, wl: &Path) -> TokenStream {
+        if let Err(error) = self.check() {
+            return syn::Error::new(Span::call_site(), error).to_compile_error()
+        }
+        let (format, labelled, args) = (&self.format, &self.labelled, &self.args);
+        quote! {
+            if *#wl::DEBUG_VERBOSE {
+                ::std::eprintln!(#labelled, #(#args),*)
+            } else {
+                ::std::eprintln!(#format, #(#args),*)
+            }
+        }
+    }
+}
+/// The number of arguments a format string takes, one per placeholder such as `{}` or `{:?}`, skipping escaped braces
+fn placeholder_count(format: &str) -> usize {
+    let mut count = 0;
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '{' {
+            if chars.peek() == Some(&'{') {
+                chars.next();
+            } else {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+/// Count a message in the `counter` table of the interface's metadata module under the module's `stats` flag
+fn generate_count(binding: &Binding, interface: &Interface, counter: &str, opcode: usize) -> TokenStream {
+    if binding.options.stats {
+        let metadata_module = ident(&snake_case(&interface.name));
+        let counter = ident(counter);
+        quote!{ #metadata_module::#counter[#opcode].fetch_add(1, ::std::sync::atomic::Ordering::Relaxed); }
+    } else {
+        quote!{}
+    }
+}
+/// Count a sent event under the module's `stats` flag
+fn generate_event_count(event: &Event, interface: &Interface, binding: &Binding) -> TokenStream {
+    let opcode = interface.events.iter().position(|e| e.name == event.name).unwrap_or_default();
+    generate_count(binding, interface, "EVENTS_SENT", opcode)
+}
+fn generate_event_parameter(arg: &Arg, wl: &Path) -> TokenStream {
+    let arg_name = arg.parameter();
+    let arg_type = arg.event_data_type(wl);
+    quote! {
+        #arg_name: #arg_type
+    }
+}
+fn generate_event_debug_print(event: &Event, interface: &Interface, object: TokenStream, wl: &Path) -> TokenStream {
+    let interface_name = &interface.name;
+    let event_name = &event.name;
+    let mut trace = Trace::default();
+    trace.text("-> ");
+    trace.message(quote!{ #interface_name }, object, quote!{ #event_name });
+    for (index, arg) in event.args.iter().enumerate() {
+        if index > 0 {
+            trace.text(", ");
+        }
+        trace.arg(arg, arg.debug_string());
+    }
+    trace.text(")");
+    trace.eprintln(wl)
+}
+fn generate_request(request: &Request, interface: &Interface, bindings: &HashMap<String, Binding>, enum_examples: Option<&EnumExamples>, wl: &Path) -> TokenStream {
+    let binding = &bindings[&camel_case(&interface.name)];
+    let request_name = ident(&binding.method_name(&request.name));
+    let alias = binding.method_alias(&request.name);
+    let request_summary = request.summary.iter();
+    let request_description = request.description.iter();
+    let owning_interface = &interface.name;
+    let example = enum_examples.map(|enum_examples| doc_example(&binding.method_name(&request.name), &request.args, interface, Some(&binding.options), enum_examples)).into_iter();
+    // The provided methods of `default_error` bindings leave the arguments unused
+    let unused = bindings[&camel_case(owning_interface)].options.default_error;
+    let parameters = request.args.iter().map(|arg| if unused {
+        let arg_name = format_ident!("_{}", arg.parameter());
+        let arg_type = arg.request_data_type(owning_interface, bindings, wl);
+        quote!{ #arg_name: #arg_type }
+    } else {
+        generate_parameter(arg, owning_interface, bindings, wl)
+    });
+    let context_name = if unused { format_ident!("_context") } else { format_ident!("context") };
+    let context = bindings[&camel_case(owning_interface)].options.context.iter().map(|context| quote!{ #context_name: &mut #context, });
+    let untyped_notes = request.args.iter()
+        .filter(|arg| arg.is_loose(owning_interface, bindings))
+        .map(|arg| format!("`wl_{}` is untyped because interface `{}` is not bound", snake_case(&arg.name), arg.interface.as_deref().unwrap_or_default()));
+    let body = if unused {
+        let error_message = format!("{}.{} is not implemented", interface.name, request.name);
+        quote! {
+            {
+                self::not_implemented(client, #wl::Object::object(self), #error_message)
+            }
+        }
+    } else {
+        quote!{ ; }
+    };
+    let (attributes, attribute_errors) = extension_attributes(&request.attrs, &format!("request \"{}.{}\"", interface.name, request.name));
+    quote! {
+        #attribute_errors
+        #(#[doc = #request_summary])*
+        #[doc = "\n"]
+        #(#[doc = #request_description])*
+        #(#[doc = "\n"] #[doc = #untyped_notes])*
+        #(#[doc = "\n"] #[doc = #example])*
+        #alias
+        #(#attributes)*
+        fn #request_name(&mut self, client: &mut #wl::server::Client, #(#context)* #(#parameters),*) -> #wl::server::Result<()> #body
+    }
+}
+/// The expression of an enum's first entry by `(interface, enum)`, with the type of its constants
+type EnumExamples = HashMap<(String, String), (String, DataType)>;
+fn enum_examples(protocol: &Protocol, enum_representations: &HashMap<(String, String), DataType>) -> EnumExamples {
+    protocol.interfaces.iter()
+        .flat_map(|interface| interface.enums.iter().map(move |e| (interface, e)))
+        .filter_map(|(interface, e)| {
+            let key = (interface.name.clone(), e.name.clone());
+            let representation = enum_representations.get(&key).copied().unwrap_or(DataType::Uint);
+            let entry = e.entries.first()?;
+            let example = format!("{}{}::{}", camel_case(&interface.name), camel_case(&e.name), normalise_entry_name(e, &entry.name));
+            Some((key, (example, representation)))
+        })
+        .collect()
+}
+/// A call of a request or event method for its documentation, marked `ignore` as the variables are left undeclared
+fn doc_example(method: &str, args: &[Arg], interface: &Interface, request_options: Option<&BindingOptions>, enum_examples: &EnumExamples) -> String {
+    // Objects are named after their interface without the prefix, e.g. `surface` for `wl_surface`
+    let object = interface.name.split_once('_').map(|(_, name)| name).unwrap_or(&interface.name);
+    let is_request = request_options.is_some();
+    let option = |option: fn(&BindingOptions) -> bool| request_options.map(option).unwrap_or(false);
+    let mut call_args = vec!["client".to_string()];
+    if option(|options| options.context.is_some()) {
+        call_args.push("context".to_string());
+    }
+    call_args.extend(args.iter().map(|arg| {
+        let name = ident(&snake_case(&arg.name)).to_string();
+        let enum_example = arg.enum_key(&interface.name).and_then(|key| enum_examples.get(&key));
+        match (arg.kind, enum_example) {
+            (DataType::Int | DataType::Uint, Some((example, representation))) if *representation == arg.kind => format!("*{}", example),
+            (DataType::Int, Some((example, _))) => format!("*{} as i32", example),
+            (DataType::Uint, Some((example, _))) => format!("*{} as u32", example),
+            (DataType::Int | DataType::Uint, None) => "0".to_string(),
+            (DataType::Fixed, _) if option(|options| options.fixed_as_f64) => "0.0".to_string(),
+            (DataType::Fixed, _) => "Fixed::default()".to_string(),
+            (DataType::String, _) if is_request => "String::new()".to_string(),
+            (DataType::String, _) => "\"\"".to_string(),
+            (DataType::Array, _) if option(|options| options.borrowed_args) => "&[]".to_string(),
+            (DataType::Array, _) => "Array::default()".to_string(),
+            (DataType::Object, _) if arg.nullable && is_request => format!("Nullable::Object({})", name),
+            (DataType::Object, _) if arg.nullable => format!("Nullable::Object(&{})", name),
+            (DataType::Object | DataType::Fd, _) if !is_request => format!("&{}", name),
+            (DataType::Object | DataType::Fd | DataType::NewId, _) => name
+        }
+    }));
+    format!("```ignore\n{}.{}({})?;\n```", ident(object), ident(method), call_args.join(", "))
+}
+fn generate_parameter(arg: &Arg, owning_interface: &str, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let arg_name = arg.parameter();
+    let arg_type = arg.request_data_type(owning_interface, bindings, wl);
+    quote! {
+        #arg_name: #arg_type
+    }
+}
+fn generate_request_dispatch(request: &Request, opcode: u16, interface: &Interface, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let interface_string = &interface.name;
+    let mut request_name = ident(&bindings[&camel_case(interface_string)].method_name(&request.name));
+    request_name.set_span(bindings[&camel_case(interface_string)].implementation.span());
+    let arg_names = request.args.iter().map(|arg| arg.parameter());
+    let arg_getters = (0..request.args.len()).map(|index| generate_arg_getter(request, index, opcode, interface, bindings, wl));
+    let deferred_leases = generate_deferred_leases(request, opcode, interface, bindings, wl);
+    let debug_print = generate_debug(&bindings[&camel_case(interface_string)], quote!{ #wl::DEBUG }, generate_request_debug_print(request, &request.args, None, interface, &bindings[&camel_case(interface_string)], wl));
+    let context = &bindings[&camel_case(interface_string)].options.context;
+    let context_arg = context.iter().map(|_| quote!{ &mut context });
+    let invocation = quote!{ lease.#request_name(client #(, #context_arg)* #(, #arg_names)*) };
+    let call = match context {
+        // The context is taken from the client for the call so that both may be borrowed mutably, and is put back
+        // before the handler's result is propagated so that it is there for the next request whether or not this one failed
+        Some(context) => quote! {
+            let mut context = client.take_data::<#context>()?;
+            let result = #invocation;
+            client.put_data(context);
+        },
+        None => quote!{ let result = #invocation; }
+    };
+    let call = if request.destructor && display_binding(bindings).is_some() {
+        let mark_destroyed = bindings[&camel_case(interface_string)].options.runtime.destroyed_objects.then(|| quote! {
+            client.mark_destroyed(object);
+        });
+        quote! {
+            let object = lease.object();
+            #call
+            result?;
+            ::std::mem::drop(lease);
+            #mark_destroyed
+            self::delete_id(client, object)
+        }
+    } else if context.is_some() {
+        quote! {
+            #call
+            result
+        }
+    } else {
+        invocation
+    };
+    let count = generate_count(&bindings[&camel_case(interface_string)], interface, "REQUESTS_DISPATCHED", opcode as usize);
+    // Checked before any argument is decoded, so that no file descriptor is taken from the client for a denied request
+    let privilege_check = match &bindings[&camel_case(interface_string)].options.privileged {
+        Some(predicate) if request.is_privileged(interface) => {
+            let request_string = format!("{}.{}", interface.name, request.name);
+            quote! {
+                if !#predicate(client) {
+                    return ::std::result::Result::Err(#wl::DispatchError::AccessDenied {
+                        request: #request_string,
+                        object: lease.object()
+                    }.into())
+                }
+            }
+        },
+        _ => quote!{}
+    };
+    // Only requests with arguments on the wire read the message
+    let args_binding = request.args.iter().any(Arg::reads_args).then(|| quote!{ let mut args = message.args(); });
     quote! {
         #opcode => {
+            #privilege_check
+            #count
+            #args_binding
             #(#arg_getters)*
-            if *::wl::DEBUG {
-                #debug_print
+            #deferred_leases
+            #debug_print
+            #call
+        }
+    }
+}
+/// Whether object arguments are decoded as ids and leased once every id is known, so duplicates can be rejected
+fn defers_leases(request: &Request, binding: &Binding) -> bool {
+    binding.options.runtime.duplicate_objects && request.args.iter().filter(|arg| arg.kind == DataType::Object).count() > 1
+}
+/// Evaluate a getter, tracing the request up to the failing argument when it fails to decode. Arguments decoded but
+/// then rejected with `generate_rejection` are returned without the trace
+fn generate_traced_getter(getter: TokenStream, request: &Request, index: usize, opcode: u16, interface: &Interface, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let failure_print = generate_request_debug_print(request, &request.args[..index], Some((&request.args[index], opcode)), interface, &bindings[&camel_case(&interface.name)], wl);
+    let failure_print = generate_debug(&bindings[&camel_case(&interface.name)], quote!{ #wl::DEBUG }, failure_print);
+    quote! {
+        match (|| -> #wl::server::Result<#wl::server::Result<_>> { ::std::result::Result::Ok(::std::result::Result::Ok(#getter)) })() {
+            ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+            ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => return ::std::result::Result::Err(rejection),
+            ::std::result::Result::Err(error) => {
+                #failure_print
+                return ::std::result::Result::Err(error)
             }
-            lease.#request_name(client #(, #arg_names)*)
         }
     }
 }
-fn generate_arg_getter(arg: &Arg, owning_interface: &String, bindings: &HashMap<String, Binding>) -> TokenStream {
-    let arg_name = format_ident!("wl_{}", arg.name.to_snake_case());
-    let getter = arg.getter(owning_interface, bindings);
+/// Reject an argument from within the getter of `generate_traced_getter`, as it decoded but is not acceptable
+fn generate_rejection(error: TokenStream) -> TokenStream {
+    quote!{ return ::std::result::Result::Ok(::std::result::Result::Err(#error.into())) }
+}
+/// Decode an argument, tracing the request up to the failing argument when decoding fails
+fn generate_arg_getter(request: &Request, index: usize, opcode: u16, interface: &Interface, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let arg = &request.args[index];
+    let arg_name = arg.parameter();
+    let binding = &bindings[&camel_case(&interface.name)];
+    let deferred = arg.kind == DataType::Object && defers_leases(request, binding);
+    let error = |message: String| {
+        let error = syn::Error::new(binding.implementation.span(), message + &interface.error_context()).to_compile_error();
+        quote!{ let #arg_name = #error; }
+    };
+    if arg.allow_self && (arg.kind != DataType::Object || arg.interface.as_ref() != Some(&interface.name)) {
+        return error(format!("Argument {:?} of {}.{} is not an object of interface {:?} so cannot declare `allow_self`", arg.name, interface.name, request.name, interface.name))
+    }
+    let getter = match arg.kind {
+        _ if deferred => quote!{args.next_u32()?},
+        DataType::Object => generate_object_lease(arg, quote!{args.next_u32()?}, request, interface, bindings, wl),
+        // The runtime's error does not say which argument was malformed
+        DataType::String if binding.options.runtime.malformed_strings => {
+            let request_string = format!("{}.{}", interface.name, request.name);
+            let arg_string = &arg.name;
+            quote! {
+                args.next_str().map_err(|error| #wl::DispatchError::MalformedString {
+                    request: #request_string,
+                    argument: #arg_string,
+                    error: ::std::boxed::Box::new(error)
+                })?
+            }
+        },
+        _ => arg.getter(&interface.name, bindings, wl)
+    };
+    // The length is checked before decoding so that an oversized argument is never allocated
+    let getter = match (arg.kind, arg.max_len.or(binding.options.max_arg_len)) {
+        (DataType::String | DataType::Array, Some(limit)) if !binding.options.runtime.argument_limits => return error(format!("Argument {:?} of {}.{} is limited to {} bytes, which requires `#![runtime(argument_limits)]`", arg.name, interface.name, request.name, limit)),
+        (DataType::String | DataType::Array, Some(limit)) => {
+            let request_string = format!("{}.{}", interface.name, request.name);
+            let arg_string = &arg.name;
+            let rejection = generate_rejection(quote! {
+                #wl::DispatchError::ArgumentTooLong {
+                    request: #request_string,
+                    argument: #arg_string,
+                    limit: #limit
+                }
+            });
+            quote! {{
+                if args.peek_u32()? > #limit {
+                    #rejection
+                }
+                #getter
+            }}
+        },
+        _ if arg.max_len.is_some() => return error(format!("Argument {:?} of {}.{} is not a string or array so cannot declare `max_len`", arg.name, interface.name, request.name)),
+        _ => getter
+    };
+    let getter = match arg.kind {
+        DataType::Fixed if binding.options.fixed_as_f64 => quote!{ ::std::primitive::f64::from(#getter) },
+        _ => getter
+    };
+    let getter = match (arg.kind, arg.range) {
+        (DataType::Fixed, Some([min, max])) if min <= max && !binding.options.runtime.argument_ranges => return error(format!("Argument {:?} of {}.{} is limited to [{}, {}], which requires `#![runtime(argument_ranges)]`", arg.name, interface.name, request.name, min, max)),
+        (DataType::Fixed, Some([min, max])) if min <= max => {
+            let request_string = format!("{}.{}", interface.name, request.name);
+            let arg_string = &arg.name;
+            let float = if binding.options.fixed_as_f64 {
+                quote!{ value }
+            } else {
+                quote!{ ::std::primitive::f64::from(value) }
+            };
+            let rejection = generate_rejection(quote! {
+                #wl::DispatchError::ArgumentOutOfRange {
+                    request: #request_string,
+                    argument: #arg_string,
+                    value: float,
+                    min: #min,
+                    max: #max
+                }
+            });
+            quote! {{
+                let value = #getter;
+                let float = #float;
+                if !(#min..=#max).contains(&float) {
+                    #rejection
+                }
+                value
+            }}
+        },
+        (DataType::Fixed, Some([min, max])) => return error(format!("Argument {:?} of {}.{} has range [{}, {}], which must be written as [min, max]", arg.name, interface.name, request.name, min, max)),
+        (_, Some(_)) => return error(format!("Argument {:?} of {}.{} is not fixed so cannot declare `range`", arg.name, interface.name, request.name)),
+        (_, None) => getter
+    };
+    let decode = generate_traced_getter(getter, request, index, opcode, interface, bindings, wl);
+    if arg.since.is_none() {
+        if let Some(gated) = request.args[..index].iter().find(|previous| previous.since.is_some()) {
+            return error(format!("Argument {:?} of {}.{} follows since-gated argument {:?} so must also declare `since`", arg.name, interface.name, request.name, gated.name))
+        }
+        return quote!{ let #arg_name = #decode; }
+    }
+    if !binding.options.runtime.short_messages {
+        return error(format!("Argument {:?} of {}.{} is since-gated, which requires `#![runtime(short_messages)]`", arg.name, interface.name, request.name))
+    }
+    // Older clients send a shorter message, so once the arguments run out every following since-gated argument is absent
+    match arg.absent_value(wl) {
+        Ok(_) if deferred => quote! {
+            let #arg_name = if args.is_empty() {
+                0
+            } else {
+                #decode
+            };
+        },
+        Ok(absent) => {
+            let absent = if arg.allow_self {
+                quote!{ self::ReceiverOr::Object(#absent) }
+            } else {
+                absent
+            };
+            quote! {
+                let #arg_name = if args.is_empty() {
+                    #absent
+                } else {
+                    #decode
+                };
+            }
+        },
+        Err(message) => error(format!("{} ({}.{})", message, interface.name, request.name))
+    }
+}
+/// Whether any request argument declares `allow_self`, requiring the `ReceiverOr` type
+fn allows_self(interfaces: &[&Interface]) -> bool {
+    interfaces.iter().flat_map(|interface| &interface.requests).flat_map(|request| &request.args).any(|arg| arg.allow_self)
+}
+/// The type of object arguments declaring `allow_self`
+fn generate_receiver_or() -> TokenStream {
     quote! {
-        let #arg_name = #getter;
+        /// An object argument that may name the object receiving the request, which is already borrowed as `self`
+        #[derive(::std::fmt::Debug)]
+        pub enum ReceiverOr<T> {
+            /// The argument is the object receiving the request
+            Receiver,
+            Object(T)
+        }
+        impl<T: ::std::fmt::Display> ::std::fmt::Display for ReceiverOr<T> {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self {
+                    Self::Receiver => ::std::write!(f, "self"),
+                    Self::Object(object) => object.fmt(f)
+                }
+            }
+        }
     }
 }
-fn generate_request_debug_print(request: &Request, interface: &Interface) -> TokenStream {
+/// Lease the object with the given id for an object argument. The object receiving the request is already leased, so
+/// its id is rejected for arguments of the receiver's interface unless the argument declares `allow_self`, when it is
+/// received as `ReceiverOr::Receiver`
+fn generate_object_lease(arg: &Arg, id: TokenStream, request: &Request, interface: &Interface, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    let lease = arg.lease(quote!{id}, &interface.name, bindings, wl);
+    let request_string = format!("{}.{}", interface.name, request.name);
+    let binding = &bindings[&camel_case(&interface.name)];
+    if arg.allow_self {
+        quote! {{
+            let id = #id;
+            if id == lease.object() {
+                self::ReceiverOr::Receiver
+            } else {
+                self::ReceiverOr::Object(#lease)
+            }
+        }}
+    } else if binding.options.runtime.duplicate_objects && arg.interface.as_ref() == Some(&interface.name) {
+        let rejection = generate_rejection(quote!{ #wl::DispatchError::DuplicateObject { request: #request_string, object: id } });
+        quote! {{
+            let id = #id;
+            if id == lease.object() {
+                #rejection
+            }
+            #lease
+        }}
+    } else {
+        arg.lease(id, &interface.name, bindings, wl)
+    }
+}
+/// Reject requests naming one object in several arguments, then lease the objects whose ids were decoded
+fn generate_deferred_leases(request: &Request, opcode: u16, interface: &Interface, bindings: &HashMap<String, Binding>, wl: &Path) -> TokenStream {
+    if !defers_leases(request, &bindings[&camel_case(&interface.name)]) {
+        return quote!{}
+    }
+    let request_string = format!("{}.{}", interface.name, request.name);
+    let object_ids: Vec<_> = request.args.iter()
+        .filter(|arg| arg.kind == DataType::Object)
+        .map(|arg| arg.parameter())
+        .collect();
+    let leases = request.args.iter().enumerate().filter(|(_, arg)| arg.kind == DataType::Object).map(|(index, arg)| {
+        let arg_name = arg.parameter();
+        let lease = generate_traced_getter(generate_object_lease(arg, quote!{#arg_name}, request, interface, bindings, wl), request, index, opcode, interface, bindings, wl);
+        quote!{ let #arg_name = #lease; }
+    });
+    quote! {
+        {
+            let ids = [#(#object_ids),*];
+            for (index, id) in ids.iter().enumerate() {
+                if *id != 0 && ids[..index].contains(id) {
+                    return ::std::result::Result::Err(#wl::DispatchError::DuplicateObject { request: #request_string, object: *id }.into())
+                }
+            }
+        }
+        #(#leases)*
+    }
+}
+/// Trace the decoded arguments of a request, noting the argument and opcode that failed to decode if any
+fn generate_request_debug_print(request: &Request, decoded: &[Arg], failed: Option<(&Arg, u16)>, interface: &Interface, binding: &Binding, wl: &Path) -> TokenStream {
     let interface_name = &interface.name;
     let request_name = &request.name;
-    let args = request.args.iter().map(|arg| {
-        let arg_name = format_ident!("wl_{}", arg.name);
-        quote!{#arg_name}
-    });
-    let mut format_string = "{}@{}.{}(".to_string();
-    let mut first = true;
-    for arg in &request.args {
-        if !first  {
-            format_string.push_str(", ")
+    let mut trace = Trace::default();
+    trace.message(quote!{ #interface_name }, quote!{ lease.object() }, quote!{ #request_name });
+    for (index, arg) in decoded.iter().enumerate() {
+        if index > 0 {
+            trace.text(", ");
+        }
+        // Borrowed arrays are plain slices without the runtime's `Display` implementation
+        if arg.kind == DataType::Array && binding.options.borrowed_args {
+            trace.arg(arg, "{:?}");
         } else {
-            first = false
+            trace.arg(arg, arg.debug_string());
         }
-        format_string.push_str(arg.debug_string());
     }
-    format_string.push(')');
-    quote! {
-        ::std::eprintln!(#format_string, #interface_name, lease.object(), #request_name, #(#args),*)
+    if let Some((arg, opcode)) = failed {
+        if !decoded.is_empty() {
+            trace.text(", ");
+        }
+        trace.text(&format!("<malformed {}>) [opcode {}]", arg.label(), opcode));
+    } else {
+        trace.text(")");
     }
+    trace.eprintln(wl)
 }
-fn generate_enums(interface: &Interface) -> TokenStream {
-    let enums = interface.enums.iter().map(|e| generate_enum(e, interface));
+fn generate_enums(interface: &Interface, enums: &[&Enum], representations: &HashMap<(String, String), DataType>, legacy_entry_names: bool, protocol_errors: bool, wl: &Path) -> TokenStream {
+    let enums = enums.iter().map(|e| {
+        let representation = representations.get(&(interface.name.clone(), e.name.clone())).copied().unwrap_or(DataType::Uint);
+        generate_enum(e, interface, representation, legacy_entry_names, protocol_errors, wl)
+    });
     quote! {
         #(#enums)*
     }
 }
-fn generate_enum(e: &Enum, interface: &Interface) -> TokenStream {
-    let enum_name = format_ident!("{}{}", interface.name.to_camel_case(), e.name.to_camel_case());
+/// Enums with more entries than this validate values by a binary search rather than a match
+const LARGE_ENUM_ENTRIES: usize = 32;
+fn generate_enum(e: &Enum, interface: &Interface, representation: DataType, legacy_entry_names: bool, protocol_errors: bool, wl: &Path) -> TokenStream {
+    let enum_name = ident(&format!("{}{}", camel_case(&interface.name), camel_case(&e.name)));
     let enum_summary = e.summary.iter();
     let enum_description = e.description.iter();
     let enum_wl_name = format!("{}.{}", interface.name, e.name);
-    let normalise_entry_name = |name: &str| if name.chars().next().map(|c| c.is_alphabetic()).unwrap_or(false) {
-        name.to_shouty_snake_case()
+    let normalise_entry_name = |name: &str| normalise_entry_name(e, name);
+    let (repr, variant) = if representation == DataType::Int {
+        (quote!{ i32 }, quote!{ value as u32 })
     } else {
-        format!("{}_{}", interface.name.to_shouty_snake_case(), name.to_shouty_snake_case())
+        (quote!{ u32 }, quote!{ value })
+    };
+    let entry_value = |entry: &Entry| {
+        let value = if entry.hex && entry.value >= 0 {
+            // Written as in the specification, where values such as fourcc codes are easier to audit in hexadecimal
+            let fits = if representation == DataType::Int { i32::try_from(entry.value).is_ok() } else { u32::try_from(entry.value).is_ok() };
+            fits.then(|| {
+                let value = LitInt::new(&format!("{:#x}", entry.value), Span::call_site());
+                quote!{#value}
+            })
+        } else if representation == DataType::Int {
+            i32::try_from(entry.value).map(|value| quote!{#value}).ok()
+        } else {
+            u32::try_from(entry.value).map(|value| quote!{#value}).ok()
+        };
+        value.unwrap_or_else(|| syn::Error::new(Span::call_site(), format!("Value {} of entry {:?} in enum {:?} does not fit in {}{}", entry.value, entry.name, enum_wl_name, repr, interface.error_context())).to_compile_error())
     };
     let entries = e.entries.iter().map(|entry| {
-        let entry_name = format_ident!("{}", normalise_entry_name(&entry.name));
+        let entry_name = ident(&normalise_entry_name(&entry.name));
         let entry_summary = entry.summary.iter();
         let entry_description = entry.description.iter();
-        let value = entry.value;
+        let value = entry_value(entry);
+        // The blank line only separates a summary from a description, and is repeated for every entry of large enums
+        let separator = entry.description.iter().map(|_| quote!{ #[doc = "\n"] });
         quote!{
             #(#[doc = #entry_summary])*
-            #[doc = "\n"]
+            #(#separator)*
             #(#[doc = #entry_description])*
-            pub const #entry_name: u32 = #value
+            pub const #entry_name: Self = Self(#value)
         }
     });
+    let legacy_entries = e.entries.iter().filter(|_| legacy_entry_names).filter_map(|entry| {
+        let legacy = ident(&legacy_entry_name(interface, &entry.name)?);
+        let entry_name = ident(&normalise_entry_name(&entry.name));
+        let note = format!("renamed to `{}`", entry_name);
+        Some(quote! {
+            #[deprecated(note = #note)]
+            pub const #legacy: Self = Self::#entry_name
+        })
+    });
     let entry_constructors = e.entries.iter().map(|entry| {
-        let entry_name = format_ident!("{}", normalise_entry_name(&entry.name));
-        let value = entry.value;
+        let entry_name = ident(&normalise_entry_name(&entry.name));
+        let value = entry_value(entry);
         quote!{
-            #value => ::std::result::Result::Ok(Self(Self::#entry_name))
+            #value => ::std::result::Result::Ok(Self::#entry_name)
         }
     });
+    let no_variant = quote!{ ::std::result::Result::Err(#wl::DispatchError::NoVariant { name: Self::ENUM_NAME, variant: #variant }.into()) };
+    let validation = if e.entries.len() > LARGE_ENUM_ENTRIES {
+        // A match over every entry of enums such as `wl_shm.format` is slow to compile, so values are searched instead
+        let mut sorted: Vec<&Entry> = e.entries.iter().collect();
+        sorted.sort_by_key(|entry| entry.value);
+        sorted.dedup_by_key(|entry| entry.value);
+        let values = sorted.iter().map(|entry| entry_value(entry));
+        quote! {
+            const VALUES: &[#repr] = &[#(#values),*];
+            if VALUES.binary_search(&value).is_ok() {
+                ::std::result::Result::Ok(Self(value))
+            } else {
+                #no_variant
+            }
+        }
+    } else {
+        quote! {
+            match value {
+                #(#entry_constructors,)*
+                _ => #no_variant
+            }
+        }
+    };
+    let error = e.is_error_enum().then(|| generate_error_enum(e, &enum_name, representation, protocol_errors, wl));
+    let default = e.entries.iter().find(|entry| entry.value == 0).map(|entry| {
+        let entry_name = ident(&normalise_entry_name(&entry.name));
+        quote! {
+            impl ::std::default::Default for #enum_name {
+                fn default() -> Self {
+                    Self::#entry_name
+                }
+            }
+        }
+    });
+    let (attributes, attribute_errors) = extension_attributes(&e.attrs, &format!("enum {:?}", enum_wl_name));
     quote! {
-        #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone, ::std::cmp::Eq, ::std::cmp::PartialEq)]
-        pub struct #enum_name(u32);
+        #attribute_errors
+        #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone, ::std::cmp::Eq, ::std::cmp::PartialEq, ::std::hash::Hash, ::std::cmp::PartialOrd, ::std::cmp::Ord)]
+        #[repr(transparent)]
+        #(#attributes)*
+        pub struct #enum_name(#repr);
         #(#[doc = #enum_summary])*
         #[doc = "\n"]
         #(#[doc = #enum_description])*
         impl #enum_name {
             pub const ENUM_NAME: &'static str = #enum_wl_name;
             #(#entries;)*
-            pub fn new(value: u32) -> ::wl::server::Result<Self> {
+            #(#legacy_entries;)*
+            pub fn new(value: #repr) -> #wl::server::Result<Self> {
                 use ::std::convert::Into;
-                match value {
-                    #(#entry_constructors,)*
-                    _ => ::std::result::Result::Err(::wl::DispatchError::NoVariant { name: Self::ENUM_NAME, variant: value }.into())
-                }
+                #validation
             }
         }
-        impl ::std::convert::Into<u32> for #enum_name {
-            fn into(self) -> u32 {
+        impl ::std::convert::Into<#repr> for #enum_name {
+            fn into(self) -> #repr {
                 self.0
             }
         }
         impl ::std::ops::Deref for #enum_name {
-            type Target = u32;
+            type Target = #repr;
             fn deref(&self) -> &Self::Target {
                 &self.0
             }
         }
+        #default
+        #error
+    }
+}
+/// The module of an enum's entries as integers within the `raw` module, e.g. `wl_shm_format`
+fn raw_module(interface: &Interface, e: &Enum) -> String {
+    format!("{}_{}", snake_case(&interface.name), snake_case(&e.name))
+}
+/// The `raw` module, holding a module per enum with its entries as integer constants for matching values received as
+/// integers
+fn generate_raw_entries(enum_interfaces: &[(&Interface, Vec<&Enum>)], representations: &HashMap<(String, String), DataType>) -> TokenStream {
+    if enum_interfaces.is_empty() {
+        return quote!{}
+    }
+    let modules = enum_interfaces.iter().flat_map(|(interface, enums)| enums.iter().map(move |e| (*interface, *e))).map(|(interface, e)| {
+        let enum_name = ident(&format!("{}{}", camel_case(&interface.name), camel_case(&e.name)));
+        let module = ident(&raw_module(interface, e));
+        let doc = format!("The entries of [`{}`](super::{}) as integers", enum_name, enum_name);
+        let repr = match representations.get(&(interface.name.clone(), e.name.clone())) {
+            Some(DataType::Int) => quote!{ i32 },
+            _ => quote!{ u32 }
+        };
+        let entries = e.entries.iter().map(|entry| {
+            let entry_name = ident(&normalise_entry_name(e, &entry.name));
+            quote!{ pub const #entry_name: #repr = super::super::#enum_name::#entry_name.0; }
+        });
+        quote! {
+            #[doc = #doc]
+            pub mod #module {
+                #(#entries)*
+            }
+        }
+    });
+    quote! {
+        /// The entries of each enum as plain integer constants, e.g. `raw::wl_shm_format::ARGB8888`, for matching values
+        /// received as integers without converting them to the enum first
+        pub mod raw {
+            #(#modules)*
+        }
+    }
+}
+/// `Display` and `Error` for an enum of protocol error codes, displaying each entry by its summary, along with the
+/// conversion to the `DispatchError` reported to the client by `wl_display.error` where the runtime has one
+fn generate_error_enum(e: &Enum, enum_name: &Ident, representation: DataType, protocol_errors: bool, wl: &Path) -> TokenStream {
+    // Entries sharing a value are displayed by the first of them
+    let mut values = Vec::new();
+    let arms: Vec<TokenStream> = e.entries.iter().filter(|entry| {
+        let first = !values.contains(&entry.value);
+        values.push(entry.value);
+        first
+    }).map(|entry| {
+        let entry_name = ident(&normalise_entry_name(e, &entry.name));
+        let message = entry.summary.clone().unwrap_or_else(|| entry.name.replace('_', " "));
+        quote!{ Self::#entry_name => f.write_str(#message) }
+    }).collect();
+    let code = if representation == DataType::Int {
+        quote!{ self.0 as u32 }
+    } else {
+        quote!{ self.0 }
+    };
+    let protocol_error = protocol_errors.then(|| quote! {
+        impl #enum_name {
+            /// The error to return from a request handler to post this error with `message` on the object the request
+            /// was sent to, through the generated `report` function
+            pub fn protocol_error(self, message: impl ::std::convert::Into<::std::string::String>) -> #wl::DispatchError {
+                #wl::DispatchError::Protocol {
+                    name: Self::ENUM_NAME,
+                    code: #code,
+                    message: message.into()
+                }
+            }
+        }
+    });
+    quote! {
+        impl ::std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                    #(#arms,)*
+                    _ => ::std::write!(f, "{} {}", Self::ENUM_NAME, self.0)
+                }
+            }
+        }
+        impl ::std::error::Error for #enum_name {}
+        #protocol_error
+    }
+}
+/// Conversions in both directions between each pair of enums related by `maps_to`, mapping entries by name. Pairs
+/// annotated from both sides are generated once, and mappings from enums this module does not generate are skipped
+fn generate_enum_mappings(mappings: &[EnumMapping], enum_interfaces: &[(&Interface, Vec<&Enum>)], enum_representations: &HashMap<(String, String), DataType>) -> TokenStream {
+    let is_generated = |interface: &Interface, e: &Enum| enum_interfaces.iter()
+        .any(|(generated, enums)| generated.name == interface.name && enums.iter().any(|generated| generated.name == e.name));
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let conversions = mappings.iter().filter(|mapping| is_generated(mapping.from.0, mapping.from.1)).map(|mapping| {
+        let ((interface, e), (target, target_enum)) = (mapping.from, mapping.to);
+        let from_name = format!("{}.{}", interface.name, e.name);
+        let to_name = format!("{}.{}", target.name, target_enum.name);
+        if !is_generated(target, target_enum) {
+            let message = format!("Enum {:?} maps to {:?}, which is not generated by this module as interface {:?} is not bound and no bound interface references the enum{}", from_name, to_name, target.name, interface.error_context());
+            return syn::Error::new(Span::call_site(), message).to_compile_error()
+        }
+        let mut pair = [from_name, to_name];
+        pair.sort();
+        let [first, second] = pair;
+        if pairs.contains(&(first.clone(), second.clone())) {
+            return quote!{}
+        }
+        pairs.push((first, second));
+        let forward = generate_enum_conversion((interface, e), (target, target_enum), enum_representations);
+        let backward = generate_enum_conversion((target, target_enum), (interface, e), enum_representations);
+        quote! {
+            #forward
+            #backward
+        }
+    });
+    quote! {
+        #(#conversions)*
+    }
+}
+/// `From` where every value of the source enum has an entry of the same name in the target, otherwise `TryFrom`
+/// returning the unmapped value. The impl is documented with a table of the entry each source entry becomes
+fn generate_enum_conversion((interface, e): (&Interface, &Enum), (target, target_enum): (&Interface, &Enum), enum_representations: &HashMap<(String, String), DataType>) -> TokenStream {
+    let from = ident(&format!("{}{}", camel_case(&interface.name), camel_case(&e.name)));
+    let to = ident(&format!("{}{}", camel_case(&target.name), camel_case(&target_enum.name)));
+    let mapped = |entry: &Entry| target_enum.entries.iter().find(|other| other.name == entry.name);
+    let mut doc = format!("Converts `{}.{}` entries to the `{}.{}` entries of the same name:\n\n| `{}` | `{}` |\n|---|---|\n", interface.name, e.name, target.name, target_enum.name, from, to);
+    for entry in &e.entries {
+        let mapping = mapped(entry).map(|other| format!("`{}`", normalise_entry_name(target_enum, &other.name))).unwrap_or_else(|| "none, the conversion fails".to_string());
+        doc += &format!("| `{}` | {} |\n", normalise_entry_name(e, &entry.name), mapping);
     }
-}
\ No newline at end of file
+    // Entries sharing a value are matched once, by the first of them with a counterpart
+    let mut values = Vec::new();
+    let arms: Vec<TokenStream> = e.entries.iter().filter_map(|entry| {
+        let other = mapped(entry)?;
+        if values.contains(&entry.value) {
+            return None
+        }
+        values.push(entry.value);
+        let entry_name = ident(&normalise_entry_name(e, &entry.name));
+        let other_name = ident(&normalise_entry_name(target_enum, &other.name));
+        Some(quote!{ #from::#entry_name => #to::#other_name })
+    }).collect();
+    let total = e.entries.iter().all(|entry| values.contains(&entry.value));
+    if total {
+        // Only code in the module can build a value that is no entry of the source, which keeps its value rather than
+        // panicking
+        let representation = |interface: &Interface, e: &Enum| enum_representations.get(&(interface.name.clone(), e.name.clone())).copied().unwrap_or(DataType::Uint);
+        let unmapped = match (representation(interface, e), representation(target, target_enum)) {
+            (source, destination) if source == destination => quote!{ #to(value.0) },
+            (_, DataType::Int) => quote!{ #to(value.0 as i32) },
+            _ => quote!{ #to(value.0 as u32) }
+        };
+        quote! {
+            #[doc = #doc]
+            impl ::std::convert::From<#from> for #to {
+                fn from(value: #from) -> Self {
+                    match value {
+                        #(#arms,)*
+                        _ => #unmapped
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {
+            #[doc = #doc]
+            impl ::std::convert::TryFrom<#from> for #to {
+                /// The value, which has no entry of the same name in the target enum
+                type Error = #from;
+                fn try_from(value: #from) -> ::std::result::Result<Self, Self::Error> {
+                    ::std::result::Result::Ok(match value {
+                        #(#arms,)*
+                        _ => return ::std::result::Result::Err(value)
+                    })
+                }
+            }
+        }
+    }
+}
+/// The constant of an enum entry. Entries not beginning with a letter, such as `90` of `wl_output.transform`, are
+/// prefixed with the enum's name, becoming `TRANSFORM_90`
+fn normalise_entry_name(e: &Enum, name: &str) -> String {
+    if name.chars().next().map(|c| c.is_alphabetic()).unwrap_or(false) {
+        shouty_snake_case(name)
+    } else {
+        format!("{}_{}", shouty_snake_case(&e.name), shouty_snake_case(name))
+    }
+}
+/// The constant an entry not beginning with a letter was named before it was prefixed with the enum's name rather than
+/// the interface's, e.g. `WL_OUTPUT_90`
+fn legacy_entry_name(interface: &Interface, name: &str) -> Option<String> {
+    (!name.chars().next().map(|c| c.is_alphabetic()).unwrap_or(false))
+        .then(|| format!("{}_{}", shouty_snake_case(&interface.name), shouty_snake_case(name)))
+}
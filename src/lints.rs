@@ -0,0 +1,77 @@
+//! Non-fatal checks for likely mistakes in hand-maintained protocol specifications.
+//!
+//! Stable procedural macros cannot emit warnings, so lints are exposed through the generated `PROTOCOL_LINTS` constant
+//! and become errors under the `#![deny_lints]` module flag.
+
+use std::collections::HashMap;
+use crate::normalise_entry_name;
+use crate::protocol::*;
+
+/// Events with more arguments than this are likely to be better split
+const MAX_EVENT_ARGS: usize = 20;
+/// Interface versions beyond this are likely to be a typo
+const MAX_INTERFACE_VERSION: u32 = 32;
+
+pub fn lint(protocol: &Protocol) -> Vec<String> {
+    let mut lints = Vec::new();
+    for undeclared in &protocol.undeclared_schemas {
+        let fields: Vec<String> = undeclared.fields.iter().map(|field| format!("`{}`", field)).collect();
+        lints.push(format!("Protocol specification file {:?} declares no schema but uses {}, which older versions of wl-macro ignore. Declare `schema = {}` so that they reject it instead", undeclared.file, fields.join(", "), undeclared.schema));
+    }
+    for interface in &protocol.interfaces {
+        if interface.version > MAX_INTERFACE_VERSION {
+            lints.push(format!("Interface {:?} has version {}, greater than {}", interface.name, interface.version, MAX_INTERFACE_VERSION));
+        }
+        let latest = latest_since(protocol, interface, &mut Vec::new());
+        if !interface.trimmed && latest < interface.version {
+            let missing = if latest + 1 == interface.version {
+                format!("version {} appears", interface.version)
+            } else {
+                format!("versions {} to {} appear", latest + 1, interface.version)
+            };
+            lints.push(format!("Interface {:?} has version {} but nothing is introduced after version {}, so {} to be missing messages. Set `trimmed = true` if they were removed on purpose", interface.name, interface.version, latest, missing));
+        }
+        for request in &interface.requests {
+            if request.summary.is_none() {
+                lints.push(format!("Request {}.{} has no summary", interface.name, request.name));
+            }
+        }
+        for event in &interface.events {
+            if event.args.len() > MAX_EVENT_ARGS {
+                lints.push(format!("Event {}.{} has {} arguments, more than {}", interface.name, event.name, event.args.len(), MAX_EVENT_ARGS));
+            }
+        }
+        for e in &interface.enums {
+            if e.entries.len() == 1 {
+                lints.push(format!("Enum {}.{} has a single entry", interface.name, e.name));
+            }
+            let mut constants = HashMap::new();
+            for entry in &e.entries {
+                let constant = normalise_entry_name(e, &entry.name);
+                if let Some(existing) = constants.insert(constant.clone(), &entry.name) {
+                    lints.push(format!("Entries {:?} and {:?} of enum {}.{} both normalise to `{}`", existing, entry.name, interface.name, e.name, constant));
+                }
+            }
+        }
+    }
+    lints
+}
+
+/// The latest version anything of the interface was introduced in, or anything of an interface it creates objects of
+/// or whose objects create it, as created objects take the version of the object creating them and version bumps of
+/// either are shared
+fn latest_since<'a>(protocol: &'a Protocol, interface: &'a Interface, visited: &mut Vec<&'a str>) -> u32 {
+    visited.push(&interface.name);
+    let creates = |creator: &Interface, created: &str| creator.requests.iter().flat_map(|request| &request.args)
+        .any(|arg| arg.kind == DataType::NewId && arg.interface.as_deref() == Some(created));
+    let related: Vec<&Interface> = protocol.interfaces.iter()
+        .filter(|other| creates(interface, &other.name) || creates(other, &interface.name))
+        .collect();
+    let mut latest = interface.versioned_items().into_iter().map(|(_, since)| since).max().unwrap_or(1);
+    for other in related {
+        if !visited.contains(&other.name.as_str()) {
+            latest = latest.max(latest_since(protocol, other, visited));
+        }
+    }
+    latest
+}
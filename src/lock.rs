@@ -0,0 +1,267 @@
+//! A record of the wire format an implementation was written against, for catching incompatible protocol upgrades.
+//!
+//! With `lock = "path"` in the attribute, the first build writes each interface's version, message signatures and
+//! argument names in opcode order and enum entry values. Later builds compare the protocol against the lock and report
+//! each change that would alter the meaning of existing messages, including arguments renamed or reordered without
+//! changing their types. New interfaces, trailing messages, trailing arguments introduced by a later version and new
+//! enum entries are compatible. Setting `WL_MACRO_UPDATE_LOCK` rewrites the lock after the changes have been reviewed.
+
+use std::{collections::BTreeMap, fs, io};
+use serde::{Deserialize, Serialize};
+use crate::protocol::*;
+
+pub const UPDATE_VARIABLE: &str = "WL_MACRO_UPDATE_LOCK";
+
+#[derive(Serialize, Deserialize)]
+pub struct Lock {
+    #[serde(rename = "interface", default)]
+    interfaces: Vec<InterfaceLock>
+}
+#[derive(Serialize, Deserialize)]
+struct InterfaceLock {
+    name: String,
+    version: u32,
+    #[serde(rename = "request", default, skip_serializing_if = "Vec::is_empty")]
+    requests: Vec<MessageLock>,
+    #[serde(rename = "event", default, skip_serializing_if = "Vec::is_empty")]
+    events: Vec<MessageLock>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    enums: Vec<EnumLock>
+}
+#[derive(Serialize, Deserialize)]
+struct MessageLock {
+    name: String,
+    opcode: u16,
+    /// The wire type of each argument, e.g. `object<wl_buffer>?` or `uint since 3`
+    args: Vec<String>,
+    /// The name of each argument, so that arguments of the same type swapping places are noticed. Absent from locks
+    /// written before names were recorded, which are then not compared
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    arg_names: Vec<String>
+}
+#[derive(Serialize, Deserialize)]
+struct EnumLock {
+    name: String,
+    entries: BTreeMap<String, i64>
+}
+
+impl Lock {
+    pub fn new(protocol: &Protocol) -> Self {
+        let message = |name: &str, opcode: usize, args: &[Arg]| MessageLock {
+            name: name.to_string(),
+            opcode: crate::protocol::opcode(opcode),
+            args: args.iter().map(Arg::signature).collect(),
+            arg_names: args.iter().map(|arg| arg.name.clone()).collect()
+        };
+        Self {
+            interfaces: protocol.interfaces.iter().map(|interface| InterfaceLock {
+                name: interface.name.clone(),
+                version: interface.version,
+                requests: interface.requests.iter().enumerate().map(|(opcode, request)| message(&request.name, opcode, &request.args)).collect(),
+                events: interface.events.iter().enumerate().map(|(opcode, event)| message(&event.name, opcode, &event.args)).collect(),
+                enums: interface.enums.iter().map(|e| EnumLock {
+                    name: e.name.clone(),
+                    entries: e.entries.iter().map(|entry| (entry.name.clone(), entry.value)).collect()
+                }).collect()
+            }).collect()
+        }
+    }
+    /// Compare the protocol against the lock at `path`, returning a description of each incompatible change. The lock is
+    /// written instead if it does not exist or an update was requested by the environment
+    pub fn check(&self, path: &str) -> Result<Vec<String>, String> {
+        let locked = match fs::read_to_string(path) {
+            Ok(_) if std::env::var_os(UPDATE_VARIABLE).is_some() => return self.write(path).map(|_| Vec::new()),
+            Ok(locked) => locked,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return self.write(path).map(|_| Vec::new()),
+            Err(error) => return Err(format!("Unable to read protocol lock {:?}: {}", path, error))
+        };
+        let locked: Lock = toml::from_str(&locked).map_err(|error| format!("Unable to parse protocol lock {:?}: {}", path, error))?;
+        Ok(locked.incompatibilities(self))
+    }
+    fn write(&self, path: &str) -> Result<(), String> {
+        let header = format!("# The protocol wire format the implementation was written against. Set {} to regenerate\n\n", UPDATE_VARIABLE);
+        toml::to_string(self)
+            .map_err(|error| error.to_string())
+            .and_then(|lock| fs::write(path, header + &lock).map_err(|error| error.to_string()))
+            .map_err(|error| format!("Unable to write protocol lock {:?}: {}", path, error))
+    }
+    /// Changes from the locked protocol to `current` that break the wire format of existing messages
+    fn incompatibilities(&self, current: &Lock) -> Vec<String> {
+        let mut changes = Vec::new();
+        for locked in &self.interfaces {
+            let interface = match current.interfaces.iter().find(|interface| interface.name == locked.name) {
+                Some(interface) => interface,
+                None => {
+                    changes.push(format!("Interface {:?} was removed", locked.name));
+                    continue
+                }
+            };
+            if interface.version < locked.version {
+                changes.push(format!("Interface {:?} was downgraded from version {} to {}", locked.name, locked.version, interface.version));
+            }
+            message_incompatibilities(&mut changes, &locked.name, "Request", &locked.requests, &interface.requests);
+            message_incompatibilities(&mut changes, &locked.name, "Event", &locked.events, &interface.events);
+            for locked_enum in &locked.enums {
+                let e = match interface.enums.iter().find(|e| e.name == locked_enum.name) {
+                    Some(e) => e,
+                    None => {
+                        changes.push(format!("Enum \"{}.{}\" was removed", locked.name, locked_enum.name));
+                        continue
+                    }
+                };
+                for (entry, &value) in &locked_enum.entries {
+                    match e.entries.get(entry) {
+                        None => changes.push(format!("Entry {:?} of enum \"{}.{}\" was removed", entry, locked.name, e.name)),
+                        Some(&current) if current != value => changes.push(format!("Entry {:?} of enum \"{}.{}\" changed value from {} to {}", entry, locked.name, e.name, value, current)),
+                        Some(_) => ()
+                    }
+                }
+            }
+        }
+        changes
+    }
+}
+
+fn message_incompatibilities(changes: &mut Vec<String>, interface: &str, kind: &str, locked: &[MessageLock], current: &[MessageLock]) {
+    for message in locked {
+        let name = format!("{} \"{}.{}\"", kind, interface, message.name);
+        let current = match current.get(message.opcode as usize) {
+            Some(current) => current,
+            None => {
+                changes.push(format!("{} with opcode {} was removed", name, message.opcode));
+                continue
+            }
+        };
+        if current.name != message.name {
+            changes.push(format!("{} with opcode {} was replaced by {:?}", name, message.opcode, current.name));
+            continue
+        }
+        // Arguments may only be appended when introduced by a later version, as older clients will not send them
+        let prefix_matches = current.args.len() >= message.args.len() && current.args[..message.args.len()] == message.args[..];
+        let appended_since = current.args[message.args.len().min(current.args.len())..].iter().all(|arg| arg.contains(" since "));
+        if !prefix_matches || !appended_since {
+            changes.push(format!("{} changed signature from ({}) to ({})", name, message.args.join(", "), current.args.join(", ")));
+            continue
+        }
+        // Arguments of the same type may swap places without changing the signature, but not their names
+        let names_match = message.arg_names.is_empty() || current.arg_names.get(..message.arg_names.len()) == Some(&message.arg_names[..]);
+        if !names_match {
+            let current_names = &current.arg_names[..message.arg_names.len().min(current.arg_names.len())];
+            changes.push(format!("{} renamed or reordered its arguments from ({}) to ({})", name, message.arg_names.join(", "), current_names.join(", ")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pointer_button(protocol: &mut Protocol) -> &mut Event {
+        let pointer = protocol.interfaces.iter_mut().find(|interface| interface.name == "wl_pointer").unwrap();
+        pointer.events.iter_mut().find(|event| event.name == "button").unwrap()
+    }
+
+    fn interface<'a>(protocol: &'a mut Protocol, name: &str) -> &'a mut Interface {
+        protocol.interfaces.iter_mut().find(|interface| interface.name == name).unwrap()
+    }
+    /// The incompatibilities of the core protocol after `change`
+    fn changed(change: impl FnOnce(&mut Protocol)) -> Vec<String> {
+        let mut protocol = Protocol::load("tests/wayland.toml").unwrap();
+        let locked = Lock::new(&protocol);
+        change(&mut protocol);
+        locked.incompatibilities(&Lock::new(&protocol))
+    }
+
+    #[test]
+    fn removed_interface() {
+        let removed = changed(|protocol| protocol.interfaces.retain(|interface| interface.name != "wl_shell"));
+        assert_eq!(removed, ["Interface \"wl_shell\" was removed"]);
+    }
+
+    #[test]
+    fn downgraded_version() {
+        assert_eq!(changed(|protocol| interface(protocol, "wl_seat").version = 5), ["Interface \"wl_seat\" was downgraded from version 9 to 5"]);
+        assert!(changed(|protocol| interface(protocol, "wl_seat").version = 10).is_empty());
+    }
+
+    #[test]
+    fn changed_type() {
+        let changes = changed(|protocol| pointer_button(protocol).args[1].kind = DataType::Int);
+        assert_eq!(changes, ["Event \"wl_pointer.button\" changed signature from (uint, uint, uint, uint) to (uint, int, uint, uint)"]);
+    }
+
+    #[test]
+    fn removed_enum_entry() {
+        let changes = changed(|protocol| {
+            let shm = interface(protocol, "wl_shm");
+            shm.enums[0].entries.retain(|entry| entry.name != "invalid_fd");
+        });
+        assert_eq!(changes, ["Entry \"invalid_fd\" of enum \"wl_shm.error\" was removed"]);
+    }
+
+    #[test]
+    fn trailing_additions() {
+        let changes = changed(|protocol| {
+            let pointer = interface(protocol, "wl_pointer");
+            pointer.version += 1;
+            let mut event = pointer.events[0].clone();
+            event.name = "appended".to_string();
+            pointer.events.push(event);
+            let mut request = pointer.requests[0].clone();
+            request.name = "appended".to_string();
+            pointer.requests.push(request);
+            let mut entry = pointer.enums[0].entries[0].clone();
+            entry.name = "appended".to_string();
+            entry.value = 100;
+            pointer.enums[0].entries.push(entry);
+            let mut arg = pointer_button(protocol).args[0].clone();
+            arg.name = "appended".to_string();
+            arg.since = Some(10);
+            pointer_button(protocol).args.push(arg);
+        });
+        assert!(changes.is_empty(), "{:?}", changes);
+        // Arguments appended without `since` would be expected from older clients too
+        let changes = changed(|protocol| {
+            let arg = pointer_button(protocol).args[0].clone();
+            pointer_button(protocol).args.push(arg);
+        });
+        assert_eq!(changes, ["Event \"wl_pointer.button\" changed signature from (uint, uint, uint, uint) to (uint, uint, uint, uint, uint)"]);
+    }
+
+    #[test]
+    fn written_on_first_build_and_update() {
+        let path = std::env::temp_dir().join(format!("wl-macro-lock-{}.toml", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+        let mut protocol = Protocol::load("tests/wayland.toml").unwrap();
+        assert!(Lock::new(&protocol).check(path).unwrap().is_empty());
+        let written = fs::read_to_string(path).unwrap();
+        assert!(written.starts_with(&format!("# The protocol wire format the implementation was written against. Set {} to regenerate", UPDATE_VARIABLE)));
+        assert!(written.contains("name = \"wl_display\""));
+
+        interface(&mut protocol, "wl_seat").version = 5;
+        let downgraded = Lock::new(&protocol);
+        assert_eq!(downgraded.check(path).unwrap(), ["Interface \"wl_seat\" was downgraded from version 9 to 5"]);
+        // The lock is only compared, never rewritten, until an update is requested
+        assert_eq!(fs::read_to_string(path).unwrap(), written);
+        std::env::set_var(UPDATE_VARIABLE, "1");
+        let updated = downgraded.check(path);
+        std::env::remove_var(UPDATE_VARIABLE);
+        assert!(updated.unwrap().is_empty());
+        assert!(downgraded.check(path).unwrap().is_empty());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reordered_arguments() {
+        let mut protocol = Protocol::load("tests/wayland.toml").unwrap();
+        let locked = Lock::new(&protocol);
+        // `serial` and `time` are both `uint`, so the signature alone does not change
+        pointer_button(&mut protocol).args.swap(0, 1);
+        let current = Lock::new(&protocol);
+        assert_eq!(locked.incompatibilities(&current), ["Event \"wl_pointer.button\" renamed or reordered its arguments from (serial, time, button, state) to (time, serial, button, state)"]);
+        // Locks written before argument names were recorded only compare signatures
+        let unnamed: Lock = toml::from_str(&toml::to_string(&locked).unwrap().replace("arg_names", "unknown")).unwrap();
+        assert!(unnamed.incompatibilities(&current).is_empty());
+    }
+}
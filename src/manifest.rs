@@ -0,0 +1,160 @@
+//! A JSON description of the resolved protocol for tooling outside of Rust.
+//!
+//! When the `WL_MACRO_MANIFEST` environment variable names a directory at compile time, each `server_protocol`
+//! invocation writes `<protocol>.json` there. Writing it requires the `json` feature. Interfaces, messages, arguments and entries are listed in specification
+//! order with fixed field order so that the output is stable and may be checked in.
+
+use std::{collections::HashMap, fs, path::Path};
+use serde::Serialize;
+use crate::{Binding, binding_of, normalise_entry_name};
+use crate::names::{camel_case, snake_case};
+use crate::protocol::*;
+
+pub const MANIFEST_VARIABLE: &str = "WL_MACRO_MANIFEST";
+
+#[derive(Serialize)]
+pub struct Manifest<'a> {
+    protocol: &'a str,
+    /// Every protocol merged into the module, starting with the one naming it
+    protocols: Vec<&'a str>,
+    module: String,
+    interfaces: Vec<InterfaceManifest<'a>>
+}
+#[derive(Serialize)]
+struct InterfaceManifest<'a> {
+    name: &'a str,
+    /// The generated trait
+    trait_name: String,
+    version: u32,
+    binding: Option<BindingManifest>,
+    requests: Vec<MessageManifest<'a>>,
+    events: Vec<MessageManifest<'a>>,
+    enums: Vec<EnumManifest<'a>>
+}
+#[derive(Serialize)]
+struct BindingManifest {
+    implementation: String,
+    external: bool,
+    context: Option<String>
+}
+#[derive(Serialize)]
+struct MessageManifest<'a> {
+    name: &'a str,
+    /// The generated trait method
+    method: String,
+    opcode: u16,
+    since: u32,
+    destructor: bool,
+    args: Vec<ArgManifest<'a>>
+}
+#[derive(Serialize)]
+struct ArgManifest<'a> {
+    name: &'a str,
+    /// The generated method parameter
+    parameter: String,
+    #[serde(rename = "type")]
+    kind: DataType,
+    interface: Option<&'a str>,
+    nullable: bool,
+    #[serde(rename = "enum")]
+    enumeration: Option<&'a str>,
+    since: Option<u32>,
+    default: Option<i64>,
+    max_len: Option<u32>,
+    range: Option<[f64; 2]>,
+    from_field: Option<&'a str>
+}
+#[derive(Serialize)]
+struct EnumManifest<'a> {
+    name: &'a str,
+    /// The generated type
+    type_name: String,
+    representation: DataType,
+    entries: Vec<EntryManifest<'a>>
+}
+#[derive(Serialize)]
+struct EntryManifest<'a> {
+    name: &'a str,
+    /// The generated associated constant
+    constant: String,
+    value: i64
+}
+
+impl<'a> Manifest<'a> {
+    pub fn new(protocol: &'a Protocol, merged: &'a [Protocol], module: &syn::Ident, bindings: &HashMap<String, Binding>) -> Self {
+        let representations = protocol.enum_representations();
+        let message = |binding: Option<&Binding>, name: &'a str, opcode: usize, since: u32, destructor: bool, args: &'a [Arg]| MessageManifest {
+            name,
+            method: binding.map(|binding| binding.method_name(name)).unwrap_or_else(|| snake_case(name)),
+            opcode: crate::protocol::opcode(opcode),
+            since,
+            destructor,
+            args: args.iter().map(|arg| ArgManifest {
+                name: &arg.name,
+                parameter: arg.parameter().to_string(),
+                kind: arg.kind,
+                interface: arg.interface.as_deref(),
+                nullable: arg.nullable,
+                enumeration: arg.enumeration.as_deref(),
+                since: arg.since,
+                default: arg.default,
+                max_len: arg.max_len,
+                range: arg.range,
+                from_field: arg.from_field.as_deref()
+            }).collect()
+        };
+        let interfaces = protocol.interfaces.iter().map(|interface| InterfaceManifest {
+            name: &interface.name,
+            trait_name: camel_case(&interface.name),
+            version: interface.version,
+            binding: binding_of(bindings, &interface.name).map(|binding| BindingManifest {
+                implementation: binding.implementation_name(),
+                external: binding.is_external,
+                context: binding.context_name()
+            }),
+            requests: interface.requests.iter().enumerate()
+                .map(|(opcode, request)| message(binding_of(bindings, &interface.name), &request.name, opcode, request.since_version(), request.destructor, &request.args))
+                .collect(),
+            events: interface.events.iter().enumerate()
+                .map(|(opcode, event)| message(binding_of(bindings, &interface.name), &event.name, opcode, event.since_version(), false, &event.args))
+                .collect(),
+            enums: interface.enums.iter().map(|e| EnumManifest {
+                name: &e.name,
+                type_name: format!("{}{}", camel_case(&interface.name), camel_case(&e.name)),
+                representation: representations.get(&(interface.name.clone(), e.name.clone())).copied().unwrap_or(DataType::Uint),
+                entries: e.entries.iter().map(|entry| EntryManifest {
+                    name: &entry.name,
+                    constant: normalise_entry_name(e, &entry.name),
+                    value: entry.value
+                }).collect()
+            }).collect()
+        }).collect();
+        Self {
+            protocol: &protocol.name,
+            protocols: merged.iter().map(|protocol| protocol.name.as_str()).collect(),
+            module: module.to_string(),
+            interfaces
+        }
+    }
+    /// Write the manifest if requested by the environment. Failure, including a build without the `json` feature, is
+    /// reported as a warning as the manifest is not required for the build
+    pub fn write_if_requested(&self) {
+        let directory = match std::env::var_os(MANIFEST_VARIABLE) {
+            Some(directory) => directory,
+            None => return
+        };
+        let path = Path::new(&directory).join(format!("{}.json", snake_case(self.protocol)));
+        let result = self.to_json().and_then(|json| fs::write(&path, json + "\n").map_err(|error| error.to_string()));
+        if let Err(error) = result {
+            eprintln!("warning: unable to write protocol manifest {:?}: {}", path, error);
+        }
+    }
+    #[cfg(feature = "json")]
+    fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|error| error.to_string())
+    }
+    #[cfg(not(feature = "json"))]
+    fn to_json(&self) -> Result<String, String> {
+        Err(format!("{} is set, but writing manifests requires the `json` feature of wl-macro", MANIFEST_VARIABLE))
+    }
+}
@@ -0,0 +1,138 @@
+//! Conversion of protocol names into Rust identifiers.
+//!
+//! Protocol names are lower `snake_case` by convention. The conversions here are intentionally simple and do not
+//! attempt to detect word boundaries, so that generated identifiers only change when the protocol does:
+//! - `camel_case` splits on underscores and uppercases the first character of each segment, keeping the remainder
+//!   as written. Digit runs stay attached to their segment: `zwp_linux_dmabuf_v1` becomes `ZwpLinuxDmabufV1` and
+//!   `xrgb2101010` becomes `Xrgb2101010`. Segments starting with a digit are kept as is, so `foo_2` becomes `Foo2`.
+//! - `snake_case` lowercases the name, inserting an underscore where a lowercase letter or digit is followed by an
+//!   uppercase letter. Names that are already snake_case are unchanged.
+//! - `shouty_snake_case` is the uppercase of `snake_case`: `xrgb2101010` becomes `XRGB2101010`.
+//!
+//! Converted names that are Rust keywords are emitted as raw identifiers by `ident`. The few keywords that cannot be
+//! raw identifiers are reported by `is_unusable` so that the protocol item responsible can be named in an error.
+//!
+//! Keywords of every edition are made raw, not only those of the macro's own. Generated tokens are read under this
+//! crate's edition, but some identifiers are spanned at a binding for their diagnostics and so are read under the
+//! edition of the crate using the macro.
+
+use proc_macro2::{Ident, Span};
+
+/// Keywords, including reserved words, that are valid as raw identifiers
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move",
+    "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield"
+];
+/// Keywords that cannot be raw identifiers
+const UNUSABLE: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+pub fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    for segment in name.split('_') {
+        let mut chars = segment.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.extend(chars);
+        }
+    }
+    camel
+}
+
+pub fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() && previous.map(|p| p.is_lowercase() || p.is_ascii_digit()).unwrap_or(false) {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+    snake
+}
+
+pub fn shouty_snake_case(name: &str) -> String {
+    snake_case(name).to_uppercase()
+}
+
+/// Create an identifier for a converted name, raw if it is a keyword
+pub fn ident(name: &str) -> Ident {
+    if KEYWORDS.contains(&name) {
+        Ident::new_raw(name, Span::call_site())
+    } else {
+        Ident::new(name, Span::call_site())
+    }
+}
+
+/// Whether the converted name is a keyword that cannot be used as an identifier, even a raw one
+pub fn is_unusable(name: &str) -> bool {
+    UNUSABLE.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_are_raw() {
+        // 2018 and 2021 keywords that the 2015 edition accepts as plain identifiers still need to be raw
+        for keyword in ["async", "await", "dyn", "try", "gen", "type", "move"] {
+            assert_eq!(ident(keyword).to_string(), format!("r#{}", keyword));
+        }
+        assert_eq!(ident("surface").to_string(), "surface");
+        assert!(is_unusable("self") && is_unusable("Self") && !is_unusable("gen"));
+    }
+    #[test]
+    fn real_protocol_names() {
+        // Interfaces and enums of wayland, xdg-shell, linux-dmabuf, viewporter and wlr protocols, as their traits and
+        // enum types are named
+        let camel = [
+            ("wl_display", "WlDisplay"),
+            ("wl_data_device_manager", "WlDataDeviceManager"),
+            ("wl_subcompositor", "WlSubcompositor"),
+            ("wl_shm_format", "WlShmFormat"),
+            ("wl_data_device_manager_dnd_action", "WlDataDeviceManagerDndAction"),
+            ("wl_pointer_axis_source", "WlPointerAxisSource"),
+            ("xdg_wm_base", "XdgWmBase"),
+            ("xdg_toplevel", "XdgToplevel"),
+            ("xdg_toplevel_resize_edge", "XdgToplevelResizeEdge"),
+            ("xdg_positioner_constraint_adjustment", "XdgPositionerConstraintAdjustment"),
+            ("zxdg_decoration_manager_v1", "ZxdgDecorationManagerV1"),
+            ("zwp_linux_dmabuf_v1", "ZwpLinuxDmabufV1"),
+            ("zwp_linux_buffer_params_v1", "ZwpLinuxBufferParamsV1"),
+            ("wp_viewporter", "WpViewporter"),
+            ("zwlr_layer_shell_v1", "ZwlrLayerShellV1"),
+            ("zwlr_layer_surface_v1_anchor", "ZwlrLayerSurfaceV1Anchor"),
+            ("zwlr_output_power_manager_v1", "ZwlrOutputPowerManagerV1"),
+            ("zwlr_screencopy_frame_v1", "ZwlrScreencopyFrameV1"),
+            ("xrgb2101010", "Xrgb2101010")
+        ];
+        for (name, expected) in camel.iter() {
+            assert_eq!(camel_case(name), *expected, "camel_case({:?})", name);
+            // Generated names convert back to the protocol's
+            assert_eq!(snake_case(expected), *name, "snake_case({:?})", expected);
+        }
+        // Enum entries, as their constants are named
+        let shouty = [
+            ("xrgb2101010", "XRGB2101010"),
+            ("argb8888", "ARGB8888"),
+            ("c8", "C8"),
+            ("yuv420", "YUV420"),
+            ("nv12", "NV12"),
+            ("top_left", "TOP_LEFT"),
+            ("slide_x", "SLIDE_X"),
+            ("y_invert", "Y_INVERT"),
+            ("wheel_tilt", "WHEEL_TILT"),
+            ("overlay", "OVERLAY")
+        ];
+        for (name, expected) in shouty.iter() {
+            assert_eq!(shouty_snake_case(name), *expected, "shouty_snake_case({:?})", name);
+        }
+        // Requests and events, as their methods are named
+        for name in ["get_layer_surface", "ack_configure", "create_immed", "copy_with_damage", "set_fullscreen"].iter() {
+            assert_eq!(snake_case(name), *name);
+        }
+    }
+}
@@ -1,45 +1,570 @@
+// The full specification is deserialized even where code generation does not consume a field
+#![allow(dead_code)]
+
 use std::{
-    fs::File,
-    io::Read,
-    path::Path, collections::HashMap,
+    fs, fmt,
+    path::{Path, PathBuf}, collections::{BTreeMap, HashMap, HashSet},
+    convert::TryFrom,
 };
-use crate::Binding;
-use heck::{CamelCase, SnakeCase};
+use crate::{Binding, binding_of, normalise_entry_name};
+use crate::names::{camel_case, snake_case};
 use proc_macro2::TokenStream;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::{DeserializeOwned, IgnoredAny}};
 use quote::{quote, format_ident};
 use syn::{parse_quote, spanned::Spanned};
 
 // Note: owned strings are required as TOML allows string normalisation
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Deserialize)]
 pub struct Protocol {
     pub name: String,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub copyright: Option<String>,
+    /// Files of further interfaces merged into the protocol, relative to the directory of the including file
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(rename = "interface", default)]
+    pub interfaces: Vec<Interface>,
+    /// Every specification file read, including those included, so that changes to any of them rebuild the module
+    #[serde(skip)]
+    pub files: Vec<PathBuf>,
+    /// Files declaring no schema despite using fields of a later schema than the first, reported as lints
+    #[serde(skip)]
+    pub undeclared_schemas: Vec<UndeclaredSchema>
+}
+
+/// The latest schema of specification files this version understands, declared by a file with a top-level
+/// `schema = N`. Files declaring none are read as the first schema
+pub const SCHEMA: u32 = 2;
+/// The schema each field was introduced in, by the kind of table it is written in. Fields of the first schema, those
+/// of the upstream XML format, are not listed
+pub const SCHEMA_FIELDS: &[(&str, &str, u32)] = &[
+    ("protocol", "include", 2),
+    ("interface", "deprecated", 2),
+    ("interface", "deprecated_reason", 2),
+    ("interface", "expected_requests", 2),
+    ("interface", "expected_events", 2),
+    ("interface", "trimmed", 2),
+    ("interface", "attrs", 2),
+    ("interface", "privileged", 2),
+    ("enum", "maps_to", 2),
+    ("enum", "is_error", 2),
+    ("enum", "attrs", 2),
+    ("request", "double_buffered", 2),
+    ("request", "latching", 2),
+    ("request", "attrs", 2),
+    ("request", "privileged", 2),
+    ("event", "flush", 2),
+    ("event", "attrs", 2),
+    ("arg", "since", 2),
+    ("arg", "default", 2),
+    ("arg", "max_len", 2),
+    ("arg", "range", 2),
+    ("arg", "from_field", 2),
+    ("arg", "allow_self", 2)
+];
+/// A specification file using fields introduced after the first schema without declaring a schema, which older
+/// versions of the macro would read while silently ignoring those fields
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndeclaredSchema {
+    pub file: PathBuf,
+    /// The fields used, written `table.field`
+    pub fields: Vec<String>,
+    /// The schema the file should declare, the latest introducing any of the fields
+    pub schema: u32
+}
+/// A field used by a specification, written `table.field`, with the schema introducing it
+type SchemaField = (String, u32);
+/// A value of a specification in any format, holding only what is needed to find the fields it uses
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SchemaValue {
+    Integer(i64),
+    String(String),
+    Table(BTreeMap<String, SchemaValue>),
+    Array(Vec<SchemaValue>),
+    Other(IgnoredAny)
+}
+impl fmt::Display for SchemaValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(integer) => write!(f, "{}", integer),
+            Self::String(string) => write!(f, "{:?}", string),
+            Self::Table(_) => f.write_str("a table"),
+            Self::Array(_) => f.write_str("an array"),
+            Self::Other(_) => f.write_str("a value of another type")
+        }
+    }
+}
+/// The schema declared by a specification, and each field it uses that was introduced after the first schema
+fn schema_fields(source: &str, format: Format) -> Result<(Option<u32>, Vec<SchemaField>), String> {
+    fn collect(kind: &str, table: &BTreeMap<String, SchemaValue>, fields: &mut Vec<SchemaField>) {
+        for (key, value) in table {
+            if let Some(&(_, _, schema)) = SCHEMA_FIELDS.iter().find(|(table, field, _)| (*table, *field) == (kind, key.as_str())) {
+                let field = format!("{}.{}", kind, key);
+                if !fields.iter().any(|(existing, _)| *existing == field) {
+                    fields.push((field, schema));
+                }
+            }
+            let nested = match (kind, key.as_str()) {
+                ("protocol", "interface") => "interface",
+                ("interface", "enum") => "enum",
+                ("interface", "request") => "request",
+                ("interface", "event") => "event",
+                ("enum", "entry") => "entry",
+                ("request", "arg") | ("event", "arg") => "arg",
+                _ => continue
+            };
+            if let SchemaValue::Array(tables) = value {
+                for table in tables {
+                    if let SchemaValue::Table(table) = table {
+                        collect(nested, table, fields);
+                    }
+                }
+            }
+        }
+    }
+    let source: BTreeMap<String, SchemaValue> = format.parse(source)?;
+    let schema = match source.get("schema") {
+        None => None,
+        Some(&SchemaValue::Integer(schema)) if schema >= 1 => Some(u32::try_from(schema).unwrap_or(u32::MAX)),
+        Some(schema) => return Err(format!("schema must be a positive integer, not {}", schema))
+    };
+    let mut fields = Vec::new();
+    collect("protocol", &source, &mut fields);
+    // Tables are read in key order, so fields are listed in the order of the table instead
+    fields.sort_by_key(|(field, _)| SCHEMA_FIELDS.iter().position(|(table, name, _)| *field == format!("{}.{}", table, name)));
+    Ok((schema, fields))
+}
+/// The formats a specification file may be written in, chosen by the file's extension
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Toml,
+    /// Requires the `json` feature
+    Json,
+    /// Requires the `yaml` feature
+    Yaml
+}
+impl Format {
+    /// The format of the file at `path`: JSON for `.json`, YAML for `.yaml` and `.yml` and otherwise TOML
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::Json,
+            Some("yaml") | Some("yml") => Self::Yaml,
+            _ => Self::Toml
+        }
+    }
+    /// Deserialize a value written in the format. Errors give the line and column of the problem
+    fn parse<T: DeserializeOwned>(self, source: &str) -> Result<T, String> {
+        match self {
+            Self::Toml => toml::from_str(source).map_err(|error| error.to_string()),
+            #[cfg(feature = "json")]
+            Self::Json => serde_json::from_str(source).map_err(|error| error.to_string()),
+            #[cfg(not(feature = "json"))]
+            Self::Json => Err("JSON specifications require the `json` feature of wl-macro".into()),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_yaml::from_str(source).map_err(|error| error.to_string()),
+            #[cfg(not(feature = "yaml"))]
+            Self::Yaml => Err("YAML specifications require the `yaml` feature of wl-macro".into())
+        }
+    }
+}
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+            Self::Yaml => "YAML"
+        })
+    }
+}
+/// A file included by a specification, holding interfaces and further includes only
+#[derive(Deserialize)]
+struct Fragment {
+    #[serde(default)]
+    include: Vec<String>,
     #[serde(rename = "interface", default)]
-    pub interfaces: Vec<Interface>
+    interfaces: Vec<Interface>
+}
+/// The files including another, innermost first, as appended to the file's name in errors
+fn included_from(chain: &[PathBuf]) -> String {
+    chain.iter().map(|file| format!(", included from {:?}", file)).collect()
+}
+/// Whether two paths name the same file, comparing them as written if either cannot be resolved
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b
+    }
 }
 impl Protocol {
     pub fn from_str(string: &str) -> Result<Self, toml::de::Error> {
-        toml::from_str(string)
+        let mut protocol: Self = toml::from_str(string)?;
+        protocol.mark_hex_entries(string)?;
+        Ok(protocol)
+    }
+    /// Parse a specification written in any supported format, without checking it or merging its includes
+    pub fn from_str_as(string: &str, format: Format) -> Result<Self, String> {
+        match format {
+            Format::Toml => Self::from_str(string).map_err(|error| error.to_string()),
+            _ => format.parse(string)
+        }
+    }
+    /// Parse an included file as a protocol without a name
+    fn from_fragment_str(string: &str, format: Format) -> Result<Self, String> {
+        let fragment: Fragment = format.parse(string)?;
+        let mut protocol = Self {
+            name: String::new(),
+            summary: None,
+            description: None,
+            copyright: None,
+            include: fragment.include,
+            interfaces: fragment.interfaces,
+            files: Vec::new(),
+            undeclared_schemas: Vec::new()
+        };
+        if format == Format::Toml {
+            protocol.mark_hex_entries(string).map_err(|error| error.to_string())?;
+        }
+        Ok(protocol)
+    }
+    /// Note the entries whose values are written in hexadecimal, as the parsed values do not record their literal form.
+    /// Only TOML keeps the spans needed, so entries of other formats are documented in decimal
+    fn mark_hex_entries(&mut self, source: &str) -> Result<(), toml::de::Error> {
+        #[derive(Deserialize)]
+        struct Spans {
+            #[serde(rename = "interface", default)]
+            interfaces: Vec<InterfaceSpans>
+        }
+        #[derive(Deserialize)]
+        struct InterfaceSpans {
+            #[serde(rename = "enum", default)]
+            enums: Vec<EnumSpans>
+        }
+        #[derive(Deserialize)]
+        struct EnumSpans {
+            #[serde(rename = "entry", default)]
+            entries: Vec<EntrySpans>
+        }
+        #[derive(Deserialize)]
+        struct EntrySpans {
+            value: toml::Spanned<i64>
+        }
+        let spans: Spans = toml::from_str(source)?;
+        for (interface, spans) in self.interfaces.iter_mut().zip(spans.interfaces) {
+            for (e, spans) in interface.enums.iter_mut().zip(spans.enums) {
+                for (entry, spans) in e.entries.iter_mut().zip(spans.entries) {
+                    let literal = source.get(spans.value.start()..spans.value.end()).unwrap_or_default();
+                    entry.hex = literal.starts_with("0x");
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Determine the wire representation of every enum referenced by an argument, keyed by `(interface, enum)`.
+    ///
+    /// Enums only referenced from `int` arguments are represented as `i32`, all others as `u32`. Mixed use is
+    /// reported as `uint`; the core protocol itself references `wl_output.transform` from both kinds of argument.
+    pub fn enum_representations(&self) -> HashMap<(String, String), DataType> {
+        let mut representations = HashMap::new();
+        for interface in &self.interfaces {
+            let args = interface.requests.iter().flat_map(|request| &request.args)
+                .chain(interface.events.iter().flat_map(|event| &event.args));
+            for arg in args {
+                if let Some(key) = arg.enum_key(&interface.name) {
+                    if !matches!(arg.kind, DataType::Int | DataType::Uint) {
+                        continue
+                    }
+                    let kind = representations.entry(key).or_insert(arg.kind);
+                    if *kind != arg.kind {
+                        *kind = DataType::Uint
+                    }
+                }
+            }
+        }
+        representations
+    }
+    /// The `(interface, enum)` pairs referenced by arguments of the given interfaces
+    pub fn referenced_enums(interfaces: &[&Interface]) -> HashSet<(String, String)> {
+        interfaces.iter()
+            .flat_map(|interface| interface.requests.iter().flat_map(|request| &request.args)
+                .chain(interface.events.iter().flat_map(|event| &event.args))
+                .filter_map(move |arg| arg.enum_key(&interface.name)))
+            .collect()
+    }
+    /// Pair each enum annotated with `maps_to` with the enum it names, or describe each annotation naming a missing
+    /// enum, the annotated enum itself or an enum with none of the same entries
+    pub fn enum_mappings(&self) -> Result<Vec<EnumMapping<'_>>, Vec<String>> {
+        let mut mappings = Vec::new();
+        let mut errors = Vec::new();
+        for interface in &self.interfaces {
+            for e in &interface.enums {
+                let maps_to = match &e.maps_to {
+                    Some(maps_to) => maps_to,
+                    None => continue
+                };
+                let (owner, name) = maps_to.split_once('.').unwrap_or((&interface.name, maps_to));
+                let target = self.interfaces.iter()
+                    .find(|target| target.name == owner)
+                    .and_then(|target| target.enums.iter().find(|target| target.name == name).map(|e| (target, e)));
+                match target {
+                    None => errors.push(format!("Enum \"{}.{}\" maps to \"{}.{}\", which does not exist{}", interface.name, e.name, owner, name, interface.error_context())),
+                    Some((target, _)) if target.name == interface.name && name == e.name => errors.push(format!("Enum \"{}.{}\" maps to itself{}", interface.name, e.name, interface.error_context())),
+                    Some((target, target_enum)) if !e.entries.iter().any(|entry| target_enum.entries.iter().any(|other| other.name == entry.name)) => {
+                        errors.push(format!("Enum \"{}.{}\" maps to \"{}.{}\", but the two have no entries of the same name{}", interface.name, e.name, target.name, target_enum.name, interface.error_context()))
+                    },
+                    Some(to) => mappings.push(EnumMapping { from: (interface, e), to })
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(mappings)
+        } else {
+            Err(errors)
+        }
+    }
+    /// Remove the long descriptions, keeping only the one-line summaries
+    pub fn drop_descriptions(&mut self) {
+        self.description = None;
+        for interface in &mut self.interfaces {
+            interface.description = None;
+            for request in &mut interface.requests {
+                request.description = None;
+            }
+            for event in &mut interface.events {
+                event.description = None;
+            }
+            for e in &mut interface.enums {
+                e.description = None;
+                for entry in &mut e.entries {
+                    entry.description = None;
+                }
+            }
+        }
     }
-    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+    /// A hash of the wire signature of every interface in specification order, whichever of them are bound
+    pub fn signature_hash(&self) -> u64 {
+        fnv1a(&self.interfaces.iter().flat_map(Interface::wire_signature).collect::<Vec<_>>().join("\n"))
+    }
+    /// Combine the interfaces of several protocols into one named after the first
+    pub fn merge(protocols: &[Protocol]) -> Result<Self, String> {
+        let first = &protocols[0];
+        let mut interfaces: Vec<Interface> = Vec::new();
+        for protocol in protocols {
+            for interface in &protocol.interfaces {
+                if interfaces.iter().any(|existing| existing.name == interface.name) {
+                    return Err(format!("Interface {:?} of protocol {:?} is already defined by another protocol", interface.name, protocol.name))
+                }
+                interfaces.push(interface.clone());
+            }
+        }
+        Ok(Self {
+            name: first.name.clone(),
+            summary: first.summary.clone(),
+            description: first.description.clone(),
+            copyright: first.copyright.clone(),
+            include: Vec::new(),
+            interfaces,
+            files: protocols.iter().flat_map(|protocol| protocol.files.iter().cloned()).collect(),
+            undeclared_schemas: protocols.iter().flat_map(|protocol| protocol.undeclared_schemas.iter().cloned()).collect()
+        })
+    }
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
         let path = path.as_ref();
-        let mut protocol = String::new();
-        let mut file = File::open(path).unwrap_or_else(|error| panic!("Unable to open protocol specification file {:?}: {:?}", path, error));
-        file.read_to_string(&mut protocol).unwrap_or_else(|error| panic!("Unable to read protocol specification file {:?}: {:?}", path, error));
-        Self::from_str(&protocol).unwrap_or_else(|error| panic!("Failed to parse protocol specification file {:?}: {:?}", path, error))
+        let mut protocol = Self::read(path, &[], Self::from_str_as)?;
+        let mut origins = vec![path.to_path_buf(); protocol.interfaces.len()];
+        protocol.merge_includes(path, &mut Vec::new(), &mut origins)?;
+        match protocol.check().into_iter().next() {
+            Some(issue) => Err(format!("Protocol specification file {:?}: {}", path, issue)),
+            None => Ok(protocol)
+        }
+    }
+    /// Apply the rules checked when a specification is loaded to a protocol parsed some other way, such as by
+    /// `from_str`, returning every rule broken
+    pub fn check(&self) -> Vec<ValidationIssue> {
+        vec![self.check_message_counts(), self.check_versions()].into_iter()
+            .filter_map(Result::err)
+            .map(ValidationIssue)
+            .collect()
+    }
+    /// Read and parse a single specification file, naming the files that include it, outermost last, in errors
+    fn read(path: &Path, chain: &[PathBuf], parse: fn(&str, Format) -> Result<Self, String>) -> Result<Self, String> {
+        let included_from = included_from(chain);
+        let bytes = fs::read(path).map_err(|error| format!("Unable to read protocol specification file {:?}{}: {}", path, included_from, error))?;
+        let protocol = String::from_utf8(bytes)
+            .map_err(|error| format!("Protocol specification file {:?}{} is not valid UTF-8 (byte offset {})", path, included_from, error.utf8_error().valid_up_to()))?;
+        // Files saved by some Windows editors begin with a byte order mark, which TOML and JSON do not allow
+        let protocol = protocol.strip_prefix('\u{feff}').unwrap_or(&protocol);
+        let format = Format::of(path);
+        // The schema is checked first, as a file of a later schema may fail to parse as this one
+        let (schema, fields) = schema_fields(protocol, format).map_err(|error| format!("Failed to parse protocol specification file {:?}{}: {}", path, included_from, error))?;
+        if let Some(schema) = schema.filter(|&schema| schema > SCHEMA) {
+            return Err(format!("Protocol specification file {:?}{} requires wl-macro schema {}, this version supports {}", path, included_from, schema, SCHEMA))
+        }
+        let undeclared = match schema {
+            Some(schema) => match fields.iter().find(|(_, introduced)| *introduced > schema) {
+                Some((field, introduced)) => return Err(format!("Protocol specification file {:?}{} declares schema {} but uses `{}`, introduced in schema {}", path, included_from, schema, field, introduced)),
+                None => None
+            },
+            None => fields.iter().map(|(_, introduced)| *introduced).max().map(|schema| UndeclaredSchema {
+                file: path.to_path_buf(),
+                fields: fields.iter().map(|(field, _)| field.clone()).collect(),
+                schema
+            })
+        };
+        let mut protocol = parse(protocol, format).map_err(|error| format!("Failed to parse protocol specification file {:?}{}: {}", path, included_from, error))?;
+        protocol.files.push(path.to_path_buf());
+        protocol.undeclared_schemas.extend(undeclared);
+        Ok(protocol)
+    }
+    /// Merge in the interfaces of the files included by the specification read from `path`, and those they include in
+    /// turn. `chain` holds the files including `path` and `origins` the file each interface was read from, so that
+    /// cycles and interfaces defined twice are reported naming the files involved
+    fn merge_includes(&mut self, path: &Path, chain: &mut Vec<PathBuf>, origins: &mut Vec<PathBuf>) -> Result<(), String> {
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        chain.insert(0, path.to_path_buf());
+        for include in std::mem::take(&mut self.include) {
+            let included_path = directory.join(include);
+            if chain.iter().any(|file| same_file(file, &included_path)) {
+                return Err(format!("Protocol specification file {:?}{} includes itself", included_path, included_from(chain)))
+            }
+            let mut included = Self::read(&included_path, chain, Self::from_fragment_str)?;
+            let mut included_origins = vec![included_path.clone(); included.interfaces.len()];
+            included.merge_includes(&included_path, chain, &mut included_origins)?;
+            for (interface, origin) in included.interfaces.into_iter().zip(included_origins) {
+                if let Some(existing) = self.interfaces.iter().position(|existing| existing.name == interface.name) {
+                    return Err(format!("Interface {:?} of {:?} is already defined by {:?}, when merging {:?}{}", interface.name, origin, origins[existing], included_path, included_from(chain)))
+                }
+                self.interfaces.push(interface);
+                origins.push(origin);
+            }
+            self.files.extend(included.files);
+            self.undeclared_schemas.extend(included.undeclared_schemas);
+        }
+        chain.remove(0);
+        Ok(())
+    }
+    /// Compare the requests and events of each interface against its expected counts, where given, and against the
+    /// limit on messages per interface
+    fn check_message_counts(&self) -> Result<(), String> {
+        let oversized: Vec<String> = self.interfaces.iter().flat_map(|interface| {
+            let requests = (interface.requests.len() > MAX_MESSAGES)
+                .then(|| format!("interface {:?} lists {} requests", interface.name, interface.requests.len()));
+            let events = (interface.events.len() > MAX_MESSAGES)
+                .then(|| format!("interface {:?} lists {} events", interface.name, interface.events.len()));
+            requests.into_iter().chain(events)
+        }).collect();
+        if !oversized.is_empty() {
+            return Err(format!("{} but at most {} of each are supported", oversized.join("; "), MAX_MESSAGES))
+        }
+        let mismatches: Vec<String> = self.interfaces.iter().flat_map(|interface| {
+            let requests = interface.expected_requests
+                .filter(|&expected| expected != interface.requests.len())
+                .map(|expected| format!("interface {:?} lists {} requests but is expected to have {}{}", interface.name, interface.requests.len(), expected, interface.error_context()));
+            let events = interface.expected_events
+                .filter(|&expected| expected != interface.events.len())
+                .map(|expected| format!("interface {:?} lists {} events but is expected to have {}{}", interface.name, interface.events.len(), expected, interface.error_context()));
+            requests.into_iter().chain(events)
+        }).collect();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("{}. Check that requests and events are not listed under each other's sections", mismatches.join("; ")))
+        }
+    }
+    /// Check that nothing is introduced in a version later than its interface's version, which generated code would
+    /// never dispatch or send
+    fn check_versions(&self) -> Result<(), String> {
+        let errors: Vec<String> = self.interfaces.iter().flat_map(|interface| {
+            interface.versioned_items().into_iter()
+                .filter(move |(_, since)| *since > interface.version)
+                .map(move |(item, since)| format!("{} is introduced in version {}, after interface {:?} version {}{}", item, since, interface.name, interface.version, interface.error_context()))
+        }).collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// A rule of the specification format broken by a protocol, found by [`Protocol::check`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue(pub String);
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// An enum annotated with `maps_to` and the enum it mirrors
+pub struct EnumMapping<'a> {
+    pub from: (&'a Interface, &'a Enum),
+    pub to: (&'a Interface, &'a Enum)
+}
+
+/// The most requests or events a single interface may list. Opcodes are 16 bits on the wire, but no real protocol comes
+/// close to this and a longer list is far more likely to be a broken generator or a corrupted file
+pub const MAX_MESSAGES: usize = 1024;
+
+/// The wire opcode of the message at `index`, which always fits as message counts are checked when the protocol is loaded
+pub fn opcode(index: usize) -> u16 {
+    u16::try_from(index).expect("message counts are limited to MAX_MESSAGES when the protocol is loaded")
+}
+
+/// The size in bytes of the header of every message, the object id followed by the size and opcode
+pub const HEADER_SIZE: u32 = 8;
+
+/// The smallest size in bytes a message with these arguments takes on the wire, with its strings and arrays empty and
+/// its header included
+pub fn min_size(args: &[Arg]) -> u32 {
+    HEADER_SIZE + args.iter().map(Arg::min_size).sum::<u32>()
+}
+
+/// The size in bytes a message with these arguments always takes on the wire, if none of them vary in length
+pub fn fixed_size(args: &[Arg]) -> Option<u32> {
+    if args.iter().any(Arg::is_variable) {
+        None
+    } else {
+        Some(min_size(args))
+    }
+}
+
+/// A 64-bit FNV-1a hash of `text`. The standard library hashers are not stable between releases
+pub fn fnv1a(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Interface {
     pub name: String,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub version: u32,
+    /// Implementations should not be written for the interface, such as `wl_shell` which is replaced by `xdg_shell`
+    #[serde(default)]
+    pub deprecated: bool,
+    pub deprecated_reason: Option<String>,
+    /// The number of requests the interface is known to have, guarding against sections listed under the wrong kind
+    pub expected_requests: Option<usize>,
+    /// The number of events the interface is known to have
+    pub expected_events: Option<usize>,
+    /// Messages of the later versions have been removed from the specification on purpose, so the version is not
+    /// expected to be reached by any `since`
+    #[serde(default)]
+    pub trimmed: bool,
+    /// Outer attributes for the generated trait, written as in source, e.g. `"#[cfg(feature = \"audit\")]"`
+    #[serde(default)]
+    pub attrs: Vec<String>,
+    /// Every request is only dispatched for clients the module's `privileged` predicate accepts
+    #[serde(default)]
+    pub privileged: bool,
     #[serde(rename = "enum", default)]
     pub enums: Vec<Enum>,
     #[serde(rename = "request", default)]
@@ -48,52 +573,275 @@ pub struct Interface {
     pub events: Vec<Event>
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// The variable which, when set at compile time, appends the normalised form of the interface to errors about it
+pub const DUMP_VARIABLE: &str = "WL_MACRO_DUMP_INTERFACE";
+
+impl Interface {
+    /// `wl_display` with only its `error` event, for posting protocol errors from modules whose specification does not
+    /// include the core protocol. It is sent as part of an exchange with the client, so is not flushed
+    pub fn display_errors() -> Self {
+        toml::from_str(r#"
+            name = "wl_display"
+            version = 1
+            [[event]]
+            name = "error"
+            flush = false
+            [[event.arg]]
+            name = "object_id"
+            type = "object"
+            [[event.arg]]
+            name = "code"
+            type = "uint"
+            [[event.arg]]
+            name = "message"
+            type = "string"
+        "#).expect("the error event of wl_display is a valid interface")
+    }
+    /// The interface as the macro sees it, appended to an error about it if requested by the environment
+    pub fn error_context(&self) -> String {
+        if std::env::var_os(DUMP_VARIABLE).is_some() {
+            format!("\n\nInterface {:?} after normalisation:\n{}", self.name, self.to_string().trim_end())
+        } else {
+            String::new()
+        }
+    }
+    /// Each request, event, argument, enum and entry declaring `since`, described for errors, with the version it was
+    /// introduced in
+    pub fn versioned_items(&self) -> Vec<(String, u32)> {
+        let mut items = Vec::new();
+        let args = |items: &mut Vec<(String, u32)>, kind: &str, message: &str, args: &[Arg]| {
+            for arg in args {
+                if let Some(since) = arg.since {
+                    items.push((format!("Argument {:?} of {} \"{}.{}\"", arg.name, kind, self.name, message), since));
+                }
+            }
+        };
+        for request in &self.requests {
+            if let Some(since) = request.since {
+                items.push((format!("Request \"{}.{}\"", self.name, request.name), since));
+            }
+            args(&mut items, "request", &request.name, &request.args);
+        }
+        for event in &self.events {
+            if let Some(since) = event.since {
+                items.push((format!("Event \"{}.{}\"", self.name, event.name), since));
+            }
+            args(&mut items, "event", &event.name, &event.args);
+        }
+        for e in &self.enums {
+            if let Some(since) = e.since {
+                items.push((format!("Enum \"{}.{}\"", self.name, e.name), since));
+            }
+            for entry in &e.entries {
+                if let Some(since) = entry.since {
+                    items.push((format!("Entry \"{}.{}.{}\"", self.name, e.name, entry.name), since));
+                }
+            }
+        }
+        items
+    }
+    /// What the interface puts on the wire, one line each for its name and version and for each request and event in
+    /// opcode order with the version it was introduced in and the wire type of each argument. Names of messages and
+    /// arguments, documentation and enums do not reach the wire and are left out
+    pub fn wire_signature(&self) -> Vec<String> {
+        let message = |kind: &str, opcode: usize, since: u32, args: &[Arg]| {
+            format!("{} {} since {}({})", kind, opcode, since, args.iter().map(Arg::signature).collect::<Vec<_>>().join(", "))
+        };
+        Some(format!("interface {} version {}", self.name, self.version)).into_iter()
+            .chain(self.requests.iter().enumerate().map(|(opcode, request)| message("request", opcode, request.since_version(), &request.args)))
+            .chain(self.events.iter().enumerate().map(|(opcode, event)| message("event", opcode, event.since_version(), &event.args)))
+            .collect()
+    }
+    /// The smallest size in bytes of the largest of the interface's requests and events, or only the header if it has
+    /// none
+    pub fn largest_fixed_message(&self) -> u32 {
+        self.requests.iter().map(|request| min_size(&request.args))
+            .chain(self.events.iter().map(|event| min_size(&event.args)))
+            .max()
+            .unwrap_or(HEADER_SIZE)
+    }
+    /// A hash of the wire signature, the same for every build of the same specification
+    pub fn signature_hash(&self) -> u64 {
+        fnv1a(&self.wire_signature().join("\n"))
+    }
+    /// The note given for uses of a deprecated interface
+    pub fn deprecation_note(&self) -> Option<String> {
+        if !self.deprecated {
+            return None
+        }
+        Some(match &self.deprecated_reason {
+            Some(reason) => format!("interface {} is deprecated: {}", self.name, reason),
+            None => format!("interface {} is deprecated", self.name)
+        })
+    }
+}
+
+/// A compact form listing the interface's version and generated trait, its requests and events by opcode and its enums
+/// with their generated types and constants
+impl fmt::Display for Interface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "interface {} version {}, trait {}", self.name, self.version, camel_case(&self.name))?;
+        for (opcode, request) in self.requests.iter().enumerate() {
+            writeln!(f, "  request {} {}", opcode, request)?;
+        }
+        for (opcode, event) in self.events.iter().enumerate() {
+            writeln!(f, "  event {} {}", opcode, event)?;
+        }
+        for e in &self.enums {
+            write!(f, "  enum {}, type {}{}", e.name, camel_case(&self.name), camel_case(&e.name))?;
+            match &e.maps_to {
+                Some(maps_to) => writeln!(f, ", maps to {}", maps_to)?,
+                None => writeln!(f)?
+            }
+            for entry in &e.entries {
+                writeln!(f, "    {} = {}", normalise_entry_name(e, &entry.name), entry.value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Enum {
     pub name: String,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub since: Option<u32>,
+    /// Another enum, written `interface.enum` or just `enum` for one of the same interface, whose entries of the same
+    /// names mean the same thing, so that conversions between the two are generated
+    pub maps_to: Option<String>,
+    /// The entries are protocol error codes, as they are for every enum named `error`
+    #[serde(default)]
+    pub is_error: bool,
+    /// Outer attributes for the generated type
+    #[serde(default)]
+    pub attrs: Vec<String>,
     #[serde(rename = "entry", default)]
     pub entries: Vec<Entry>
 }
-#[derive(Clone, Debug, Deserialize)]
+impl Enum {
+    /// Whether the entries are error codes posted with `wl_display.error`
+    pub fn is_error_enum(&self) -> bool {
+        self.is_error || self.name == "error"
+    }
+}
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Request {
     pub name: String,
     pub since: Option<u32>,
     #[serde(default)]
     pub destructor: bool,
+    /// Sets pending state which is only applied by a later latching request
+    #[serde(default)]
+    pub double_buffered: bool,
+    /// Applies pending state set by double-buffered requests
+    #[serde(default)]
+    pub latching: bool,
+    /// Only dispatched for clients the module's `privileged` predicate accepts
+    #[serde(default)]
+    pub privileged: bool,
     pub summary: Option<String>,
     pub description: Option<String>,
+    /// Outer attributes for the generated trait method
+    #[serde(default)]
+    pub attrs: Vec<String>,
     #[serde(rename = "arg", default)]
     pub args: Vec<Arg>
 }
-#[derive(Clone, Debug, Deserialize)]
+impl Request {
+    /// The version the request was introduced in, defaulting to the first version
+    pub fn since_version(&self) -> u32 {
+        self.since.unwrap_or(1)
+    }
+    /// Whether the request is only dispatched for privileged clients, as declared by it or its interface
+    pub fn is_privileged(&self, interface: &Interface) -> bool {
+        self.privileged || interface.privileged
+    }
+}
+/// The request as a call of its arguments, e.g. `attach(buffer: object<wl_buffer>?, x: int, y: int)`, followed by
+/// whether it is a destructor and the version it was introduced in
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        write_args(f, &self.args)?;
+        write!(f, ")")?;
+        if self.destructor {
+            write!(f, " destructor")?;
+        }
+        if let Some(since) = self.since {
+            write!(f, " since {}", since)?;
+        }
+        Ok(())
+    }
+}
+fn write_args(f: &mut fmt::Formatter, args: &[Arg]) -> fmt::Result {
+    for (index, arg) in args.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", arg)?;
+    }
+    Ok(())
+}
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Event {
     pub name: String,
     pub since: Option<u32>,
     pub summary: Option<String>,
     pub description: Option<String>,
+    /// Whether the client should be flushed after sending the event, see [`Event::flushes`]
+    pub flush: Option<bool>,
+    /// Outer attributes for the generated trait methods sending the event
+    #[serde(default)]
+    pub attrs: Vec<String>,
     #[serde(rename = "arg", default)]
     pub args: Vec<Arg>
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl Event {
+    /// The version the event was introduced in, defaulting to the first version
+    pub fn since_version(&self) -> u32 {
+        self.since.unwrap_or(1)
+    }
+    /// Whether sending the event should flush the client. Unless set with `flush`, events that end an exchange flush:
+    /// `done` and `frame` events, and every event of a oneshot interface, one without requests such as `wl_callback`
+    pub fn flushes(&self, interface: &Interface) -> bool {
+        self.flush.unwrap_or(matches!(self.name.as_str(), "done" | "frame") || interface.requests.is_empty())
+    }
+}
+/// The event as a call of its arguments followed by the version it was introduced in
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        write_args(f, &self.args)?;
+        write!(f, ")")?;
+        if let Some(since) = self.since {
+            write!(f, " since {}", since)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Entry {
     pub name: String,
     pub since: Option<u32>,
     pub summary: Option<String>,
     pub description: Option<String>,
-    pub value: u32
+    /// Signed so that entries of enums used by `int` arguments may be negative
+    pub value: i64,
+    /// The value is written in hexadecimal, as are the fourcc codes of `wl_shm.format`
+    #[serde(skip)]
+    pub hex: bool
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RequestType {
     Destructor
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Arg {
     pub name: String,
     #[serde(rename = "allow-null", default)]
@@ -103,64 +851,133 @@ pub struct Arg {
     pub interface: Option<String>,
     #[serde(rename = "enum")]
     pub enumeration: Option<String>,
-    pub summary: Option<String>
+    pub summary: Option<String>,
+    /// The version a trailing argument was appended to its message in, absent from messages of older clients
+    pub since: Option<u32>,
+    /// The value of a since-gated argument when it is absent
+    pub default: Option<i64>,
+    /// The greatest length in bytes of a string or array argument, as sent including a string's terminating nul
+    pub max_len: Option<u32>,
+    /// The inclusive bounds of a fixed argument, checked once it is decoded
+    pub range: Option<[f64; 2]>,
+    /// The field of the implementation an event argument is sent from by the generated `send_` method
+    pub from_field: Option<String>,
+    /// An object argument may name the object receiving the request, received as `ReceiverOr::Receiver`
+    #[serde(default)]
+    pub allow_self: bool
 }
 impl Arg {
-    pub(crate) fn getter(&self, owning_interface: &String, bindings: &HashMap<String, Binding>) -> TokenStream {
+    /// The `(interface, enum)` the argument takes values from, resolving names local to the owning interface
+    pub fn enum_key(&self, owning_interface: &str) -> Option<(String, String)> {
+        self.enumeration.as_ref().map(|enumeration| match enumeration.split_once('.') {
+            Some((owner, name)) => (owner.to_string(), name.to_string()),
+            None => (owning_interface.to_string(), enumeration.clone())
+        })
+    }
+    /// Whether the argument is an object of an unbound interface received untyped under the `loose_objects` option
+    pub(crate) fn is_loose(&self, owning_interface: &str, bindings: &HashMap<String, Binding>) -> bool {
+        match (&self.kind, &self.interface) {
+            (DataType::Object, Some(interface)) => binding_of(bindings, interface).is_none() && bindings[&camel_case(owning_interface)].options.loose_objects,
+            _ => false
+        }
+    }
+    pub(crate) fn getter(&self, owning_interface: &str, bindings: &HashMap<String, Binding>, wl: &syn::Path) -> TokenStream {
         match self.kind {
             DataType::Int => quote!{args.next_i32()?},
             DataType::Uint => quote!{args.next_u32()?},
             DataType::Fixed => quote!{args.next_fixed()?},
             DataType::String => quote!{args.next_str()?},
+            DataType::Array if bindings[&camel_case(owning_interface)].options.borrowed_args => quote!{args.next_array_ref()?},
             DataType::Array => quote!{args.next_array()?},
             DataType::Fd => quote!{client.next_file()?},
-            DataType::Object => if let Some(_) = &self.interface {
-                if self.nullable {
-                    quote!{
-                        {
-                            let id = args.next_u32()?;
-                            if id == 0 {
-                                ::wl::Nullable::Null
-                            } else {
-                                ::wl::Nullable::Object(client.get(id)?)
-                            }
-                        }
-                    }
-                } else {
-                    quote!{client.get(args.next_u32()?)?}
-                }
-            } else {
-                if self.nullable {
-                    quote!{
-                        {
-                            let id = args.next_u32()?;
-                            if id == 0 {
-                                ::wl::Nullable::Null
-                            } else {
-                                ::wl::Nullable::Object(client.get_any(id)?)
-                            }
-                        }
-                    }
-                } else {
-                    quote!{client.get_any(args.next_u32()?)?}
-                }
-            },
+            DataType::Object => self.lease(quote!{args.next_u32()?}, owning_interface, bindings, wl),
             DataType::NewId => if let Some(interface) = &self.interface {
-                let interface_binding = &bindings.get(&interface.to_snake_case()).map(|b| &b.implementation);
-                if let Some(interface_binding) = interface_binding {
-                    quote!{args.next_new_id(#interface, #interface_binding::VERSION)?}
+                if let Some(binding) = binding_of(bindings, interface) {
+                    if bindings[&camel_case(owning_interface)].options.runtime.lease_version {
+                        // Objects created by a request inherit the version of the object the request was sent to
+                        quote!{args.next_new_id(#interface, lease.version())?}
+                    } else if binding.options.delegate.is_some() && !binding.is_external {
+                        // Delegated bindings have no `Dispatch` impl, so their version is the one `negotiate` clamps to
+                        quote!{args.next_new_id(#interface, self::negotiate(#interface, u32::MAX).unwrap_or(1))?}
+                    } else {
+                        let implementation = binding.implementation_type();
+                        quote!{args.next_new_id(#interface, <#implementation as #wl::server::Dispatch>::VERSION)?}
+                    }
                 } else {
-                    let owner = owning_interface.to_camel_case();
-                    let to_implement = interface.to_camel_case();
-                    syn::Error::new(bindings[owning_interface].implementation.span(), format!("Interface {:?} depends on {:?}. Please specify an implementation for {:?}.", owner, to_implement, to_implement)).to_compile_error()
+                    let owner = camel_case(owning_interface);
+                    let to_implement = camel_case(interface);
+                    syn::Error::new(bindings[&owner].implementation.span(), format!("Interface {:?} depends on {:?}. Please specify an implementation for {:?}.", owner, to_implement, to_implement)).to_compile_error()
                 }
             } else {
                 quote!{args.next_dynamic_new_id()?}
             },
         }
     }
-    pub(crate) fn pusher(&self) -> proc_macro2::TokenStream {
-        let arg = format_ident!("wl_{}", self.name);
+    /// The value substituted for a since-gated argument that the client did not send
+    pub(crate) fn absent_value(&self, wl: &syn::Path) -> Result<TokenStream, String> {
+        let out_of_range = |value| format!("Default {} of argument {:?} does not fit in its {:?} type", value, self.name, self.kind);
+        match (self.kind, self.default) {
+            (DataType::Int, Some(value)) => i32::try_from(value).map(|value| quote!{#value}).map_err(|_| out_of_range(value)),
+            (DataType::Uint, Some(value)) => u32::try_from(value).map(|value| quote!{#value}).map_err(|_| out_of_range(value)),
+            (DataType::Object, None) if self.nullable => Ok(quote!{#wl::Nullable::Null}),
+            _ => Err(format!("Argument {:?} is since-gated so must be an int or uint with a `default`, or a nullable object without one", self.name))
+        }
+    }
+    /// Lease the object with the given id, for object arguments
+    pub(crate) fn lease(&self, id: TokenStream, owning_interface: &str, bindings: &HashMap<String, Binding>, wl: &syn::Path) -> TokenStream {
+        let get = if self.interface.is_some() && !self.is_loose(owning_interface, bindings) {
+            quote!{ get }
+        } else {
+            quote!{ get_any }
+        };
+        if self.nullable {
+            quote!{
+                {
+                    let id = #id;
+                    if id == 0 {
+                        #wl::Nullable::Null
+                    } else {
+                        #wl::Nullable::Object(client.#get(id)?)
+                    }
+                }
+            }
+        } else {
+            quote!{client.#get(#id)?}
+        }
+    }
+    /// The argument's name in snake_case, labelling its value in verbose traces
+    pub fn label(&self) -> String {
+        snake_case(&self.name)
+    }
+    /// The parameter the argument is received or sent as, its label prefixed so that it never collides with a keyword
+    /// or the other names of a generated method
+    pub fn parameter(&self) -> proc_macro2::Ident {
+        format_ident!("wl_{}", self.label())
+    }
+    /// The smallest size in bytes the argument takes on the wire. File descriptors are sent alongside the message rather
+    /// than in it, strings and arrays take at least their length, and a `new_id` of no particular interface is preceded
+    /// by the interface name and version
+    pub fn min_size(&self) -> u32 {
+        match self.kind {
+            DataType::Fd => 0,
+            DataType::NewId if self.interface.is_none() => 12,
+            _ => 4
+        }
+    }
+    /// Whether the size of the argument on the wire depends on its value
+    pub fn is_variable(&self) -> bool {
+        match self.kind {
+            DataType::String | DataType::Array => true,
+            DataType::NewId => self.interface.is_none(),
+            _ => false
+        }
+    }
+    /// Whether the getter consumes from the message arguments rather than the client's file descriptor queue
+    pub fn reads_args(&self) -> bool {
+        !matches!(self.kind, DataType::Fd)
+    }
+    pub(crate) fn pusher(&self, wl: &syn::Path) -> proc_macro2::TokenStream {
+        let arg = self.parameter();
         match self.kind {
             DataType::Int => quote!{message.push_i32(#arg)},
             DataType::Uint => quote!{message.push_u32(#arg)},
@@ -168,62 +985,127 @@ impl Arg {
             DataType::String => quote!{message.push_str(#arg)},
             DataType::Array => quote!{message.push_array(#arg)},
             DataType::Fd => quote!{message.push_file(#arg)},
-            DataType::Object => quote!{{use ::wl::Object; message.push_u32(#arg.object())}},
-            DataType::NewId => if let Some(_) = self.interface {
+            // `Nullable` is the only concrete type, as other object arguments are trait objects
+            DataType::Object if self.nullable => quote!{{use #wl::Object; message.push_u32(#arg.object())}},
+            DataType::Object => quote!{message.push_u32(#arg.object())},
+            DataType::NewId => if self.interface.is_some() {
                 quote!{message.push_new_id(#arg)}
             } else {
                 quote!{message.push_dynamic_new_id(#arg)}
             },
         }
     }
-    pub(crate) fn request_data_type(&self, owning_interface: &String, bindings: &HashMap<String, Binding>) -> TokenStream {
+    pub(crate) fn request_data_type(&self, owning_interface: &str, bindings: &HashMap<String, Binding>, wl: &syn::Path) -> TokenStream {
         match self.kind {
             DataType::Int => quote!{ i32 },
             DataType::Uint => quote!{ u32 },
-            DataType::Fixed => quote!{ ::wl::Fixed },
+            DataType::Fixed if bindings[&camel_case(owning_interface)].options.fixed_as_f64 => quote!{ f64 },
+            DataType::Fixed => quote!{ #wl::Fixed },
             DataType::String => quote!{ ::std::string::String },
-            DataType::Array => quote!{ ::wl::Array },
+            DataType::Array if bindings[&camel_case(owning_interface)].options.borrowed_args => quote!{ &[u8] },
+            DataType::Array => quote!{ #wl::Array },
             DataType::Fd => quote!{ ::std::fs::File },
             DataType::Object => {
-                if let Some(interface) = &self.interface {
-                    if let Some(Binding { implementation, ..}) = bindings.get(&interface.to_snake_case()) {
-                        if self.nullable {
-                            quote!{ ::wl::Nullable<::wl::server::Lease<#implementation>> }
+                if let Some(interface) = self.interface.as_ref().filter(|_| !self.is_loose(owning_interface, bindings)) {
+                    if let Some(binding) = binding_of(bindings, interface) {
+                        let implementation = binding.implementation_type();
+                        let lease = if self.nullable {
+                            quote!{ #wl::Nullable<#wl::server::Lease<#implementation>> }
+                        } else {
+                            quote!{ #wl::server::Lease<#implementation> }
+                        };
+                        if self.allow_self {
+                            quote!{ ReceiverOr<#lease> }
                         } else {
-                            quote!{ ::wl::server::Lease<#implementation> }
+                            lease
                         }
                     } else {
-                        let owner = owning_interface.to_camel_case();
-                        let to_implement = interface.to_camel_case();
-                        syn::Error::new(bindings[owning_interface].implementation.span(), format!("Interface {:?} depends on {:?}. Please specify an implementation for {:?}.", owner, to_implement, to_implement)).to_compile_error()
+                        let owner = camel_case(owning_interface);
+                        let to_implement = camel_case(interface);
+                        syn::Error::new(bindings[&owner].implementation.span(), format!("Interface {:?} depends on {:?}. Please specify an implementation for {:?}.", owner, to_implement, to_implement)).to_compile_error()
                     }
                 } else {
                     if self.nullable {
-                        quote!{ ::wl::Nullable<::wl::server::Lease<dyn ::std::any::Any>> }
+                        quote!{ #wl::Nullable<#wl::server::Lease<dyn ::std::any::Any>> }
                     } else {
-                        quote!{ ::wl::server::Lease<dyn ::std::any::Any> }
+                        quote!{ #wl::server::Lease<dyn ::std::any::Any> }
                     }
                 }
             },
-            DataType::NewId => quote!{ ::wl::NewId }
+            DataType::NewId => quote!{ #wl::NewId }
         }
     }
-    pub fn event_data_type(&self) -> syn::Type {
+    /// The parameter type of a request method as written with the prelude in scope, where `_` stands for the
+    /// implementation bound to an object argument's interface
+    pub fn signature_type(&self) -> String {
+        let kind = match self.kind {
+            DataType::Int => "i32",
+            DataType::Uint => "u32",
+            DataType::Fixed => "Fixed",
+            DataType::String => "String",
+            DataType::Array => "Array",
+            DataType::Fd => "std::fs::File",
+            DataType::Object => if self.interface.is_some() {
+                "Lease<_>"
+            } else {
+                "Lease<dyn std::any::Any>"
+            },
+            DataType::NewId => "NewId"
+        };
+        let kind = if self.nullable && self.kind == DataType::Object {
+            format!("Nullable<{}>", kind)
+        } else {
+            kind.to_string()
+        };
+        if self.allow_self {
+            format!("ReceiverOr<{}>", kind)
+        } else {
+            kind
+        }
+    }
+    pub fn event_data_type(&self, wl: &syn::Path) -> syn::Type {
         match self.kind {
             DataType::Int => parse_quote!{ i32 },
             DataType::Uint => parse_quote!{ u32 },
-            DataType::Fixed => parse_quote!{ ::wl::Fixed },
+            DataType::Fixed => parse_quote!{ #wl::Fixed },
             DataType::String => parse_quote!{ &str },
-            DataType::Array => parse_quote!{ ::wl::Array },
+            DataType::Array => parse_quote!{ #wl::Array },
             DataType::Fd => parse_quote!{ &::std::fs::File },
             DataType::Object => if self.nullable {
-                parse_quote!{ ::wl::Nullable<&dyn ::wl::Object> }
+                parse_quote!{ #wl::Nullable<&dyn #wl::Object> }
             } else {
-                parse_quote!{ &dyn ::wl::Object }
+                parse_quote!{ &dyn #wl::Object }
             },
-            DataType::NewId => parse_quote!{ ::wl::NewId }
+            DataType::NewId => parse_quote!{ #wl::NewId }
+        }
+    }
+    /// The wire type of the argument and the version it was appended in, e.g. `object<wl_buffer>?` or `uint since 3`
+    pub fn signature(&self) -> String {
+        match self.since {
+            Some(since) => format!("{} since {}", self.wire_type(), since),
+            None => self.wire_type()
         }
     }
+    /// The wire type of the argument, e.g. `object<wl_buffer>?`
+    fn wire_type(&self) -> String {
+        let mut wire_type = match self.kind {
+            DataType::Int => "int",
+            DataType::Uint => "uint",
+            DataType::Fixed => "fixed",
+            DataType::String => "string",
+            DataType::Array => "array",
+            DataType::Fd => "fd",
+            DataType::Object => "object",
+            DataType::NewId => "new_id"
+        }.to_string();
+        if let Some(interface) = &self.interface {
+            wire_type += &format!("<{}>", interface);
+        }
+        if self.nullable {
+            wire_type += "?";
+        }
+        wire_type
+    }
     pub fn debug_string(&self) -> &'static str {
         match self.kind {
             DataType::NewId if self.interface.is_none() => "dyn {}",
@@ -233,7 +1115,21 @@ impl Arg {
     }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize)]
+/// The argument with its wire type, e.g. `transform: int enum wl_output.transform since 2`
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.wire_type())?;
+        if let Some(enumeration) = &self.enumeration {
+            write!(f, " enum {}", enumeration)?;
+        }
+        if let Some(since) = self.since {
+            write!(f, " since {}", since)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DataType {
     Int,
@@ -244,4 +1140,142 @@ pub enum DataType {
     Fd,
     Object,
     NewId
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interface_display() {
+        let protocol = Protocol::load("tests/wayland.toml").unwrap();
+        let callback = protocol.interfaces.iter().find(|interface| interface.name == "wl_callback").unwrap();
+        assert_eq!(callback.to_string(), "interface wl_callback version 1, trait WlCallback\n  event 0 done(callback_data: uint)\n");
+        let display = protocol.interfaces.iter().find(|interface| interface.name == "wl_display").unwrap().to_string();
+        assert!(display.starts_with("interface wl_display version 1, trait WlDisplay\n  request 0 sync(callback: new_id<wl_callback>)\n"), "{}", display);
+        assert!(display.contains("  enum error, type WlDisplayError\n    INVALID_OBJECT = 0\n"), "{}", display);
+    }
+    #[test]
+    fn nested_includes() {
+        let protocol = Protocol::load("tests/include/protocol.toml").unwrap();
+        let names: Vec<&str> = protocol.interfaces.iter().map(|interface| interface.name.as_str()).collect();
+        assert_eq!(names, ["ext_root", "ext_outer", "ext_inner"]);
+        let files: Vec<PathBuf> = ["protocol.toml", "interfaces/ext_outer.toml", "interfaces/nested/ext_inner.toml"].iter()
+            .map(|file| Path::new("tests/include").join(file))
+            .collect();
+        assert_eq!(protocol.files, files);
+    }
+    #[test]
+    fn enum_mapping_errors() {
+        let errors = |maps_to: &str, entry: &str| {
+            let source = format!("name = \"t\"\nschema = 2\n[[interface]]\nname = \"t_a\"\nversion = 1\n[[interface.enum]]\nname = \"state\"\nmaps_to = \"{}\"\n[[interface.enum.entry]]\nname = \"on\"\nvalue = 0\n[[interface.enum]]\nname = \"mode\"\n[[interface.enum.entry]]\nname = \"{}\"\nvalue = 0\n", maps_to, entry);
+            Protocol::from_str(&source).unwrap().enum_mappings().err().unwrap_or_default()
+        };
+        assert_eq!(errors("t_a.mode", "on"), Vec::<String>::new());
+        assert_eq!(errors("mode", "on"), Vec::<String>::new());
+        assert_eq!(errors("t_b.mode", "on"), ["Enum \"t_a.state\" maps to \"t_b.mode\", which does not exist"]);
+        assert_eq!(errors("t_a.absent", "on"), ["Enum \"t_a.state\" maps to \"t_a.absent\", which does not exist"]);
+        assert_eq!(errors("state", "on"), ["Enum \"t_a.state\" maps to itself"]);
+        assert_eq!(errors("t_a.mode", "off"), ["Enum \"t_a.state\" maps to \"t_a.mode\", but the two have no entries of the same name"]);
+    }
+    #[test]
+    fn include_errors() {
+        let error = |path| Protocol::load(path).unwrap_err();
+        assert_eq!(
+            error("tests/include/duplicate.toml"),
+            "Interface \"ext_inner\" of \"tests/include/interfaces/nested/ext_inner.toml\" is already defined by \"tests/include/duplicate.toml\", when merging \"tests/include/interfaces/ext_outer.toml\", included from \"tests/include/duplicate.toml\""
+        );
+        let missing = error("tests/include/missing.toml");
+        assert!(missing.starts_with("Unable to read protocol specification file \"tests/include/interfaces/ext_absent.toml\", included from \"tests/include/interfaces/ext_broken.toml\", included from \"tests/include/missing.toml\": "), "{}", missing);
+        assert_eq!(
+            error("tests/include/cycle.toml"),
+            "Protocol specification file \"tests/include/interfaces/../cycle.toml\", included from \"tests/include/interfaces/ext_cycle.toml\", included from \"tests/include/cycle.toml\" includes itself"
+        );
+    }
+    #[test]
+    fn schema_matrix() {
+        let load = |file: &str| Protocol::load(format!("tests/schema/{}.toml", file));
+        // Files of the first schema load whether or not they declare it
+        assert!(load("first").unwrap().undeclared_schemas.is_empty());
+        let undeclared = load("undeclared").unwrap().undeclared_schemas;
+        assert_eq!(undeclared.len(), 1);
+        assert_eq!((&undeclared[0].fields[..], undeclared[0].schema), (&["interface.trimmed".to_string(), "arg.max_len".to_string()][..], 2));
+        assert!(crate::lints::lint(&load("undeclared").unwrap())[0].contains("declares no schema but uses `interface.trimmed`, `arg.max_len`"));
+        assert!(load("declared").unwrap().undeclared_schemas.is_empty());
+        assert_eq!(load("outdated").unwrap_err(), "Protocol specification file \"tests/schema/outdated.toml\" declares schema 1 but uses `arg.max_len`, introduced in schema 2");
+        // A later schema is reported before the file fails to parse as this one
+        assert_eq!(load("future").unwrap_err(), format!("Protocol specification file \"tests/schema/future.toml\" requires wl-macro schema 3, this version supports {}", SCHEMA));
+        assert!(load("invalid").unwrap_err().ends_with("schema must be a positive integer, not \"2\""));
+    }
+    #[test]
+    fn formats() {
+        let load = |path: &str| {
+            let mut protocol = Protocol::load(path).unwrap();
+            protocol.files.clear();
+            protocol
+        };
+        let toml = load("tests/extension.toml");
+        let others: &[&str] = &[
+            #[cfg(feature = "json")]
+            "tests/extension.json",
+            #[cfg(feature = "yaml")]
+            "tests/extension.yaml"
+        ];
+        for path in others {
+            assert_eq!(load(path), toml, "{} differs from the TOML specification", path);
+        }
+        #[cfg(not(feature = "json"))]
+        assert_eq!(Protocol::load("tests/extension.json").unwrap_err(), "Failed to parse protocol specification file \"tests/extension.json\": JSON specifications require the `json` feature of wl-macro");
+        // Parse errors give the position of the problem in every format
+        let error = Protocol::from_str_as("name = \"t\"\n[[interface]]\nversion = 1\n", Format::Toml).unwrap_err();
+        assert!(error.ends_with("at line 2 column 1"), "{}", error);
+        #[cfg(feature = "json")]
+        {
+            let error = Protocol::from_str_as("{\n    \"name\": \"t\",\n    \"interface\": [{ \"version\": 1 }]\n}", Format::Json).unwrap_err();
+            assert!(error.ends_with("at line 3 column 34"), "{}", error);
+        }
+        #[cfg(feature = "yaml")]
+        {
+            let error = Protocol::from_str_as("name: t\ninterface:\n- version: 1\n", Format::Yaml).unwrap_err();
+            assert!(error.ends_with("at line 3 column 3"), "{}", error);
+        }
+    }
+    #[test]
+    fn encodings() {
+        // A leading byte order mark is skipped
+        let protocol = Protocol::load("tests/encoding/bom.toml").unwrap();
+        assert_eq!((protocol.name.as_str(), protocol.interfaces[0].name.as_str()), ("bom", "ext_bom"));
+        // Other encodings are reported at the first byte that is not UTF-8
+        assert_eq!(Protocol::load("tests/encoding/latin1.toml").unwrap_err(), "Protocol specification file \"tests/encoding/latin1.toml\" is not valid UTF-8 (byte offset 67)");
+    }
+    #[test]
+    fn oversized_interfaces() {
+        let synthetic = |requests: usize, events: usize| {
+            let mut protocol = Protocol::from_str("name = \"t\"\n[[interface]]\nname = \"t_big\"\nversion = 1\n[[interface.request]]\nname = \"r\"\n[[interface.event]]\nname = \"e\"\n").unwrap();
+            // Only the counts are checked, so copies of one message stand in for distinct ones
+            let interface = &mut protocol.interfaces[0];
+            let (request, event) = (interface.requests[0].clone(), interface.events[0].clone());
+            interface.requests.resize(requests, request);
+            interface.events.resize(events, event);
+            protocol.check().into_iter().map(|issue| issue.to_string()).collect::<Vec<_>>()
+        };
+        assert!(synthetic(MAX_MESSAGES, MAX_MESSAGES).is_empty());
+        assert_eq!(synthetic(MAX_MESSAGES + 1, 2), ["interface \"t_big\" lists 1025 requests but at most 1024 of each are supported"]);
+        // Counts past the 16-bit opcode are reported rather than wrapping around to a small count
+        assert_eq!(synthetic(1, 65537), ["interface \"t_big\" lists 65537 events but at most 1024 of each are supported"]);
+    }
+    #[test]
+    fn schema_fields_are_introduced_after_the_first() {
+        for (table, field, schema) in SCHEMA_FIELDS {
+            assert!((2..=SCHEMA).contains(schema), "{}.{} is listed with schema {}", table, field, schema);
+        }
+    }
+    #[test]
+    fn format_contract() {
+        // Argument types the macro does not handle are rejected listing those it does
+        let error = Protocol::from_str("name = \"t\"\n[[interface]]\nname = \"t_a\"\nversion = 1\n[[interface.request]]\nname = \"r\"\n[[interface.request.arg]]\nname = \"a\"\ntype = \"uint32\"\n").unwrap_err().to_string();
+        assert!(error.starts_with("unknown variant `uint32`, expected one of `int`, `uint`, `fixed`, `string`, `array`, `fd`, `object`, `new_id`"), "{}", error);
+        // Rules enforced when loading a file are not applied by parsing alone
+        let protocol = Protocol::from_str("name = \"t\"\n[[interface]]\nname = \"t_a\"\nversion = 1\n[[interface.request]]\nname = \"r\"\nsince = 2\n").unwrap();
+        assert_eq!(protocol.check(), [ValidationIssue("Request \"t_a.r\" is introduced in version 2, after interface \"t_a\" version 1".into())]);
+    }
+}
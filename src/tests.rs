@@ -0,0 +1,627 @@
+//! Expansion tests, running the generation outside of the compiler against the core protocol vendored in `tests/`.
+//!
+//! Generated code is compared against pretty-printed snapshots in `tests/snapshots`, which are written when missing.
+//! Setting `WL_MACRO_UPDATE_SNAPSHOTS` rewrites them once a change to the generated code has been reviewed. Diagnostics
+//! are covered by the UI tests in `tests/ui`, which compile against a stand-in runtime.
+
+use std::fs;
+use super::*;
+
+const PROTOCOL: &str = "tests/wayland.toml";
+const UPDATE_VARIABLE: &str = "WL_MACRO_UPDATE_SNAPSHOTS";
+
+fn protocol() -> Protocol {
+    Protocol::load(PROTOCOL).unwrap()
+}
+fn interface<'a>(protocol: &'a Protocol, name: &str) -> &'a Interface {
+    protocol.interfaces.iter().find(|interface| interface.name == name).unwrap()
+}
+fn module(module: TokenStream) -> ProtocolModule {
+    syn::parse2(module).unwrap()
+}
+fn expand(module: TokenStream) -> TokenStream {
+    expand_protocol(PROTOCOL, module)
+}
+fn expand_protocol(path: &str, module: TokenStream) -> TokenStream {
+    expand_server_protocol(syn::parse2(quote!{ #path }).unwrap(), self::module(module))
+}
+fn runtime() -> Path {
+    syn::parse_quote!{ ::wl }
+}
+
+/// Compare pretty-printed items against the snapshot of the same name
+fn assert_snapshot(name: &str, items: TokenStream) {
+    // Specification files are tracked by absolute path, which differs between checkouts
+    let directory = std::env::current_dir().unwrap().display().to_string();
+    let actual = prettyplease::unparse(&syn::parse2(items).unwrap()).replace(&directory, "$CARGO_MANIFEST_DIR");
+    let path = format!("tests/snapshots/{}.rs", name);
+    let expected = match fs::read_to_string(&path) {
+        Ok(_) if std::env::var_os(UPDATE_VARIABLE).is_some() => None,
+        Ok(expected) => Some(expected),
+        Err(_) => None
+    };
+    match expected {
+        Some(expected) => if let Some((line, (expected, actual))) = expected.lines().zip(actual.lines()).enumerate().find(|(_, (expected, actual))| expected != actual) {
+            panic!("{} differs from the generated code at line {}:\n  snapshot:  {}\n  generated: {}\nSet {} to update it", path, line + 1, expected, actual, UPDATE_VARIABLE)
+        } else {
+            assert_eq!(expected.lines().count(), actual.lines().count(), "{} differs from the generated code in length. Set {} to update it", path, UPDATE_VARIABLE)
+        },
+        None => fs::write(&path, actual).unwrap()
+    }
+}
+/// The names of the traits declared at the top level of the generated module
+fn traits(expansion: TokenStream) -> Vec<String> {
+    let module: syn::ItemMod = syn::parse2(expansion).unwrap();
+    module.content.unwrap().1.iter().filter_map(|item| match item {
+        Item::Trait(item) => Some(item.ident.to_string()),
+        _ => None
+    }).collect()
+}
+
+#[test]
+fn wl_surface_snapshot() {
+    let protocol = protocol();
+    let module = module(quote! {
+        mod wayland {
+            #![loose_objects]
+            type WlSurface = crate::Surface;
+            type WlCallback = crate::Callback;
+        }
+    });
+    assert_snapshot("wl_surface", generate_interface(interface(&protocol, "wl_surface"), &module.bindings, None, &runtime()));
+}
+#[test]
+fn wl_callback_module_snapshot() {
+    assert_snapshot("wl_callback_module", expand(quote! {
+        pub mod wayland {
+            type WlCallback = crate::Callback;
+        }
+    }));
+}
+#[test]
+fn enum_snapshots() {
+    let protocol = protocol();
+    let representations = protocol.enum_representations();
+    let enums = ["wl_display", "wl_shm"].iter().map(|name| {
+        let interface = interface(&protocol, name);
+        let enums: Vec<&Enum> = interface.enums.iter().collect();
+        generate_enums(interface, &enums, &representations, false, false, &runtime())
+    });
+    assert_snapshot("enums", quote!{ #(#enums)* });
+}
+#[test]
+fn error_enums_display_summaries() {
+    let protocol = protocol();
+    let display = interface(&protocol, "wl_display");
+    let expansion = generate_enums(display, &display.enums.iter().collect::<Vec<_>>(), &protocol.enum_representations(), false, true, &runtime()).to_string();
+    assert!(expansion.contains("impl :: std :: fmt :: Display for WlDisplayError"));
+    assert!(expansion.contains("impl :: std :: error :: Error for WlDisplayError"));
+    assert!(expansion.contains("Self :: INVALID_OBJECT => f . write_str (\"server couldn't find object\")"));
+    assert!(expansion.contains("pub fn protocol_error"));
+    assert!(expansion.contains(":: wl :: DispatchError :: Protocol"));
+    // Other enums are untouched
+    let shm = interface(&protocol, "wl_shm");
+    let format = shm.enums.iter().find(|e| e.name == "format").unwrap();
+    let expansion = generate_enums(shm, &[format], &protocol.enum_representations(), false, true, &runtime()).to_string();
+    assert!(!expansion.contains("Display"));
+}
+
+#[test]
+fn split_traits() {
+    let traits = traits(expand(quote! {
+        mod wayland {
+            #![split_traits]
+            type WlCallback = crate::Callback;
+        }
+    }));
+    assert!(["WlCallback", "WlCallbackRequests", "WlCallbackEvents"].iter().all(|name| traits.iter().any(|t| t == name)), "{:?}", traits);
+    let traits = self::traits(expand(quote! {
+        mod wayland {
+            type WlCallback = crate::Callback;
+        }
+    }));
+    assert!(!traits.iter().any(|name| name == "WlCallbackEvents"), "{:?}", traits);
+}
+/// The link targets of the generated traits' documentation naming a method of a trait, as `(trait, target)`, that do
+/// not resolve to a method of a trait generated in the module, along with the number of targets checked
+fn unresolved_method_links(expansion: TokenStream) -> (Vec<(String, String)>, usize) {
+    let module: syn::ItemMod = syn::parse2(expansion).unwrap();
+    let traits: Vec<syn::ItemTrait> = module.content.unwrap().1.into_iter().filter_map(|item| match item {
+        Item::Trait(item) => Some(item),
+        _ => None
+    }).collect();
+    let methods = |item: &syn::ItemTrait| item.items.iter().filter_map(|item| match item {
+        syn::TraitItem::Method(method) => Some(method.sig.ident.unraw().to_string()),
+        _ => None
+    }).collect::<Vec<_>>();
+    let (mut unresolved, mut checked) = (Vec::new(), 0);
+    for item in &traits {
+        let docs = item.attrs.iter().filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(doc), .. })) if path.is_ident("doc") => Some(doc.value()),
+            _ => None
+        });
+        for doc in docs {
+            for link in doc.split("](").skip(1) {
+                let target = &link[..link.find(')').unwrap_or(link.len())];
+                let (owner, method) = match target.split_once("::") {
+                    Some(parts) => parts,
+                    None => continue
+                };
+                let owner = match owner {
+                    "Self" => Some(item),
+                    owner => traits.iter().find(|item| item.ident == owner)
+                };
+                if let Some(owner) = owner {
+                    checked += 1;
+                    if !methods(owner).iter().any(|name| name == method) {
+                        unresolved.push((item.ident.to_string(), target.to_string()));
+                    }
+                }
+            }
+        }
+    }
+    (unresolved, checked)
+}
+#[test]
+fn overview_links_resolve() {
+    let options = vec![
+        quote!{},
+        quote!{ #![split_traits] },
+        quote!{ #![doc_all] }
+    ];
+    for options in options {
+        let (unresolved, checked) = unresolved_method_links(expand(quote! {
+            mod wayland {
+                #options
+                #![loose_objects]
+                type WlSurface = crate::Surface (rename<commit = wp_commit, enter = entered>);
+                type WlCallback = crate::Callback;
+                type WlPointer = crate::Pointer;
+            }
+        }));
+        assert!(unresolved.is_empty(), "{}: {:?}", options, unresolved);
+        // wl_surface, wl_callback and wl_pointer have 20 requests and events between them
+        assert!(checked >= 20, "{}: only {} links checked", options, checked);
+    }
+}
+#[test]
+fn method_names_are_raw_only_for_keywords() {
+    let expansion = expand(quote! {
+        mod wayland {
+            #![loose_objects]
+            type WlDisplay = crate::Display;
+            type WlRegistry = crate::Registry;
+            type WlCallback = crate::Callback;
+            type WlShellSurface = crate::ShellSurface;
+        }
+    });
+    let module: syn::ItemMod = syn::parse2(expansion.clone()).unwrap();
+    let methods = |name: &str| module.content.as_ref().unwrap().1.iter().find_map(|item| match item {
+        Item::Trait(item) if item.ident == name => Some(item.items.iter().filter_map(|item| match item {
+            syn::TraitItem::Method(method) => Some(method.sig.ident.to_string()),
+            _ => None
+        }).collect::<Vec<_>>()),
+        _ => None
+    }).unwrap();
+    assert!(methods("WlDisplay").iter().any(|method| method == "sync"), "{:?}", methods("WlDisplay"));
+    assert!(methods("WlShellSurface").iter().any(|method| method == "r#move"), "{:?}", methods("WlShellSurface"));
+    // Dispatch calls the methods by the same names
+    let expansion = expansion.to_string();
+    assert!(expansion.contains(". sync (client"), "{}", expansion);
+    assert!(expansion.contains(". r#move (client"), "{}", expansion);
+    assert!(!expansion.contains("r#sync"), "{}", expansion);
+}
+#[test]
+fn binding_paths_in_nested_modules() {
+    let module = module(quote! {
+        mod wayland {
+            type WlDisplay = Display;
+            type WlRegistry = self::Registry;
+            type WlCallback = super::Callback;
+            type WlCompositor = crate::Compositor;
+            type WlShm = ::shm::Shm;
+        }
+    });
+    let nested = |interface: &str| module.bindings[interface].implementation_type_in(2).to_string();
+    assert_eq!(nested("WlDisplay"), "super :: super :: Display");
+    assert_eq!(nested("WlRegistry"), "super :: super :: Registry");
+    assert_eq!(nested("WlCallback"), "super :: super :: super :: Callback");
+    assert_eq!(nested("WlCompositor"), "crate :: Compositor");
+    assert_eq!(nested("WlShm"), ":: shm :: Shm");
+    assert_eq!(module.bindings["WlCallback"].implementation_type().to_string(), "super :: Callback");
+}
+#[test]
+fn extension_attributes() {
+    let module: syn::File = syn::parse2(expand_protocol("tests/extension.toml", quote! {
+        mod extension {
+            type ExtAudit = crate::Audit;
+        }
+    })).unwrap();
+    // Items are compared with whitespace collapsed so that the assertions do not depend on indentation
+    let expansion = prettyplease::unparse(&module).split_whitespace().collect::<Vec<_>>().join(" ");
+    for item in [
+        "#[cfg_attr(feature = \"audit\", doc(alias = \"audited\"))] pub trait ExtAudit",
+        "#[rustfmt::skip] #[cfg_attr(feature = \"audit\", must_use)] fn set_title(",
+        "#[rustfmt::skip] fn notice(",
+        "#[rustfmt::skip] fn notice_into(",
+        "#[rustfmt::skip] #[cfg_attr(feature = \"audit\", doc(alias = \"ext_mode\"))] pub struct ExtAuditMode"
+    ] {
+        assert!(expansion.contains(item), "{} is missing from {}", item, expansion);
+    }
+}
+#[test]
+fn malformed_extension_attributes() {
+    let mut protocol = Protocol::load("tests/extension.toml").unwrap();
+    protocol.interfaces[0].requests[0].attrs = vec!["#[rustfmt::skip] fn audit() {}".into(), "// audit".into()];
+    let module = module(quote! {
+        mod extension {
+            type ExtAudit = crate::Audit;
+        }
+    });
+    let expansion = generate_interface(&protocol.interfaces[0], &module.bindings, None, &runtime()).to_string();
+    assert!(expansion.contains("Attribute \\\"#[rustfmt::skip] fn audit() {}\\\" of request \\\"ext_audit.set_title\\\" is not an outer attribute"), "{}", expansion);
+    assert!(expansion.contains("Attribute \\\"// audit\\\" of request \\\"ext_audit.set_title\\\" contains no attribute"), "{}", expansion);
+}
+#[test]
+fn api_hash_notices_reordered_arguments() {
+    let module = module(quote! {
+        mod wayland {
+            #![loose_objects]
+            type WlPointer = crate::Pointer;
+        }
+    });
+    let mut protocol = protocol();
+    let items = |protocol: &Protocol| api::items(&[interface(protocol, "wl_pointer")], &[], &module.bindings);
+    let before = items(&protocol);
+    assert!(before.contains(&"event WlPointer::button(wl_serial: u32, wl_time: u32, wl_button: u32, wl_state: u32)".to_string()), "{:?}", before);
+    let pointer = protocol.interfaces.iter_mut().find(|interface| interface.name == "wl_pointer").unwrap();
+    pointer.events.iter_mut().find(|event| event.name == "button").unwrap().args.swap(0, 1);
+    assert_ne!(api::hash(&before), api::hash(&items(&protocol)));
+}
+#[test]
+fn missing_dependency_chain() {
+    let expansion = expand_protocol("tests/dependencies.toml", quote! {
+        mod dependencies {
+            type ExtA = crate::A;
+            type ExtB = crate::B;
+            type ExtC = crate::C;
+        }
+    }).to_string();
+    assert!(expansion.contains("Interface \\\"ExtC\\\" depends on \\\"ExtD\\\", which is missing a binding: ext_a → ext_b → ext_c → ext_d."), "{}", expansion);
+    // Only the missing binding is reported, rather than every item referring to it
+    assert_eq!(expansion.matches("compile_error").count(), 1, "{}", expansion);
+}
+#[test]
+fn unreferenced_enums_allow_dead_code() {
+    let module: syn::File = syn::parse2(expand_protocol("tests/extension.toml", quote! {
+        mod extension {
+            type ExtAudit = crate::Audit;
+            type ExtNote = crate::Note;
+        }
+    })).unwrap();
+    let expansion = prettyplease::unparse(&module).split_whitespace().collect::<Vec<_>>().join(" ");
+    let state = "#[repr(transparent)] pub struct ExtNoteState(u32); #[allow(dead_code)] ///the audit state of a note, used by no request or event /** */ /// ///No request or event of the interfaces bound by this module uses this enum, which is generated for the completeness of the protocol. impl ExtNoteState {";
+    assert!(expansion.contains(state), "{}", expansion);
+    // Only the type and inherent impl of the unused enum are allowed, not those of `ext_audit.mode` used by an event,
+    // besides the assertion at each binding
+    assert_eq!(expansion.matches("#[allow(dead_code)]").count(), 4, "{}", expansion);
+}
+#[test]
+fn signature_hash_follows_the_wire_format() {
+    let hashes = |tweak: &dyn Fn(&mut Interface)| {
+        let mut protocol = protocol();
+        tweak(protocol.interfaces.iter_mut().find(|interface| interface.name == "wl_surface").unwrap());
+        (interface(&protocol, "wl_surface").signature_hash(), protocol.signature_hash())
+    };
+    let unchanged = hashes(&|_| ());
+    assert_eq!(unchanged, hashes(&|_| ()));
+    // Names and documentation do not reach the wire
+    assert_eq!(unchanged, hashes(&|surface| {
+        surface.requests[1].name = "attach_buffer".into();
+        surface.requests[1].args[0].name = "wl_buffer".into();
+        surface.requests[1].summary = None;
+    }));
+    type Tweak = Box<dyn Fn(&mut Interface)>;
+    let tweaks: Vec<(&str, Tweak)> = vec![
+        ("version", Box::new(|surface| surface.version += 1)),
+        ("argument type", Box::new(|surface| surface.requests[1].args[1].kind = DataType::Uint)),
+        ("nullability", Box::new(|surface| surface.requests[1].args[0].nullable = false)),
+        ("argument interface", Box::new(|surface| surface.requests[1].args[0].interface = Some("wl_callback".into()))),
+        ("request since", Box::new(|surface| surface.requests[1].since = Some(2))),
+        ("argument since", Box::new(|surface| surface.requests[1].args[2].since = Some(5))),
+        ("argument order", Box::new(|surface| surface.requests[1].args.swap(0, 1))),
+        ("message order", Box::new(|surface| surface.requests.swap(0, 1)))
+    ];
+    for (tweak, change) in &tweaks {
+        let (interface, protocol) = hashes(&**change);
+        assert_ne!(interface, unchanged.0, "{} does not change the interface hash", tweak);
+        assert_ne!(protocol, unchanged.1, "{} does not change the protocol hash", tweak);
+    }
+    // The generated constants do not depend on what the module binds
+    let constants = |module| {
+        let signature_hash = |items: &[Item]| items.iter().find_map(|item| match item {
+            Item::Const(constant) if constant.ident == "SIGNATURE_HASH" => {
+                let value = &constant.expr;
+                Some(quote!{ #value }.to_string())
+            },
+            _ => None
+        }).unwrap();
+        let module: syn::ItemMod = syn::parse2(expand(module)).unwrap();
+        let items = module.content.unwrap().1;
+        let callback = items.iter().find_map(|item| match item {
+            Item::Mod(metadata) if metadata.ident == "wl_callback" => Some(signature_hash(&metadata.content.as_ref().unwrap().1)),
+            _ => None
+        }).unwrap();
+        (callback, signature_hash(&items))
+    };
+    let callback = constants(quote! {
+        mod wayland {
+            type WlCallback = crate::Callback;
+        }
+    });
+    assert_eq!(callback, (format!("{}u64", interface(&protocol(), "wl_callback").signature_hash()), format!("{}u64", protocol().signature_hash())));
+    assert_eq!(callback, constants(quote! {
+        mod wayland {
+            #![split_traits]
+            type WlDisplay = crate::Display;
+            type WlRegistry = crate::Registry;
+            type WlCallback = crate::Callback (version<1>);
+        }
+    }));
+}
+#[test]
+fn trace_placeholders_match_arguments() {
+    let mut trace = Trace::default();
+    trace.text("{literal} ");
+    trace.value("dyn {}", quote!{ a });
+    trace.value("{:?}", quote!{ b });
+    assert_eq!(placeholder_count(&trace.format), 2);
+    assert!(trace.check().is_ok());
+    trace.value("{} {}", quote!{ c });
+    assert_eq!(trace.check().unwrap_err(), "Trace \"{{literal}} dyn {}{:?}{} {}\" has 4 placeholders for 3 arguments. This is a bug in wl-macro");
+    // Every trace of every message, including those of a failure part way through a request, is checked
+    for path in &[PROTOCOL, "tests/stress.toml"] {
+        let protocol = Protocol::load(path).unwrap();
+        let bindings = protocol.interfaces.iter().map(|interface| {
+            let (name, implementation) = (ident(&camel_case(&interface.name)), ident(&format!("Implementation{}", camel_case(&interface.name))));
+            quote!{ type #name = crate::#implementation; }
+        });
+        let expansion = expand_protocol(path, quote! {
+            mod protocol {
+                #![loose_objects]
+                #(#bindings)*
+            }
+        }).to_string();
+        assert!(!expansion.contains("compile_error"), "{}", expansion);
+    }
+}
+#[test]
+fn stress_trace() {
+    let module = module(quote! {
+        mod stress {
+            type ExtStress = crate::Stress;
+        }
+    });
+    let protocol = Protocol::load("tests/stress.toml").unwrap();
+    let stress = &protocol.interfaces[0];
+    let placeholders: Vec<&str> = ["{}", "{}", "{}", "{:?}"].iter().cycle().take(32).copied().collect();
+    let terse = placeholders.join(", ");
+    let labelled = placeholders.iter().enumerate().map(|(field, placeholder)| format!("field_{}: {}", field, placeholder)).collect::<Vec<_>>().join(", ");
+    let args = (0..32).map(|field| format!("wl_field_{}", field)).collect::<Vec<_>>().join(" , ");
+    let print = |heading: &str, object: &str, message: &str| {
+        let eprintln = |format: &str| format!(":: std :: eprintln ! (\"{}{{}}@{{}}.{{}}({})\" , \"ext_stress\" , {} , \"{}\" , {})", heading, format, object, message, args);
+        format!("if * :: wl :: DEBUG_VERBOSE {{ {} }} else {{ {} }}", eprintln(&labelled), eprintln(&terse))
+    };
+    let request = generate_request_debug_print(&stress.requests[0], &stress.requests[0].args, None, stress, &module.bindings["ExtStress"], &runtime()).to_string();
+    assert_eq!(request, print("", "lease . object ()", "configure"));
+    let event = generate_event_debug_print(&stress.events[0], stress, quote!{ self.object() }, &runtime()).to_string();
+    assert_eq!(event, print("-> ", "self . object ()", "configured"));
+}
+#[test]
+fn message_sizes() {
+    let protocol = protocol();
+    // wl_pointer.enter: serial, surface, surface_x and surface_y, each a word
+    let enter = &interface(&protocol, "wl_pointer").events[0];
+    assert_eq!((protocol::min_size(&enter.args), protocol::fixed_size(&enter.args)), (24, Some(24)));
+    // wl_registry.global: name, interface and version, the interface string taking at least its length
+    let registry = interface(&protocol, "wl_registry");
+    let global = &registry.events[0];
+    assert_eq!((protocol::min_size(&global.args), protocol::fixed_size(&global.args)), (20, None));
+    // wl_registry.bind: name and a new_id preceded by its interface and version
+    assert_eq!(registry.largest_fixed_message(), 24);
+    let expansion = expand(quote! {
+        mod wayland {
+            #![loose_objects]
+            type WlPointer = crate::Pointer;
+            type WlRegistry = crate::Registry;
+        }
+    }).to_string();
+    assert!(expansion.contains("name : \"enter\" , opcode : 0u16 , since : 1u32 , double_buffered : false , latching : false , min_size : 24u32 , fixed_size : :: std :: option :: Option :: Some (24u32)"), "{}", expansion);
+    assert!(expansion.contains("name : \"global\" , opcode : 0u16 , since : 1u32 , double_buffered : false , latching : false , min_size : 20u32 , fixed_size : :: std :: option :: Option :: None"), "{}", expansion);
+    assert!(expansion.contains("pub const LARGEST_FIXED_MESSAGE : u32 = 24u32"), "{}", expansion);
+}
+#[test]
+fn empty_protocol() {
+    let expansion = expand_protocol("tests/empty.toml", quote! {
+        mod placeholder {}
+    }).to_string();
+    assert!(!expansion.contains("compile_error"), "{}", expansion);
+    // Only the traits shared by every module are generated
+    assert_eq!(traits(expansion.parse().unwrap()), ["Construct", "BoundVersion"]);
+}
+#[test]
+fn interfaces_without_requests() {
+    let expansion = expand_protocol("tests/partial.toml", quote! {
+        mod partial {
+            type ExtNotifier = crate::Notifier;
+            type ExtConstants = crate::Constants;
+        }
+    }).to_string();
+    assert!(!expansion.contains("compile_error"), "{}", expansion);
+    let traits = traits(expansion.parse().unwrap());
+    assert!(traits.contains(&"ExtNotifier".to_string()) && traits.contains(&"ExtConstants".to_string()), "{:?}", traits);
+    // Dispatch has no arguments to read
+    assert!(!expansion.contains("let mut args"), "{}", expansion);
+    assert!(expansion.contains("fn notify"), "{}", expansion);
+    assert!(expansion.contains("pub const FAILED"), "{}", expansion);
+}
+#[test]
+fn identifier_collisions() {
+    let expansion = expand_protocol("tests/collisions.toml", quote! {
+        mod collisions {
+            type ExtFoo = crate::Foo;
+            type ExtFooBar = crate::FooBar;
+            #[interface = "ext_baz_2"]
+            type ExtBaz2 = crate::Baz;
+        }
+    }).to_string();
+    // Each collision is reported once, naming both protocol items
+    assert!(expansion.contains("Generated identifier `ExtFooBar` for enum \\\"ext_foo.bar\\\" collides with interface \\\"ext_foo_bar\\\""), "{}", expansion);
+    assert!(expansion.contains("Generated identifier `ON_OFF` for entry \\\"ext_foo.bar.onOff\\\" collides with entry \\\"ext_foo.bar.on_off\\\""), "{}", expansion);
+    assert_eq!(expansion.matches("compile_error").count(), 2, "{}", expansion);
+    // Interfaces sharing a trait name are reported when bound by it
+    let expansion = expand_protocol("tests/collisions.toml", quote! {
+        mod collisions {
+            type ExtBaz2 = crate::Baz;
+        }
+    }).to_string();
+    assert!(expansion.contains("ExtBaz2 is the CamelCase name of interfaces \\\"ext_baz_2\\\" and \\\"ext_baz2\\\""), "{}", expansion);
+}
+#[test]
+fn keywords() {
+    let expansion = expand_protocol("tests/keywords.toml", quote! {
+        mod keywords {
+            type ExtKeywords = crate::Keywords;
+        }
+    }).to_string();
+    assert!(!expansion.contains("compile_error"), "{}", expansion);
+    // Constants are shouty, so no entry name is a keyword once converted, while methods are raw where needed
+    for item in ["pub struct ExtKeywordsType", "pub const SELF : Self = Self (0u32)", "pub const CRATE : Self = Self (1u32)", "pub const LOOP : Self = Self (2u32)", "fn r#loop (& mut self"] {
+        assert!(expansion.contains(item), "{} is missing from {}", item, expansion);
+    }
+    let expansion = expand_protocol("tests/keywords.toml", quote! {
+        mod keywords {
+            type ExtUnusable = crate::Unusable;
+        }
+    }).to_string();
+    assert!(expansion.contains("Generated identifier `self` for request \\\"ext_unusable.self\\\" is a keyword that cannot be a raw identifier"), "{}", expansion);
+}
+#[test]
+fn runtime_apis_are_opt_in() {
+    // Each option with the flags using it and the APIs of the stand-in runtime that the generated code uses only under it
+    let options = [
+        ("send_all", quote!{}, &[". send_all ("][..]),
+        ("lease_version", quote!{}, &[". version ()"]),
+        ("destroyed_objects", quote!{}, &[". is_destroyed ("]),
+        ("wire_dumps", quote!{}, &[":: DEBUG_WIRE", ". to_bytes ("]),
+        ("message_size", quote!{}, &[". size ()"]),
+        ("flush", quote!{}, &[". flush ()"]),
+        ("disconnects", quote!{}, &[". is_disconnected ()", "Disconnected . into ()"]),
+        ("interior_nul", quote!{}, &["InteriorNul {"]),
+        ("create", quote!{}, &[". create (", "Implementation for"]),
+        ("malformed_strings", quote!{}, &["MalformedString {"]),
+        ("duplicate_objects", quote!{}, &["DuplicateObject {"]),
+        ("argument_limits", quote!{ #![max_arg_len = 4096] }, &[". peek_u32 ()", "ArgumentTooLong {"]),
+        ("argument_ranges", quote!{ type ExtRanged = crate::Ranged; }, &["ArgumentOutOfRange {"]),
+        ("short_messages", quote!{ type ExtExtended = crate::Extended; }, &["args . is_empty ()"]),
+        ("access_denied", quote!{ #![privileged(crate::is_privileged)] type ExtGuarded = crate::Guarded; }, &["AccessDenied {"]),
+        ("protocol_errors", quote!{}, &["DispatchError :: Protocol"]),
+        ("client_data", quote!{ type ExtShared = crate::Shared (context<crate::State>); }, &[". take_data ::", ". put_data ("]),
+        ("dead_clients", quote!{ #![skip_dead_clients] #![runtime(client_data)] }, &[". is_dead ()"])
+    ];
+    // The core protocol along with interfaces whose specification relies on the options
+    let expand = |module| expand_server_protocol(syn::parse2(quote!{ "tests/wayland.toml", "tests/runtime.toml" }).unwrap(), self::module(module));
+    let bindings = quote! {
+        type WlDisplay = crate::Display;
+        type WlRegistry = crate::Registry;
+        type WlRegion = crate::Region;
+        type WlCallback = crate::Callback;
+        type WlDataOffer = crate::DataOffer;
+        type WlSubcompositor = crate::Subcompositor;
+        type WlSubsurface = crate::Subsurface;
+        type WlSurface = crate::Surface;
+    };
+    let plain = expand(quote! {
+        mod plain {
+            #![loose_objects]
+            #bindings
+        }
+    }).to_string();
+    assert!(!plain.contains("compile_error"), "{}", plain);
+    for (option, flags, apis) in options {
+        let option = ident(option);
+        let expansion = expand(quote! {
+            mod opted {
+                #![loose_objects]
+                #![runtime(#option)]
+                #flags
+                #bindings
+            }
+        }).to_string();
+        for api in apis {
+            assert!(!plain.contains(api), "{} is used without runtime({})", api, option);
+            assert!(expansion.contains(api), "{} is unused under runtime({})", api, option);
+        }
+        if !flags.is_empty() {
+            // Flags relying on an option are errors rather than silently ignored without it
+            let undeclared = expand(quote! {
+                mod undeclared {
+                    #![loose_objects]
+                    #flags
+                    #bindings
+                }
+            }).to_string();
+            let requirement = format!("requires `#![runtime({})]`", option);
+            assert!(undeclared.contains(&requirement), "{}", undeclared);
+        }
+    }
+    // The events dropped for dead clients are counted in the client's data
+    let uncounted = expand(quote! {
+        mod uncounted {
+            #![loose_objects]
+            #![skip_dead_clients]
+            #![runtime(dead_clients)]
+            #bindings
+        }
+    }).to_string();
+    assert!(uncounted.contains("requires `#![runtime(client_data)]`"), "{}", uncounted);
+}
+#[test]
+fn receivers_are_rejected_only_by_arguments_of_their_interface() {
+    let expansion = expand(quote! {
+        mod wayland {
+            #![loose_objects]
+            #![runtime(duplicate_objects)]
+            type WlCallback = crate::Callback;
+            type WlSubcompositor = crate::Subcompositor;
+            type WlSubsurface = crate::Subsurface;
+            type WlSurface = crate::Surface;
+        }
+    }).to_string();
+    assert!(expansion.contains("DuplicateObject { request : \"wl_subcompositor.get_subsurface\""), "{}", expansion);
+    // A sibling surface cannot be the subsurface receiving the request, so leasing it is left to the runtime
+    assert!(!expansion.contains("DuplicateObject { request : \"wl_subsurface.place_above\""), "{}", expansion);
+}
+#[test]
+fn enum_mappings_convert_by_name() {
+    let expansion = expand_protocol("tests/mappings.toml", quote! {
+        mod mappings {
+            type ExtMapped = crate::Mapped;
+            type ExtMirror = crate::Mirror;
+        }
+    }).to_string();
+    // Every entry of `ext_mapped.state` has a counterpart, while `resizing` has none in the other direction
+    assert!(expansion.contains("impl :: std :: convert :: From < ExtMappedState > for ExtMirrorState"), "{}", expansion);
+    assert!(expansion.contains("impl :: std :: convert :: TryFrom < ExtMirrorState > for ExtMappedState"), "{}", expansion);
+    assert!(expansion.contains("ExtMappedState :: MAXIMIZED => ExtMirrorState :: MAXIMIZED"), "{}", expansion);
+    assert!(!expansion.contains("unreachable"), "{}", expansion);
+    assert!(expansion.contains("| `FULLSCREEN` | `FULLSCREEN` |"), "{}", expansion);
+    assert!(expansion.contains("| `RESIZING` | none, the conversion fails |"), "{}", expansion);
+    // A mapping from a generated enum to one the module does not generate
+    let unbound = expand_protocol("tests/mappings.toml", quote! {
+        mod mappings {
+            type ExtMapped = crate::Mapped;
+        }
+    }).to_string();
+    assert!(unbound.contains("Enum \\\"ext_mapped.state\\\" maps to \\\"ext_mirror.state\\\", which is not generated by this module"), "{}", unbound);
+}
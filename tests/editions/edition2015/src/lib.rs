@@ -0,0 +1,8 @@
+// Messages take as many arguments as the specification gives them, and the stand-in runtime is shared with the UI tests
+#![allow(clippy::too_many_arguments, clippy::manual_div_ceil)]
+extern crate wl_macro;
+
+#[path = "../../../support/runtime.rs"]
+mod runtime;
+#[path = "../../wayland.rs"]
+mod wayland_bindings;
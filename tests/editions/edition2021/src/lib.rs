@@ -0,0 +1,6 @@
+// Messages take as many arguments as the specification gives them, and the stand-in runtime is shared with the UI tests
+#![allow(clippy::too_many_arguments, clippy::manual_div_ceil)]
+#[path = "../../../support/runtime.rs"]
+mod runtime;
+#[path = "../../wayland.rs"]
+mod wayland_bindings;
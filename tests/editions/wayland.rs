@@ -0,0 +1,88 @@
+// The core protocol expanded with every binding and runtime option, shared by the crates pinned to each edition. Paths
+// are written as every edition reads them alike: `crate::` for items of the crate and `::` for other crates. Cargo
+// compiles workspace members from the workspace's root, which the specification's path is relative to
+use crate::runtime::server::Lease;
+
+macro_rules! implementations {
+    ($($interface:ident for $implementation:ident,)*) => {
+        $(
+            #[derive(Default)]
+            pub struct $implementation;
+            impl wayland::$interface for Lease<$implementation> {}
+        )*
+    };
+}
+implementations! {
+    WlDisplay for Display,
+    WlRegistry for Registry,
+    WlCallback for Callback,
+    WlCompositor for Compositor,
+    WlShmPool for ShmPool,
+    WlShm for Shm,
+    WlBuffer for Buffer,
+    WlDataOffer for DataOffer,
+    WlDataSource for DataSource,
+    WlDataDevice for DataDevice,
+    WlDataDeviceManager for DataDeviceManager,
+    WlShell for Shell,
+    WlShellSurface for ShellSurface,
+    WlSurface for Surface,
+    WlSeat for Seat,
+    WlPointer for Pointer,
+    WlKeyboard for Keyboard,
+    WlTouch for Touch,
+    WlOutput for Output,
+    WlRegion for Region,
+    WlSubcompositor for Subcompositor,
+    WlSubsurface for Subsurface,
+}
+
+#[::wl_macro::server_protocol("tests/wayland.toml", crate = crate::runtime)]
+pub mod wayland {
+    #![testing]
+    #![roundtrip_tests]
+    #![stats]
+    #![skip_dead_clients]
+    #![runtime(send_all, lease_version, destroyed_objects, wire_dumps, message_size, flush, disconnects, interior_nul)]
+    #![runtime(create, malformed_strings, duplicate_objects, argument_limits, argument_ranges, short_messages)]
+    #![runtime(access_denied, protocol_errors, client_data, dead_clients)]
+    type WlDisplay = crate::wayland_bindings::Display (default_error);
+    type WlRegistry = crate::wayland_bindings::Registry (default_error);
+    type WlCallback = crate::wayland_bindings::Callback (default_error);
+    type WlCompositor = crate::wayland_bindings::Compositor (default_error);
+    type WlShmPool = crate::wayland_bindings::ShmPool (default_error);
+    type WlShm = crate::wayland_bindings::Shm (default_error);
+    type WlBuffer = crate::wayland_bindings::Buffer (default_error);
+    type WlDataOffer = crate::wayland_bindings::DataOffer (default_error);
+    type WlDataSource = crate::wayland_bindings::DataSource (default_error);
+    type WlDataDevice = crate::wayland_bindings::DataDevice (default_error);
+    type WlDataDeviceManager = crate::wayland_bindings::DataDeviceManager (default_error);
+    type WlShell = crate::wayland_bindings::Shell (default_error);
+    type WlShellSurface = crate::wayland_bindings::ShellSurface (default_error);
+    type WlSurface = crate::wayland_bindings::Surface (default_error);
+    type WlSeat = crate::wayland_bindings::Seat (default_error);
+    type WlPointer = crate::wayland_bindings::Pointer (default_error);
+    type WlKeyboard = crate::wayland_bindings::Keyboard (default_error);
+    type WlTouch = crate::wayland_bindings::Touch (default_error);
+    type WlOutput = crate::wayland_bindings::Output (default_error);
+    type WlRegion = crate::wayland_bindings::Region (default_error);
+    type WlSubcompositor = crate::wayland_bindings::Subcompositor (default_error);
+    type WlSubsurface = crate::wayland_bindings::Subsurface (default_error);
+}
+
+#[test]
+fn dispatches() {
+    use crate::runtime::{Message, server::{Client, Dispatch}};
+    let mut client = Client::default();
+    client.insert(1, Display);
+    client.insert(3, Region);
+    let mut message = Message::new(3, 1);
+    for value in &[0, 0, 16, 16] {
+        message.push_i32(*value);
+    }
+    <Region as Dispatch>::dispatch(client.get_any(3).unwrap(), &mut client, message).unwrap();
+    let error = &client.sent[0];
+    assert_eq!((error.object, error.opcode, error.data[0], error.data[1]), (1, 0, 3, 1));
+    let dispatched = wayland::stats().requests.into_iter().find(|count| count.count > 0).unwrap();
+    assert_eq!((dispatched.interface, dispatched.name), ("wl_region", "add"));
+}
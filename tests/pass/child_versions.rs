@@ -0,0 +1,82 @@
+// Objects created by a request take the version of the object receiving it under `#![runtime(lease_version)]`, and the
+// version their binding supports otherwise
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use runtime::{Message, NewId, server::{Client, Dispatch, Lease, Result}};
+
+static CREATED: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Default)]
+pub struct Compositor;
+impl inherited::WlCompositor for Lease<Compositor> {
+    fn create_surface(&mut self, _: &mut Client, id: NewId) -> Result<()> {
+        CREATED.store(id.version, Ordering::Relaxed);
+        Ok(())
+    }
+    fn create_region(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct FixedCompositor;
+impl supported::WlCompositor for Lease<FixedCompositor> {
+    fn create_surface(&mut self, _: &mut Client, id: NewId) -> Result<()> {
+        CREATED.store(id.version, Ordering::Relaxed);
+        Ok(())
+    }
+    fn create_region(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Surface;
+impl inherited::WlSurface for Lease<Surface> {}
+#[derive(Default)]
+pub struct Region;
+impl inherited::WlRegion for Lease<Region> {}
+#[derive(Default)]
+pub struct Callback;
+#[derive(Default)]
+pub struct SupportedSurface;
+impl supported::WlSurface for Lease<SupportedSurface> {}
+#[derive(Default)]
+pub struct SupportedRegion;
+impl supported::WlRegion for Lease<SupportedRegion> {}
+#[derive(Default)]
+pub struct SupportedCallback;
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod inherited {
+    #![loose_objects]
+    #![runtime(lease_version)]
+    type WlCompositor = crate::Compositor;
+    type WlSurface = crate::Surface (default_error);
+    type WlRegion = crate::Region (default_error);
+    type WlCallback = crate::Callback;
+}
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod supported {
+    #![loose_objects]
+    type WlCompositor = crate::FixedCompositor;
+    type WlSurface = crate::SupportedSurface (default_error, version<4>);
+    type WlRegion = crate::SupportedRegion (default_error);
+    type WlCallback = crate::SupportedCallback;
+}
+
+fn create_surface<T: Dispatch + Default + 'static>(version: u32) -> u32 {
+    let mut client = Client::default();
+    client.insert(1, T::default());
+    client.versions.insert(1, version);
+    let mut message = Message::new(1, 0);
+    message.push_u32(2);
+    let lease = client.get_any(1).unwrap();
+    T::dispatch(lease, &mut client, message).unwrap();
+    CREATED.load(Ordering::Relaxed)
+}
+
+fn main() {
+    assert_eq!(create_surface::<Compositor>(2), 2);
+    assert_eq!(create_surface::<FixedCompositor>(2), 4);
+}
@@ -0,0 +1,62 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{Message, NewId, server::{Client, Dispatch, Lease, Result}};
+use wayland::{Construct, WlCallback, WlDisplay};
+
+#[derive(Default)]
+pub struct Display;
+impl WlDisplay for Lease<Display> {
+    fn sync(&mut self, client: &mut Client, wl_callback: NewId) -> Result<()> {
+        // Built with `Default` as the binding has no `init` function
+        let mut callback: Lease<Callback> = wayland::create_new(client, wl_callback)?;
+        callback.done(client, 7)
+    }
+    fn get_registry(&mut self, client: &mut Client, wl_registry: NewId) -> Result<()> {
+        let registry: Lease<Registry> = wayland::create_new(client, wl_registry)?;
+        assert_eq!(registry.created_as, wl_registry.id);
+        Ok(())
+    }
+}
+/// Not `Default`, so it is only constructed by its `init` function
+pub struct Registry {
+    created_as: u32
+}
+impl wayland::WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+pub fn make_registry(client: &mut Client, id: NewId) -> Registry {
+    client.flushes += 1;
+    Registry { created_as: id.id }
+}
+#[derive(Default)]
+pub struct Callback;
+impl WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(create)]
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry (init<crate::make_registry>);
+    type WlCallback = crate::Callback;
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert(1, Display);
+    let mut message = Message::new(1, 0);
+    message.push_u32(5);
+    <Display as Dispatch>::dispatch(client.get_any(1).unwrap(), &mut client, message).unwrap();
+    assert_eq!(client.sent.iter().map(|message| (message.object, message.opcode, message.data.clone())).collect::<Vec<_>>(), [(5, 0, vec![7])]);
+
+    client.insert(1, Display);
+    let mut message = Message::new(1, 1);
+    message.push_u32(6);
+    <Display as Dispatch>::dispatch(client.get_any(1).unwrap(), &mut client, message).unwrap();
+    // The init function was given the client and the new id
+    assert_eq!(client.flushes, 1);
+    let registry = Registry::construct(&mut client, NewId { id: 9, version: 1, interface: "wl_registry" });
+    assert_eq!((registry.created_as, client.flushes), (9, 2));
+}
@@ -0,0 +1,60 @@
+// Bindings with a context receive it from the client's data alongside bindings without one, and get it back for the
+// next request even when a handler fails
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{DispatchError, Message, server::{Client, Dispatch, Lease, Result}};
+
+#[derive(Default)]
+pub struct Compositor {
+    area: i32
+}
+#[derive(Default)]
+pub struct Region;
+impl wayland::WlRegion for Lease<Region> {
+    fn destroy(&mut self, _: &mut Client, _: &mut Compositor) -> Result<()> {
+        Ok(())
+    }
+    fn add(&mut self, _: &mut Client, compositor: &mut Compositor, _: i32, _: i32, width: i32, height: i32) -> Result<()> {
+        compositor.area += width * height;
+        Ok(())
+    }
+    fn subtract(&mut self, _: &mut Client, _: &mut Compositor, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        Err(DispatchError::Other("unsupported".into()))
+    }
+}
+#[derive(Default)]
+pub struct Buffer;
+impl wayland::WlBuffer for Lease<Buffer> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(client_data)]
+    type WlRegion = crate::Region (context<crate::Compositor>);
+    type WlBuffer = crate::Buffer;
+}
+
+fn rectangle(client: &mut Client, opcode: u16, size: i32) -> Result<()> {
+    client.insert(1, Region);
+    let mut message = Message::new(1, opcode);
+    for value in [0, 0, size, size] {
+        message.push_i32(value);
+    }
+    <Region as Dispatch>::dispatch(client.get_any(1).unwrap(), client, message)
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert_data(Compositor::default());
+    rectangle(&mut client, 1, 2).unwrap();
+    assert!(rectangle(&mut client, 2, 2).is_err());
+    rectangle(&mut client, 1, 3).unwrap();
+    assert_eq!(client.take_data::<Compositor>().unwrap().area, 13);
+    // Bindings without a context do not need one
+    client.insert(2, Buffer);
+    <Buffer as Dispatch>::dispatch(client.get_any(2).unwrap(), &mut client, Message::new(2, 0)).unwrap();
+}
@@ -0,0 +1,47 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{Message, NewId, server::{Client, Dispatch, Lease, Result}};
+use wayland::{WlCallback, WlDisplay};
+
+#[derive(Default)]
+pub struct Display;
+impl WlDisplay for Lease<Display> {
+    fn sync(&mut self, client: &mut Client, wl_callback: NewId) -> Result<()> {
+        // The new object is leased straight away, so the event is sent without looking it up
+        wayland::create(client, wl_callback, Callback)?.done(client, 7)
+    }
+    fn get_registry(&mut self, client: &mut Client, wl_registry: NewId) -> Result<()> {
+        wayland::create(client, wl_registry, Registry).map(drop)
+    }
+}
+#[derive(Default)]
+pub struct Registry;
+impl wayland::WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Callback;
+impl WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(create)]
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry;
+    type WlCallback = crate::Callback;
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert(1, Display);
+    let mut message = Message::new(1, 0);
+    message.push_u32(5);
+    <Display as Dispatch>::dispatch(client.get_any(1).unwrap(), &mut client, message).unwrap();
+    assert_eq!(client.sent.iter().map(|message| (message.object, message.opcode, message.data.clone())).collect::<Vec<_>>(), [(5, 0, vec![7])]);
+    // The lease takes the version the object was created with
+    let registry = wayland::create(&mut client, NewId { id: 6, version: 1, interface: "wl_registry" }, Registry).unwrap();
+    assert_eq!((registry.object, registry.version()), (6, 1));
+}
@@ -0,0 +1,80 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+use wayland::{EventBatch, WlOutput};
+
+#[derive(Default)]
+pub struct Output;
+impl WlOutput for Lease<Output> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct StrictOutput;
+impl strict::WlOutput for Lease<StrictOutput> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![skip_dead_clients]
+    #![runtime(lease_version, dead_clients, client_data)]
+    type WlOutput = crate::Output;
+}
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod strict {
+    type WlOutput = crate::StrictOutput;
+}
+
+fn main() {
+    if std::env::var_os("DEAD_CLIENTS_TRACE").is_some() {
+        let mut output = Lease { object: 3, version: 4, value: Box::new(Output) };
+        let mut client = Client::default();
+        client.mark_dead();
+        output.name(&mut client, "DP-1").unwrap();
+        output.done(&mut client).unwrap();
+        return
+    }
+    let mut output = Lease { object: 3, version: 4, value: Box::new(Output) };
+    let mut client = Client::default();
+    output.name(&mut client, "DP-1").unwrap();
+    assert_eq!(client.sent.len(), 1);
+    assert_eq!(wayland::suppressed_events(&mut client), 0);
+
+    // Once the client is marked dead, events are dropped without being sent
+    client.mark_dead();
+    output.name(&mut client, "DP-2").unwrap();
+    assert!(output.try_description(&mut client, "Built-in display").unwrap());
+    let mut batch = EventBatch::new();
+    output.scale_into(&mut batch, 2);
+    output.done_into(&mut batch);
+    batch.send(&mut client).unwrap();
+    assert_eq!(client.sent.len(), 1);
+    assert_eq!(wayland::suppressed_events(&mut client), 4);
+
+    // Broadcasts skip dead clients without reporting them as failures
+    let mut live = Client::default();
+    assert_eq!(wayland::broadcast_wl_output_done(vec![&mut client, &mut live], |_| Some(3)).unwrap(), 1);
+    assert_eq!((client.sent.len(), live.sent.len()), (1, 1));
+    assert_eq!(wayland::suppressed_events(&mut client), 5);
+
+    // Without the flag events are still sent
+    let mut output = Lease { object: 3, version: 4, value: Box::new(StrictOutput) };
+    strict::WlOutput::done(&mut output, &mut client).unwrap();
+    assert_eq!(client.sent.len(), 2);
+
+    // The events dropped are traced in a single line once the client is dropped
+    let traced = std::process::Command::new(std::env::current_exe().unwrap())
+        .env("DEAD_CLIENTS_TRACE", "1")
+        .env("WAYLAND_DEBUG", "1")
+        .output()
+        .unwrap();
+    assert!(traced.status.success());
+    let stderr = String::from_utf8(traced.stderr).unwrap();
+    assert_eq!(stderr.matches("client marked dead").count(), 1, "{}", stderr);
+    assert!(stderr.contains("client marked dead, suppressed 2 events"), "{}", stderr);
+}
@@ -0,0 +1,35 @@
+// The protocol configures its attributes by a feature this crate does not declare
+#![allow(unexpected_cfgs)]
+// A private module with no use of the `ext_note.state` enum must still build warning free
+#![deny(dead_code)]
+
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+
+#[derive(Default)]
+pub struct Audit;
+impl extension::ExtAudit for Lease<Audit> {
+    fn set_title(&mut self, _: &mut Client, _: String) -> Result<()> {
+        Ok(())
+    }
+    fn inspect(&mut self, _: &mut Client, _: Lease<dyn std::any::Any>) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Note;
+impl extension::ExtNote for Lease<Note> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/extension.toml", crate = crate::runtime)]
+mod extension {
+    type ExtAudit = crate::Audit;
+    type ExtNote = crate::Note;
+}
+
+fn main() {}
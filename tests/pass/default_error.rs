@@ -0,0 +1,86 @@
+// Requests left unimplemented under `default_error` post `wl_display.error` with the `invalid_method` code, through the
+// bound display where there is one
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{DispatchError, Message, NewId, server::{Client, Dispatch, Lease, Result}};
+
+#[derive(Default)]
+pub struct Display;
+impl bound::WlDisplay for Lease<Display> {
+    fn sync(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+    fn get_registry(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Registry;
+impl bound::WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Callback;
+impl bound::WlCallback for Lease<Callback> {}
+#[derive(Default)]
+pub struct Pointer;
+impl bound::WlPointer for Lease<Pointer> {}
+#[derive(Default)]
+pub struct UnboundPointer;
+impl unbound::WlPointer for Lease<UnboundPointer> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod bound {
+    #![loose_objects]
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry;
+    type WlCallback = crate::Callback;
+    type WlPointer = crate::Pointer (default_error);
+}
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod unbound {
+    #![loose_objects]
+    #![runtime(disconnects)]
+    type WlPointer = crate::UnboundPointer (default_error);
+}
+
+/// `wl_pointer.set_cursor` with a null surface
+fn set_cursor() -> Message {
+    let mut message = Message::new(3, 0);
+    message.push_u32(7);
+    message.push_u32(0);
+    message.push_i32(0);
+    message.push_i32(0);
+    message
+}
+/// The object, code and message of a `wl_display.error` event
+fn posted(message: &Message) -> (u32, u16, u32, u32, String) {
+    let mut args = message.args();
+    (message.object, message.opcode, args.next_u32().unwrap(), args.next_u32().unwrap(), args.next_str().unwrap())
+}
+
+fn main() {
+    let expected = (1, 0, 3, 1, "wl_pointer.set_cursor is not implemented".to_string());
+
+    // Posted through the display's `error` event
+    let mut client = Client::default();
+    client.insert(1, Display);
+    client.insert(3, Pointer);
+    <Pointer as Dispatch>::dispatch(client.get_any(3).unwrap(), &mut client, set_cursor()).unwrap();
+    assert_eq!(client.sent.iter().map(posted).collect::<Vec<_>>(), [expected.clone()]);
+    // The display is leased to post the error
+    assert!(!client.objects.contains_key(&1));
+
+    // Encoded as the display would without a binding, mapping failures as its event methods do
+    let mut client = Client::default();
+    client.insert(3, UnboundPointer);
+    <UnboundPointer as Dispatch>::dispatch(client.get_any(3).unwrap(), &mut client, set_cursor()).unwrap();
+    assert_eq!(client.sent.iter().map(posted).collect::<Vec<_>>(), [expected]);
+    client.hung_up = true;
+    client.insert(3, UnboundPointer);
+    let error = <UnboundPointer as Dispatch>::dispatch(client.get_any(3).unwrap(), &mut client, set_cursor()).unwrap_err();
+    assert!(matches!(error, DispatchError::Disconnected), "{:?}", error);
+}
@@ -0,0 +1,122 @@
+// Under `delegate(path)` requests reach bindings held in an arena the runtime knows nothing of, and the display helpers
+// borrow the display through the same function
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+use runtime::{Message, NewId, Object, server::{Client, Result}};
+
+thread_local! {
+    static ARENA: RefCell<HashMap<u32, Rc<dyn Any>>> = RefCell::new(HashMap::new());
+    /// The objects resolved, in order
+    static RESOLVED: RefCell<Vec<u32>> = RefCell::new(Vec::new());
+}
+/// A receiver sharing its binding with the arena rather than leasing it from the client
+pub struct Slot<T> {
+    object: u32,
+    value: Rc<RefCell<T>>
+}
+impl<T> Object for Slot<T> {
+    fn object(&self) -> u32 {
+        self.object
+    }
+}
+fn store<T: Any>(object: u32, value: T) {
+    ARENA.with(|arena| arena.borrow_mut().insert(object, Rc::new(RefCell::new(value))));
+}
+pub fn resolve<T: Any>(_: &mut Client, object: u32) -> Result<Slot<T>> {
+    RESOLVED.with(|resolved| resolved.borrow_mut().push(object));
+    let value = ARENA.with(|arena| arena.borrow().get(&object).cloned())
+        .ok_or(runtime::DispatchError::Other("no object".into()))?
+        .downcast::<RefCell<T>>()
+        .map_err(|_| runtime::DispatchError::Other("wrong type".into()))?;
+    Ok(Slot { object, value })
+}
+
+#[derive(Default)]
+pub struct Display {
+    callbacks: Vec<NewId>
+}
+impl wayland::WlDisplay for Slot<Display> {
+    fn sync(&mut self, _: &mut Client, callback: NewId) -> Result<()> {
+        self.value.borrow_mut().callbacks.push(callback);
+        Ok(())
+    }
+    fn get_registry(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+pub struct Registry;
+impl wayland::WlRegistry for Slot<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+pub struct Callback;
+impl wayland::WlCallback for Slot<Callback> {}
+#[derive(Default)]
+pub struct Region {
+    added: Vec<(i32, i32, i32, i32)>
+}
+impl wayland::WlRegion for Slot<Region> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+    fn add(&mut self, _: &mut Client, x: i32, y: i32, width: i32, height: i32) -> Result<()> {
+        self.value.borrow_mut().added.push((x, y, width, height));
+        Ok(())
+    }
+    fn subtract(&mut self, _: &mut Client, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    #![delegate(crate::resolve)]
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry;
+    type WlCallback = crate::Callback;
+    type WlRegion = crate::Region;
+}
+
+fn resolved() -> Vec<u32> {
+    RESOLVED.with(|resolved| resolved.borrow_mut().drain(..).collect())
+}
+
+fn main() {
+    let mut client = Client::default();
+    store(1, Display::default());
+    store(3, Region::default());
+
+    let mut add = Message::new(3, 1);
+    for value in [1, 2, 3, 4] {
+        add.push_i32(value);
+    }
+    wayland::dispatch_wl_region(&mut client, 3, add).unwrap();
+    assert_eq!(resolved(), [3]);
+    let region = resolve::<Region>(&mut client, 3).unwrap();
+    assert_eq!(region.value.borrow().added, [(1, 2, 3, 4)]);
+    // The arena keeps the binding, which was never leased from the client
+    assert!(client.objects.is_empty());
+    resolved();
+
+    // New objects are created at the version their binding supports without a `Dispatch` impl to read it from
+    let mut sync = Message::new(1, 0);
+    sync.push_u32(5);
+    wayland::dispatch_wl_display(&mut client, 1, sync).unwrap();
+    let display = resolve::<Display>(&mut client, 1).unwrap();
+    assert_eq!(display.value.borrow().callbacks, [NewId { id: 5, version: 1, interface: "wl_callback" }]);
+    resolved();
+
+    // The display posting errors and recycling ids is borrowed through the same function
+    wayland::post_error(&mut client, &region, 0u32, "bad region").unwrap();
+    wayland::delete_id(&mut client, 3).unwrap();
+    assert_eq!(resolved(), [1, 1]);
+    let sent = client.sent.iter().map(|message| (message.object, message.opcode)).collect::<Vec<_>>();
+    assert_eq!(sent, [(1, 0), (1, 1)]);
+    let mut error = client.sent[0].args();
+    assert_eq!((error.next_u32().unwrap(), error.next_u32().unwrap(), error.next_str().unwrap().as_str()), (3, 0, "bad region"));
+    assert_eq!(client.sent[1].args().next_u32().unwrap(), 3);
+}
@@ -0,0 +1,68 @@
+// Requests sent to an object after its destructor are ignored under `#![runtime(destroyed_objects)]`
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use runtime::{NewId, server::{Client, Lease, Result}};
+use wayland::{mock::wl_region, WlCallback, WlDisplay, WlRegion, WlRegistry};
+
+static ADDED: AtomicUsize = AtomicUsize::new(0);
+
+pub struct Display;
+impl WlDisplay for Lease<Display> {
+    fn sync(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+    fn get_registry(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+pub struct Registry;
+impl WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+pub struct Callback;
+impl WlCallback for Lease<Callback> {}
+pub struct Region;
+impl WlRegion for Lease<Region> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+    fn add(&mut self, _: &mut Client, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        ADDED.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn subtract(&mut self, _: &mut Client, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime, only = ["wl_display", "wl_registry", "wl_callback", "wl_region"])]
+mod wayland {
+    #![testing]
+    #![runtime(destroyed_objects)]
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry;
+    type WlCallback = crate::Callback;
+    type WlRegion = crate::Region;
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert(1, Display);
+    client.insert(5, Region);
+    wl_region::call_destroy(&mut client, 5).unwrap();
+    assert!(client.is_destroyed(5));
+    // `delete_id` was sent
+    assert_eq!(client.sent.iter().map(|message| (message.object, message.opcode)).collect::<Vec<_>>(), [(1, 1)]);
+    // A runtime keeping the object until the client acknowledges `delete_id` still dispatches to it
+    client.objects.insert(5, Box::new(Region));
+    wl_region::call_add(&mut client, 5, 0, 0, 1, 1).unwrap();
+    assert_eq!(ADDED.load(Ordering::Relaxed), 0);
+    // Until the id is reused
+    client.insert(5, Region);
+    wl_region::call_add(&mut client, 5, 0, 0, 1, 1).unwrap();
+    assert_eq!(ADDED.load(Ordering::Relaxed), 1);
+}
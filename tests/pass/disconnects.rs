@@ -0,0 +1,37 @@
+// Under `#![runtime(disconnects)]` events failing to send to a client that has hung up are told apart from other
+// failures, and broadcasts skip the client
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{DispatchError, Fixed, server::{Client, Lease}};
+use wayland::{EventBatch, WlPointer};
+
+#[derive(Default)]
+pub struct Pointer;
+impl WlPointer for Lease<Pointer> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    #![runtime(disconnects)]
+    type WlPointer = crate::Pointer (default_error);
+}
+
+fn main() {
+    let mut pointer = Lease { object: 3, version: 7, value: Box::new(Pointer) };
+    let mut client = Client::default();
+    pointer.motion(&mut client, 1, Fixed(0), Fixed(0)).unwrap();
+    client.hung_up = true;
+    assert!(matches!(pointer.motion(&mut client, 2, Fixed(0), Fixed(0)), Err(DispatchError::Disconnected)));
+    assert!(matches!(pointer.begin_frame(&mut client).finish(), Err(DispatchError::Disconnected)));
+    let mut batch = EventBatch::new();
+    pointer.motion_into(&mut batch, 3, Fixed(0), Fixed(0));
+    assert!(matches!(batch.send(&mut client), Err(DispatchError::Disconnected)));
+    // Clients that have hung up are skipped rather than reported as failures
+    let mut clients: Vec<Client> = (0..3).map(|_| Client::default()).collect();
+    clients[1].hung_up = true;
+    let sent = wayland::broadcast_wl_pointer_motion(clients.iter_mut(), |_| Some(3), 4, Fixed(256), Fixed(512)).unwrap();
+    assert_eq!(sent, 2);
+    assert!(clients[1].sent.is_empty());
+    assert_eq!(clients[2].sent[0].object, 3);
+}
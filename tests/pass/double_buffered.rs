@@ -0,0 +1,33 @@
+// Requests setting double-buffered state are known from the metadata module, in constant contexts as well
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::Lease;
+
+#[derive(Default)]
+pub struct Surface;
+impl wayland::WlSurface for Lease<Surface> {}
+#[derive(Default)]
+pub struct Callback;
+impl wayland::WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    type WlSurface = crate::Surface (default_error);
+    type WlCallback = crate::Callback;
+}
+
+/// `wl_surface.attach` is annotated `double_buffered = true`
+const ATTACH_IS_DOUBLE_BUFFERED: bool = wayland::wl_surface::is_double_buffered(1);
+
+fn main() {
+    assert!(ATTACH_IS_DOUBLE_BUFFERED);
+    // `commit` latches the pending state rather than setting it, and opcodes past the last request are not requests
+    for opcode in [0, 6, 7, u16::MAX] {
+        assert!(!wayland::wl_surface::is_double_buffered(opcode), "opcode {}", opcode);
+    }
+    let requests = wayland::wl_surface::REQUESTS;
+    assert_eq!(requests.iter().filter(|request| request.double_buffered).map(|request| request.name).collect::<Vec<_>>(), ["attach"]);
+    assert_eq!(requests.iter().filter(|request| request.latching).map(|request| request.name).collect::<Vec<_>>(), ["commit"]);
+}
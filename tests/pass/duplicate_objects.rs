@@ -0,0 +1,63 @@
+// A request naming one object in two arguments is rejected before either is leased
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use runtime::{DispatchError, Message, NewId, server::{Client, Dispatch, Lease, Result}};
+use wayland::{WlSubcompositor, WlSubsurface, WlSurface};
+
+static SURFACES: [AtomicU32; 2] = [AtomicU32::new(0), AtomicU32::new(0)];
+
+#[derive(Default)]
+pub struct Subcompositor;
+impl WlSubcompositor for Lease<Subcompositor> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+    fn get_subsurface(&mut self, _: &mut Client, _: NewId, surface: Lease<Surface>, parent: Lease<Surface>) -> Result<()> {
+        SURFACES[0].store(surface.object, Ordering::Relaxed);
+        SURFACES[1].store(parent.object, Ordering::Relaxed);
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Surface;
+impl WlSurface for Lease<Surface> {}
+#[derive(Default)]
+pub struct Subsurface;
+impl WlSubsurface for Lease<Subsurface> {}
+#[derive(Default)]
+pub struct Callback;
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    #![runtime(duplicate_objects)]
+    type WlSubcompositor = crate::Subcompositor;
+    type WlSubsurface = crate::Subsurface (default_error);
+    type WlSurface = crate::Surface (default_error);
+    type WlCallback = crate::Callback;
+}
+
+fn get_subsurface(client: &mut Client, surface: u32, parent: u32) -> Result<()> {
+    client.insert(1, Subcompositor);
+    let mut message = Message::new(1, 1);
+    message.push_u32(4);
+    message.push_u32(surface);
+    message.push_u32(parent);
+    let lease = client.get_any(1).unwrap();
+    <Subcompositor as Dispatch>::dispatch(lease, client, message)
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert(2, Surface);
+    client.insert(3, Surface);
+    match get_subsurface(&mut client, 2, 2) {
+        Err(DispatchError::DuplicateObject { request, object }) => assert_eq!((request, object), ("wl_subcompositor.get_subsurface", 2)),
+        result => panic!("expected a duplicate object error, not {:?}", result)
+    }
+    // Neither surface was leased, so both are still with the client
+    get_subsurface(&mut client, 2, 3).unwrap();
+    assert_eq!([SURFACES[0].load(Ordering::Relaxed), SURFACES[1].load(Ordering::Relaxed)], [2, 3]);
+}
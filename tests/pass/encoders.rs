@@ -0,0 +1,48 @@
+// Messages built by the client-free encoders are the messages the generated methods send and dispatch
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use runtime::{Message, server::{Client, Dispatch, Lease, Result}};
+use wayland::{wl_output, WlOutput};
+
+static RELEASED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+pub struct Output;
+impl WlOutput for Lease<Output> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        RELEASED.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlOutput = crate::Output;
+}
+
+fn main() {
+    let mut client = Client::default();
+    let mut output = Lease { object: 3, version: 4, value: Box::new(Output) };
+    output.geometry(&mut client, 0, 0, 600, 340, 1, "make", "model name", 0).unwrap();
+    output.mode(&mut client, 3, 1920, 1080, 60000).unwrap();
+    output.name(&mut client, "DP-1").unwrap();
+    output.done(&mut client).unwrap();
+    let encoded = [
+        wl_output::encode_geometry(3, 0, 0, 600, 340, 1, "make", "model name", 0),
+        wl_output::encode_mode(3, 3, 1920, 1080, 60000),
+        wl_output::encode_name(3, "DP-1"),
+        wl_output::encode_done(3)
+    ];
+    assert_eq!(client.sent.len(), encoded.len());
+    for (sent, encoded) in client.sent.iter().zip(&encoded) {
+        assert_eq!(sent.to_bytes(), encoded.to_bytes());
+    }
+    // Encoded requests dispatch like those received from a client
+    let message: Message = wl_output::encode_release(3);
+    client.insert(3, Output);
+    let lease = client.get_any(3).unwrap();
+    <Output as Dispatch>::dispatch(lease, &mut client, message).unwrap();
+    assert!(RELEASED.load(Ordering::Relaxed));
+}
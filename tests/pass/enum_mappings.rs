@@ -0,0 +1,30 @@
+// Enums annotated with `maps_to` convert to each other by entry name: infallibly where every entry has a counterpart
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::convert::TryFrom;
+use runtime::server::Lease;
+use mappings::{ExtMappedState, ExtMirrorState};
+
+#[derive(Default)]
+pub struct Mapped;
+impl mappings::ExtMapped for Lease<Mapped> {}
+#[derive(Default)]
+pub struct Mirror;
+impl mappings::ExtMirror for Lease<Mirror> {}
+
+#[wl_macro::server_protocol("../../../../tests/mappings.toml", crate = crate::runtime)]
+mod mappings {
+    type ExtMapped = crate::Mapped;
+    type ExtMirror = crate::Mirror;
+}
+
+fn main() {
+    // Entries are mapped by name rather than by value
+    assert_eq!(ExtMirrorState::from(ExtMappedState::ACTIVATED), ExtMirrorState::ACTIVATED);
+    assert_eq!(ExtMirrorState::from(ExtMappedState::FULLSCREEN), ExtMirrorState::FULLSCREEN);
+    assert_eq!(*ExtMirrorState::from(ExtMappedState::new(2).unwrap()), 3);
+    // The mirror has an entry without a counterpart, so converting back is fallible
+    assert_eq!(ExtMappedState::try_from(ExtMirrorState::MAXIMIZED), Ok(ExtMappedState::MAXIMIZED));
+    assert_eq!(ExtMappedState::try_from(ExtMirrorState::RESIZING), Err(ExtMirrorState::RESIZING));
+}
@@ -0,0 +1,41 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+use wayland::{raw, WlOutputTransform};
+
+#[derive(Default)]
+pub struct Output;
+impl wayland::WlOutput for Lease<Output> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlOutput = crate::Output;
+}
+
+/// Entries are constants of the enum, so they match a converted value
+fn rotation(transform: u32) -> Option<u32> {
+    match WlOutputTransform::new(transform).ok()? {
+        WlOutputTransform::NORMAL | WlOutputTransform::FLIPPED => Some(0),
+        WlOutputTransform::TRANSFORM_90 | WlOutputTransform::FLIPPED_90 => Some(90),
+        WlOutputTransform::TRANSFORM_180 | WlOutputTransform::FLIPPED_180 => Some(180),
+        _ => Some(270)
+    }
+}
+/// The raw constants match the integer as it was received
+fn is_flipped(transform: u32) -> bool {
+    matches!(transform, raw::wl_output_transform::FLIPPED..=raw::wl_output_transform::FLIPPED_270)
+}
+
+fn main() {
+    assert_eq!(rotation(1), Some(90));
+    assert_eq!(rotation(7), Some(270));
+    assert_eq!(rotation(8), None);
+    assert!(is_flipped(6) && !is_flipped(3));
+    assert_eq!(raw::wl_output_transform::TRANSFORM_180, *WlOutputTransform::TRANSFORM_180);
+    assert_eq!(WlOutputTransform::default(), WlOutputTransform::NORMAL);
+}
@@ -0,0 +1,45 @@
+// Events collected in a batch or frame are written at once with the runtime's `send_all`, while single events are sent
+// as they are
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{Fixed, server::{Client, Lease}};
+use wayland::{EventBatch, WlPointer};
+
+#[derive(Default)]
+pub struct Pointer;
+impl WlPointer for Lease<Pointer> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    #![runtime(send_all)]
+    type WlPointer = crate::Pointer (default_error);
+}
+
+fn main() {
+    let surface = Lease { object: 4, version: 1, value: Box::new(()) };
+    let mut pointer = Lease { object: 3, version: 7, value: Box::new(Pointer) };
+    // A frame of four events is a single write
+    let mut client = Client::default();
+    let mut frame = pointer.begin_frame(&mut client);
+    frame.enter(1, &surface, Fixed(0), Fixed(0))
+        .motion(2, Fixed(256), Fixed(512))
+        .button(3, 3, 0x110, 1);
+    frame.finish().unwrap();
+    assert_eq!((client.writes, client.sent.len()), (1, 4));
+    assert_eq!(client.sent.iter().map(|message| message.opcode).collect::<Vec<_>>(), [0, 2, 3, 5]);
+    // As are events batched by hand
+    let mut client = Client::default();
+    let mut batch = EventBatch::new();
+    pointer.motion_into(&mut batch, 4, Fixed(0), Fixed(0));
+    pointer.axis_into(&mut batch, 5, 0, Fixed(2560));
+    assert_eq!(batch.len(), 2);
+    batch.send(&mut client).unwrap();
+    assert_eq!((client.writes, client.sent.len()), (1, 2));
+    // Single events are each sent with `Client::send`
+    let mut client = Client::default();
+    pointer.motion(&mut client, 6, Fixed(0), Fixed(0)).unwrap();
+    pointer.motion(&mut client, 7, Fixed(0), Fixed(0)).unwrap();
+    assert_eq!((client.writes, client.sent.len()), (2, 2));
+}
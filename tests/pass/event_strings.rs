@@ -0,0 +1,87 @@
+#![allow(unexpected_cfgs)]
+
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{DispatchError, Message, server::{Client, Dispatch, Lease, Result}};
+use wayland::{EventBatch, WlOutput};
+
+#[derive(Default)]
+pub struct Output;
+impl WlOutput for Lease<Output> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct UncheckedOutput;
+impl unchecked::WlOutput for Lease<UncheckedOutput> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Audit;
+impl extension::ExtAudit for Lease<Audit> {
+    fn set_title(&mut self, _: &mut Client, _: String) -> Result<()> {
+        Ok(())
+    }
+    fn inspect(&mut self, _: &mut Client, _: Lease<dyn std::any::Any>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(interior_nul)]
+    type WlOutput = crate::Output;
+}
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod unchecked {
+    #![unchecked_strings]
+    #![runtime(interior_nul)]
+    type WlOutput = crate::UncheckedOutput;
+}
+#[wl_macro::server_protocol("../../../../tests/extension.toml", crate = crate::runtime)]
+mod extension {
+    #![runtime(interior_nul, malformed_strings)]
+    type ExtAudit = crate::Audit;
+}
+
+fn output() -> Lease<Output> {
+    Lease { object: 3, version: 4, value: Box::new(Output) }
+}
+
+fn main() {
+    // A nul would end the string early, so the event is rejected before it is written
+    let mut client = Client::default();
+    match WlOutput::name(&mut output(), &mut client, "DP\0-1") {
+        Err(DispatchError::InteriorNul { event: "wl_output.name", argument: "name" }) => (),
+        result => panic!("{:?}", result)
+    }
+    assert!(client.sent.is_empty());
+    // One rejected event fails the whole batch
+    let mut batch = EventBatch::new();
+    let mut output = output();
+    output.name_into(&mut batch, "DP-1");
+    output.description_into(&mut batch, "Built-in\0display");
+    output.done_into(&mut batch);
+    assert_eq!(batch.len(), 2);
+    assert!(matches!(batch.send(&mut client), Err(DispatchError::InteriorNul { event: "wl_output.description", argument: "description" })));
+    assert!(client.sent.is_empty());
+    // Unless the check is turned off
+    let mut output = Lease { object: 3, version: 4, value: Box::new(UncheckedOutput) };
+    unchecked::WlOutput::name(&mut output, &mut client, "DP\0-1").unwrap();
+    assert_eq!(client.sent.len(), 1);
+
+    // A request whose string is not UTF-8 is rejected naming the argument
+    let mut client = Client::default();
+    client.insert(1, Audit);
+    let mut message = Message::new(1, 0);
+    message.push_u32(3);
+    message.push_u32(u32::from_ne_bytes([b'a', 0xff, 0, 0]));
+    match <Audit as Dispatch>::dispatch(client.get_any(1).unwrap(), &mut client, message) {
+        Err(DispatchError::MalformedString { request: "ext_audit.set_title", argument: "title", .. }) => (),
+        result => panic!("{:?}", result)
+    }
+}
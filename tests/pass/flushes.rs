@@ -0,0 +1,45 @@
+// Under `#![runtime(flush)]` the client is flushed after exactly the events ending an exchange
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+use flush::ExtProgress;
+use wayland::WlCallback;
+
+#[derive(Default)]
+pub struct Progress;
+impl ExtProgress for Lease<Progress> {
+    fn cancel(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Callback;
+impl WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/flush.toml", crate = crate::runtime)]
+mod flush {
+    #![runtime(flush)]
+    type ExtProgress = crate::Progress;
+}
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(flush)]
+    type WlCallback = crate::Callback;
+}
+
+fn main() {
+    let mut progress = Lease { object: 3, version: 1, value: Box::new(Progress) };
+    let mut client = Client::default();
+    // Events flush only when annotated with `flush = true`, and `done` not when annotated `flush = false`
+    progress.step(&mut client).unwrap();
+    assert_eq!(client.flushes, 0);
+    progress.failed(&mut client).unwrap();
+    assert_eq!(client.flushes, 1);
+    progress.done(&mut client).unwrap();
+    assert_eq!((client.flushes, client.sent.len()), (1, 3));
+    // Every event of an interface without requests flushes by default
+    let mut callback = Lease { object: 4, version: 1, value: Box::new(Callback) };
+    callback.done(&mut client, 16).unwrap();
+    assert_eq!((client.flushes, client.sent.len()), (2, 4));
+}
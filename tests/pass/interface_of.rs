@@ -0,0 +1,56 @@
+#![allow(unexpected_cfgs)]
+
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{Object, server::{Client, Lease, Result}};
+use std::{any::Any, cell::RefCell};
+
+thread_local! {
+    static INSPECTED: RefCell<Vec<Option<&'static str>>> = RefCell::new(Vec::new());
+}
+
+#[derive(Default)]
+pub struct Audit;
+impl extension::ExtAudit for Lease<Audit> {
+    fn set_title(&mut self, _: &mut Client, _: String) -> Result<()> {
+        Ok(())
+    }
+    fn inspect(&mut self, _: &mut Client, target: Lease<dyn Any>) -> Result<()> {
+        INSPECTED.with(|inspected| inspected.borrow_mut().push(extension::interface_of(&target)));
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Note;
+impl extension::ExtNote for Lease<Note> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/extension.toml", crate = crate::runtime)]
+mod extension {
+    #![testing]
+    type ExtAudit = crate::Audit;
+    type ExtNote = crate::Note;
+}
+
+struct Id(u32);
+impl Object for Id {
+    fn object(&self) -> u32 {
+        self.0
+    }
+}
+
+fn main() {
+    // Objects of both bound types, and one of a type no binding implements, passed through the untyped argument
+    let targets: [Box<dyn Fn() -> Box<dyn Any>>; 3] = [Box::new(|| Box::new(Audit)), Box::new(|| Box::new(Note)), Box::new(|| Box::new(0u32))];
+    let mut client = Client::default();
+    for target in targets {
+        client.insert(1, Audit);
+        client.objects.insert(2, target());
+        extension::mock::ext_audit::call_inspect(&mut client, 1, &Id(2)).unwrap();
+    }
+    INSPECTED.with(|inspected| assert_eq!(*inspected.borrow(), [Some("ext_audit"), Some("ext_note"), None]));
+}
@@ -0,0 +1,35 @@
+// Protocol items named after keywords generate raw identifiers where the item keeps its name
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use runtime::{Message, server::{Client, Dispatch, Lease, Result}};
+use keywords::{ExtKeywords, ExtKeywordsType};
+
+static LOOPED: AtomicU32 = AtomicU32::new(u32::MAX);
+
+#[derive(Default)]
+pub struct Keywords;
+impl ExtKeywords for Lease<Keywords> {
+    fn r#loop(&mut self, _: &mut Client, r#type: u32) -> Result<()> {
+        LOOPED.store(r#type, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/keywords.toml", crate = crate::runtime)]
+mod keywords {
+    type ExtKeywords = crate::Keywords;
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert(1, Keywords);
+    let mut message = Message::new(1, 0);
+    message.push_u32(*ExtKeywordsType::CRATE);
+    let lease = client.get_any(1).unwrap();
+    <Keywords as Dispatch>::dispatch(lease, &mut client, message).unwrap();
+    assert_eq!(LOOPED.load(Ordering::Relaxed), 1);
+    assert_eq!(ExtKeywordsType::new(2).unwrap(), ExtKeywordsType::LOOP);
+    assert!(ExtKeywordsType::SELF != ExtKeywordsType::LOOP);
+}
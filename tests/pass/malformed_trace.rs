@@ -0,0 +1,61 @@
+// Requests that fail to decode are traced up to the malformed argument, while arguments decoded but then rejected are
+// returned without the trace
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{DispatchError, Message, server::{Client, Dispatch, Lease, Result}};
+
+#[derive(Default)]
+pub struct Region;
+impl wayland::WlRegion for Lease<Region> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+    fn add(&mut self, _: &mut Client, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        Ok(())
+    }
+    fn subtract(&mut self, _: &mut Client, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct ShellSurface;
+impl wayland::WlShellSurface for Lease<ShellSurface> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    #![max_arg_len = 4]
+    #![runtime(argument_limits)]
+    type WlRegion = crate::Region;
+    type WlShellSurface = crate::ShellSurface (default_error);
+}
+
+fn main() {
+    if std::env::var_os("MALFORMED_TRACE").is_some() {
+        let mut client = Client::default();
+        // `wl_region.add` cut off after its second argument
+        client.insert(3, Region);
+        let mut add = Message::new(3, 1);
+        add.push_i32(1);
+        add.push_i32(2);
+        assert!(<Region as Dispatch>::dispatch(client.get_any(3).unwrap(), &mut client, add).is_err());
+        // `wl_shell_surface.set_title` with a title over the limit
+        client.insert(4, ShellSurface);
+        let mut set_title = Message::new(4, 8);
+        set_title.push_str("too long");
+        match <ShellSurface as Dispatch>::dispatch(client.get_any(4).unwrap(), &mut client, set_title) {
+            Err(DispatchError::ArgumentTooLong { request, limit, .. }) => assert_eq!((request, limit), ("wl_shell_surface.set_title", 4)),
+            result => panic!("expected an argument too long error, not {:?}", result.map(|_| ()))
+        }
+        return
+    }
+    let traced = std::process::Command::new(std::env::current_exe().unwrap())
+        .env("MALFORMED_TRACE", "1")
+        .env("WAYLAND_DEBUG", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(traced.stderr).unwrap();
+    assert!(traced.status.success(), "{}", stderr);
+    assert_eq!(stderr.lines().collect::<Vec<_>>(), ["wl_region@3.add(1, 2, <malformed width>) [opcode 1]"]);
+}
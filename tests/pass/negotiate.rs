@@ -0,0 +1,34 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{server::{Client, Dispatch, Lease, Result}};
+
+#[derive(Default)]
+pub struct Output;
+impl wayland::WlOutput for Lease<Output> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Callback;
+impl wayland::WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlOutput = crate::Output (version<3>);
+    type WlCallback = crate::Callback;
+}
+
+fn main() {
+    // Lower than, equal to and higher than the declared version
+    assert_eq!(wayland::negotiate("wl_output", 2), Some(2));
+    assert_eq!(wayland::negotiate("wl_output", 3), Some(3));
+    assert_eq!(wayland::negotiate("wl_output", 4), Some(3));
+    // Bindings without a declared version support the specification's version
+    assert_eq!(wayland::negotiate("wl_callback", 2), Some(1));
+    assert_eq!(wayland::negotiate("wl_surface", 1), None);
+    assert_eq!(wayland::negotiate("wl_output ", 1), None);
+    assert_eq!(<Output as Dispatch>::VERSION, 3);
+    assert_eq!(wayland::IMPLEMENTATIONS.iter().find(|implementation| implementation.interface == "wl_output").unwrap().version, 3);
+}
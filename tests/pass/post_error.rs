@@ -0,0 +1,45 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{NewId, Object, server::{Client, Lease, Result}};
+use wayland::{WlDisplay, WlDisplayError};
+
+#[derive(Default)]
+pub struct Display;
+impl WlDisplay for Lease<Display> {
+    fn sync(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+    fn get_registry(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Registry;
+impl wayland::WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Callback;
+impl wayland::WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry;
+    type WlCallback = crate::Callback;
+}
+
+fn main() {
+    // Objects of any type are passed by the same function, as `&dyn Object`
+    let registry = Lease { object: 2, version: 1, value: Box::new(Registry) };
+    let callback: &dyn Object = &Lease { object: 3, version: 1, value: Box::new(Callback) };
+    let mut client = Client::default();
+    for object in [&registry as &dyn Object, callback] {
+        client.insert(1, Display);
+        wayland::post_error(&mut client, object, WlDisplayError::INVALID_METHOD, "unknown opcode").unwrap();
+    }
+    assert_eq!(client.sent.iter().map(|message| message.data[0]).collect::<Vec<_>>(), [2, 3]);
+}
@@ -0,0 +1,82 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::fs::File;
+use runtime::{DispatchError, Message, NewId, server::{Client, Dispatch, Lease, Result}};
+use capture::{ExtCapture, ExtCaptureManager};
+
+/// Marks a client as privileged in its data
+pub struct Privileged;
+pub fn is_privileged(client: &Client) -> bool {
+    client.data.contains_key(&std::any::TypeId::of::<Privileged>())
+}
+
+#[derive(Default)]
+pub struct Manager;
+impl ExtCaptureManager for Lease<Manager> {
+    fn capture(&mut self, client: &mut Client, id: NewId) -> Result<()> {
+        client.insert(id.id, Capture);
+        Ok(())
+    }
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Capture;
+impl ExtCapture for Lease<Capture> {
+    fn copy(&mut self, _: &mut Client, _: File) -> Result<()> {
+        Ok(())
+    }
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/privileged.toml", crate = crate::runtime)]
+mod capture {
+    #![privileged(crate::is_privileged)]
+    #![runtime(access_denied)]
+    type ExtCaptureManager = crate::Manager;
+    type ExtCapture = crate::Capture;
+}
+
+fn dispatch<T: Dispatch>(client: &mut Client, message: Message) -> Result<()> {
+    let lease = client.get_any(message.object).unwrap();
+    T::dispatch(lease, client, message)
+}
+
+fn main() {
+    let mut client = Client::default();
+    client.insert(1, Manager);
+    // A privileged request is denied to other clients without calling the handler
+    let mut capture = Message::new(1, 0);
+    capture.push_u32(2);
+    match dispatch::<Manager>(&mut client, capture) {
+        Err(DispatchError::AccessDenied { request: "ext_capture_manager.capture", object: 1 }) => (),
+        result => panic!("{:?}", result)
+    }
+    assert!(!client.objects.contains_key(&2));
+    // while the manager's other requests are not privileged
+    client.insert(1, Manager);
+    dispatch::<Manager>(&mut client, Message::new(1, 1)).unwrap();
+
+    // Every request of a privileged interface is denied, before its file descriptor is taken from the client
+    client.insert(2, Capture);
+    match dispatch::<Capture>(&mut client, Message::new(2, 0)) {
+        Err(DispatchError::AccessDenied { request: "ext_capture.copy", object: 2 }) => (),
+        result => panic!("{:?}", result)
+    }
+    client.insert(2, Capture);
+    assert!(matches!(dispatch::<Capture>(&mut client, Message::new(2, 1)), Err(DispatchError::AccessDenied { request: "ext_capture.destroy", .. })));
+
+    // Privileged clients are dispatched to as usual
+    client.insert_data(Privileged);
+    client.insert(1, Manager);
+    let mut capture = Message::new(1, 0);
+    capture.push_u32(2);
+    dispatch::<Manager>(&mut client, capture).unwrap();
+    assert!(client.objects.contains_key(&2));
+    client.insert(1, Manager);
+    dispatch::<Capture>(&mut client, Message::new(2, 1)).unwrap();
+}
@@ -0,0 +1,58 @@
+// Errors raised while decoding a request are reported as `wl_display.error` with the `invalid_method` code, while
+// errors of the caller's own are left to it
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{DispatchError, NewId, server::{Client, Lease, Result}};
+use wayland::{WlDisplay, WlDisplayError};
+
+#[derive(Default)]
+pub struct Display;
+impl WlDisplay for Lease<Display> {
+    fn sync(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+    fn get_registry(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Registry;
+impl wayland::WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Callback;
+impl wayland::WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlDisplay = crate::Display;
+    type WlRegistry = crate::Registry;
+    type WlCallback = crate::Callback;
+}
+
+fn main() {
+    let errors = [
+        DispatchError::InvalidRequest { opcode: 7, object: 4, interface: "wl_surface" },
+        DispatchError::NoVariant { name: "wl_output.transform", variant: 9 },
+        DispatchError::TrailingData { opcode: 1, object: 4, interface: "wl_surface" },
+        DispatchError::InvalidSince { opcode: 9, object: 4, interface: "wl_surface", version: 1 }
+    ];
+    let mut client = Client::default();
+    for error in &errors {
+        client.insert(1, Display);
+        assert!(wayland::report(&mut client, 4, error).unwrap());
+    }
+    assert_eq!(client.sent.len(), errors.len());
+    for message in &client.sent {
+        assert_eq!((message.object, message.opcode), (1, 0));
+        assert_eq!((message.data[0], message.data[1]), (4, *WlDisplayError::INVALID_METHOD));
+    }
+    // Errors of the server's own are not reported
+    client.insert(1, Display);
+    assert!(!wayland::report(&mut client, 4, &DispatchError::Other("out of memory".into())).unwrap());
+    assert_eq!(client.sent.len(), errors.len());
+}
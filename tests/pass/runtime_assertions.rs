@@ -0,0 +1,40 @@
+// The assertions each generated module makes of the runtime's types hold for a runtime matching the wire format. They
+// are constants, so building a module with `new_id` and file descriptor arguments checks them
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::fs::File;
+use runtime::{Fixed, NewId, server::{Client, Lease, Result}};
+use wayland::{WlBuffer, WlShm, WlShmPool};
+
+#[derive(Default)]
+pub struct Shm;
+impl WlShm for Lease<Shm> {
+    fn create_pool(&mut self, _: &mut Client, _: NewId, _: File, _: i32) -> Result<()> {
+        Ok(())
+    }
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct ShmPool;
+impl WlShmPool for Lease<ShmPool> {}
+#[derive(Default)]
+pub struct Buffer;
+impl WlBuffer for Lease<Buffer> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime, only = ["wl_shm", "wl_shm_pool", "wl_buffer"])]
+mod wayland {
+    type WlShm = crate::Shm;
+    type WlShmPool = crate::ShmPool (default_error);
+    type WlBuffer = crate::Buffer (default_error);
+}
+
+fn main() {
+    assert_eq!(std::mem::size_of::<Fixed>(), 4);
+    // The file descriptor of `create_pool` is passed out of band, leaving the new id and size
+    let file = File::open(std::env::current_exe().unwrap()).unwrap();
+    let message = wayland::wl_shm::encode_create_pool(1, NewId { id: 2, version: 1, interface: "wl_shm_pool" }, &file, 4096);
+    assert_eq!((message.opcode, message.data.len()), (0, 2));
+}
@@ -0,0 +1,158 @@
+// Items of the generated module may be shadowed by glob imports among the module's own items, which must not change
+// what the generated code refers to
+#![allow(dead_code, unused_imports, unused_macros)]
+
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::Lease;
+
+/// Stand-ins for the names of the prelude
+pub mod shadows {
+    pub struct Some;
+    pub struct None;
+    pub struct Ok;
+    pub struct Err;
+    pub struct Option;
+    pub struct Result;
+    pub struct Vec;
+    pub struct String;
+    pub struct Box;
+    pub struct Self_;
+    pub trait Default {}
+    pub trait Into {}
+    pub trait From {}
+    pub trait Iterator {}
+    pub trait IntoIterator {}
+    pub trait Clone {}
+    pub trait Copy {}
+    pub trait ToString {}
+    pub trait ToOwned {}
+    pub trait AsRef {}
+    pub trait PartialEq {}
+    pub trait Eq {}
+    pub trait PartialOrd {}
+    pub trait Ord {}
+    pub trait Drop {}
+    pub trait Fn {}
+    pub trait FnMut {}
+    pub trait FnOnce {}
+    pub trait Send {}
+    pub trait Sync {}
+    pub trait Sized {}
+    macro_rules! matches {
+        ($($tokens:tt)*) => { compile_error!("`matches!` of the prelude was shadowed") };
+    }
+    pub(crate) use matches;
+    macro_rules! assert {
+        ($($tokens:tt)*) => { compile_error!("`assert!` of the prelude was shadowed") };
+    }
+    pub(crate) use assert;
+    macro_rules! assert_eq {
+        ($($tokens:tt)*) => { compile_error!("`assert_eq!` of the prelude was shadowed") };
+    }
+    pub(crate) use assert_eq;
+    macro_rules! format {
+        ($($tokens:tt)*) => { compile_error!("`format!` of the prelude was shadowed") };
+    }
+    pub(crate) use format;
+    macro_rules! vec {
+        ($($tokens:tt)*) => { compile_error!("`vec!` of the prelude was shadowed") };
+    }
+    pub(crate) use vec;
+    macro_rules! write {
+        ($($tokens:tt)*) => { compile_error!("`write!` of the prelude was shadowed") };
+    }
+    pub(crate) use write;
+    macro_rules! writeln {
+        ($($tokens:tt)*) => { compile_error!("`writeln!` of the prelude was shadowed") };
+    }
+    pub(crate) use writeln;
+    macro_rules! panic {
+        ($($tokens:tt)*) => { compile_error!("`panic!` of the prelude was shadowed") };
+    }
+    pub(crate) use panic;
+    macro_rules! unreachable {
+        ($($tokens:tt)*) => { compile_error!("`unreachable!` of the prelude was shadowed") };
+    }
+    pub(crate) use unreachable;
+    macro_rules! eprintln {
+        ($($tokens:tt)*) => { compile_error!("`eprintln!` of the prelude was shadowed") };
+    }
+    pub(crate) use eprintln;
+    macro_rules! include_bytes {
+        ($($tokens:tt)*) => { compile_error!("`include_bytes!` of the prelude was shadowed") };
+    }
+    pub(crate) use include_bytes;
+}
+
+macro_rules! implementations {
+    ($($name:ident: $interface:ident,)*) => {
+        $(
+            #[derive(Default)]
+            pub struct $name;
+            impl wayland::$interface for Lease<$name> {}
+        )*
+    };
+}
+implementations! {
+    Display: WlDisplay,
+    Registry: WlRegistry,
+    Callback: WlCallback,
+    Compositor: WlCompositor,
+    ShmPool: WlShmPool,
+    Shm: WlShm,
+    Buffer: WlBuffer,
+    DataOffer: WlDataOffer,
+    DataSource: WlDataSource,
+    DataDevice: WlDataDevice,
+    DataDeviceManager: WlDataDeviceManager,
+    Shell: WlShell,
+    ShellSurface: WlShellSurface,
+    Surface: WlSurface,
+    Seat: WlSeat,
+    Pointer: WlPointer,
+    Keyboard: WlKeyboard,
+    Touch: WlTouch,
+    Output: WlOutput,
+    Region: WlRegion,
+    Subcompositor: WlSubcompositor,
+    Subsurface: WlSubsurface,
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![testing]
+    #![stats]
+    #![api_snapshot]
+    #![roundtrip_tests]
+    #![max_arg_len = 4096]
+    #![runtime(argument_limits)]
+    type WlDisplay = crate::Display (default_error);
+    type WlRegistry = crate::Registry (default_error);
+    type WlCallback = crate::Callback (default_error);
+    type WlCompositor = crate::Compositor (default_error);
+    type WlShmPool = crate::ShmPool (default_error);
+    type WlShm = crate::Shm (default_error);
+    type WlBuffer = crate::Buffer (default_error);
+    type WlDataOffer = crate::DataOffer (default_error);
+    type WlDataSource = crate::DataSource (default_error);
+    type WlDataDevice = crate::DataDevice (default_error);
+    type WlDataDeviceManager = crate::DataDeviceManager (default_error);
+    type WlShell = crate::Shell (default_error);
+    type WlShellSurface = crate::ShellSurface (default_error);
+    type WlSurface = crate::Surface (default_error);
+    type WlSeat = crate::Seat (default_error);
+    type WlPointer = crate::Pointer (default_error);
+    type WlKeyboard = crate::Keyboard (default_error);
+    type WlTouch = crate::Touch (default_error);
+    type WlOutput = crate::Output (default_error);
+    type WlRegion = crate::Region (default_error);
+    type WlSubcompositor = crate::Subcompositor (default_error);
+    type WlSubsurface = crate::Subsurface (default_error);
+
+    #[allow(unused_imports)]
+    use crate::shadows::*;
+}
+
+fn main() {}
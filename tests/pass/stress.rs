@@ -0,0 +1,58 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{Fixed, Message, server::{Client, Dispatch, Lease, Result}};
+use stress::ExtStress;
+
+#[derive(Default)]
+pub struct Stress;
+impl ExtStress for Lease<Stress> {
+    fn configure(
+        &mut self, client: &mut Client,
+        f0: i32, f1: u32, f2: Fixed, f3: String, f4: i32, f5: u32, f6: Fixed, f7: String,
+        f8: i32, f9: u32, f10: Fixed, f11: String, f12: i32, f13: u32, f14: Fixed, f15: String,
+        f16: i32, f17: u32, f18: Fixed, f19: String, f20: i32, f21: u32, f22: Fixed, f23: String,
+        f24: i32, f25: u32, f26: Fixed, f27: String, f28: i32, f29: u32, f30: Fixed, f31: String
+    ) -> Result<()> {
+        assert_eq!((f0, f1, f2, f3.as_str()), (0, 1, Fixed(2 * 256), "field 3"));
+        assert_eq!((f28, f29, f30, f31.as_str()), (-28, 29, Fixed(30 * 256), "field 31"));
+        // The fields are echoed back, tracing the event as well
+        self.configured(
+            client,
+            f0, f1, f2, &f3, f4, f5, f6, &f7, f8, f9, f10, &f11, f12, f13, f14, &f15,
+            f16, f17, f18, &f19, f20, f21, f22, &f23, f24, f25, f26, &f27, f28, f29, f30, &f31
+        )
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/stress.toml", crate = crate::runtime)]
+mod stress {
+    type ExtStress = crate::Stress;
+}
+
+fn main() {
+    // Tracing runs the format of every argument, both unlabelled and labelled
+    std::env::set_var("WAYLAND_DEBUG", "1");
+    for verbose in &[false, true] {
+        if *verbose {
+            std::env::set_var("WAYLAND_DEBUG_VERBOSE", "1");
+        }
+        let mut client = Client::default();
+        client.insert(1, Stress);
+        let mut message = Message::new(1, 0);
+        for field in 0..32 {
+            match field % 4 {
+                0 => message.push_i32(-field),
+                1 => message.push_u32(field as u32),
+                2 => message.push_fixed(Fixed(field * 256)),
+                _ => message.push_str(&format!("field {}", field))
+            }
+        }
+        let words = message.data.clone();
+        let lease = client.get_any(1).unwrap();
+        <Stress as Dispatch>::dispatch(lease, &mut client, message).unwrap();
+        // The event carries the request's arguments in the same order
+        assert_eq!(client.sent.len(), 1);
+        assert_eq!(client.sent[0].data, words);
+    }
+}
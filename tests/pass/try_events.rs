@@ -0,0 +1,55 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+use wayland::WlOutput;
+
+#[derive(Default)]
+pub struct Output;
+impl WlOutput for Lease<Output> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+// Without `Lease::version` the bound version is given by implementing `BoundVersion`
+#[derive(Default)]
+pub struct FixedOutput;
+impl fixed::WlOutput for Lease<FixedOutput> {
+    fn release(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+impl fixed::BoundVersion for Lease<FixedOutput> {
+    fn bound_version(&self) -> u32 {
+        3
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(lease_version)]
+    type WlOutput = crate::Output;
+}
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod fixed {
+    type WlOutput = crate::FixedOutput;
+}
+
+fn main() {
+    // `name` was introduced in version 4
+    for (version, sent) in [(1, false), (3, false), (4, true)] {
+        let mut client = Client::default();
+        let mut output = Lease { object: 3, version, value: Box::new(Output) };
+        assert_eq!(output.try_name(&mut client, "DP-1".into()).unwrap(), sent);
+        assert_eq!(client.sent.len(), sent as usize);
+    }
+    let mut client = Client::default();
+    let mut output = Lease { object: 3, version: 4, value: Box::new(FixedOutput) };
+    assert!(!fixed::WlOutput::try_name(&mut output, &mut client, "DP-1".into()).unwrap());
+    // The plain method sends regardless of the bound version
+    let mut client = Client::default();
+    let mut output = Lease { object: 3, version: 1, value: Box::new(Output) };
+    output.done(&mut client).unwrap();
+    assert_eq!(client.sent.len(), 1);
+}
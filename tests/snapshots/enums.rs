@@ -0,0 +1,557 @@
+#[derive(
+    ::std::fmt::Debug,
+    ::std::marker::Copy,
+    ::std::clone::Clone,
+    ::std::cmp::Eq,
+    ::std::cmp::PartialEq,
+    ::std::hash::Hash,
+    ::std::cmp::PartialOrd,
+    ::std::cmp::Ord
+)]
+#[repr(transparent)]
+pub struct WlDisplayError(u32);
+///global error values
+/**
+*/
+///These errors are global and can be emitted in response to any server request.
+impl WlDisplayError {
+    pub const ENUM_NAME: &'static str = "wl_display.error";
+    ///server couldn't find object
+    pub const INVALID_OBJECT: Self = Self(0u32);
+    ///method doesn't exist on the specified interface or malformed request
+    pub const INVALID_METHOD: Self = Self(1u32);
+    ///server is out of memory
+    pub const NO_MEMORY: Self = Self(2u32);
+    ///implementation error in compositor
+    pub const IMPLEMENTATION: Self = Self(3u32);
+    pub fn new(value: u32) -> ::wl::server::Result<Self> {
+        use ::std::convert::Into;
+        match value {
+            0u32 => ::std::result::Result::Ok(Self::INVALID_OBJECT),
+            1u32 => ::std::result::Result::Ok(Self::INVALID_METHOD),
+            2u32 => ::std::result::Result::Ok(Self::NO_MEMORY),
+            3u32 => ::std::result::Result::Ok(Self::IMPLEMENTATION),
+            _ => {
+                ::std::result::Result::Err(
+                    ::wl::DispatchError::NoVariant {
+                        name: Self::ENUM_NAME,
+                        variant: value,
+                    }
+                        .into(),
+                )
+            }
+        }
+    }
+}
+impl ::std::convert::Into<u32> for WlDisplayError {
+    fn into(self) -> u32 {
+        self.0
+    }
+}
+impl ::std::ops::Deref for WlDisplayError {
+    type Target = u32;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl ::std::default::Default for WlDisplayError {
+    fn default() -> Self {
+        Self::INVALID_OBJECT
+    }
+}
+impl ::std::fmt::Display for WlDisplayError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            Self::INVALID_OBJECT => f.write_str("server couldn't find object"),
+            Self::INVALID_METHOD => {
+                f
+                    .write_str(
+                        "method doesn't exist on the specified interface or malformed request",
+                    )
+            }
+            Self::NO_MEMORY => f.write_str("server is out of memory"),
+            Self::IMPLEMENTATION => f.write_str("implementation error in compositor"),
+            _ => ::std::write!(f, "{} {}", Self::ENUM_NAME, self.0),
+        }
+    }
+}
+impl ::std::error::Error for WlDisplayError {}
+#[derive(
+    ::std::fmt::Debug,
+    ::std::marker::Copy,
+    ::std::clone::Clone,
+    ::std::cmp::Eq,
+    ::std::cmp::PartialEq,
+    ::std::hash::Hash,
+    ::std::cmp::PartialOrd,
+    ::std::cmp::Ord
+)]
+#[repr(transparent)]
+pub struct WlShmError(u32);
+///wl_shm error values
+/**
+*/
+///These errors can be emitted in response to wl_shm requests.
+impl WlShmError {
+    pub const ENUM_NAME: &'static str = "wl_shm.error";
+    ///buffer format is not known
+    pub const INVALID_FORMAT: Self = Self(0u32);
+    ///invalid size or stride during pool or buffer creation
+    pub const INVALID_STRIDE: Self = Self(1u32);
+    ///mmapping the file descriptor failed
+    pub const INVALID_FD: Self = Self(2u32);
+    pub fn new(value: u32) -> ::wl::server::Result<Self> {
+        use ::std::convert::Into;
+        match value {
+            0u32 => ::std::result::Result::Ok(Self::INVALID_FORMAT),
+            1u32 => ::std::result::Result::Ok(Self::INVALID_STRIDE),
+            2u32 => ::std::result::Result::Ok(Self::INVALID_FD),
+            _ => {
+                ::std::result::Result::Err(
+                    ::wl::DispatchError::NoVariant {
+                        name: Self::ENUM_NAME,
+                        variant: value,
+                    }
+                        .into(),
+                )
+            }
+        }
+    }
+}
+impl ::std::convert::Into<u32> for WlShmError {
+    fn into(self) -> u32 {
+        self.0
+    }
+}
+impl ::std::ops::Deref for WlShmError {
+    type Target = u32;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl ::std::default::Default for WlShmError {
+    fn default() -> Self {
+        Self::INVALID_FORMAT
+    }
+}
+impl ::std::fmt::Display for WlShmError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            Self::INVALID_FORMAT => f.write_str("buffer format is not known"),
+            Self::INVALID_STRIDE => {
+                f.write_str("invalid size or stride during pool or buffer creation")
+            }
+            Self::INVALID_FD => f.write_str("mmapping the file descriptor failed"),
+            _ => ::std::write!(f, "{} {}", Self::ENUM_NAME, self.0),
+        }
+    }
+}
+impl ::std::error::Error for WlShmError {}
+#[derive(
+    ::std::fmt::Debug,
+    ::std::marker::Copy,
+    ::std::clone::Clone,
+    ::std::cmp::Eq,
+    ::std::cmp::PartialEq,
+    ::std::hash::Hash,
+    ::std::cmp::PartialOrd,
+    ::std::cmp::Ord
+)]
+#[repr(transparent)]
+pub struct WlShmFormat(u32);
+///pixel formats
+/**
+*/
+///This describes the memory layout of an individual pixel.  All renderers should support argb8888 and xrgb8888 but any other formats are optional and may not be supported by the particular renderer in use.  The drm format codes match the macros defined in drm_fourcc.h, except argb8888 and xrgb8888. The formats actually supported by the compositor will be reported by the format event.  For all wl_shm formats and unless specified in another protocol extension, pre-multiplied alpha is used for pixel values.
+impl WlShmFormat {
+    pub const ENUM_NAME: &'static str = "wl_shm.format";
+    ///32-bit ARGB format, [31:0] A:R:G:B 8:8:8:8 little endian
+    pub const ARGB8888: Self = Self(0u32);
+    ///32-bit RGB format, [31:0] x:R:G:B 8:8:8:8 little endian
+    pub const XRGB8888: Self = Self(1u32);
+    ///8-bit color index format, [7:0] C
+    pub const C8: Self = Self(0x20203843);
+    ///8-bit RGB format, [7:0] R:G:B 3:3:2
+    pub const RGB332: Self = Self(0x38424752);
+    ///8-bit BGR format, [7:0] B:G:R 2:3:3
+    pub const BGR233: Self = Self(0x38524742);
+    ///16-bit xRGB format, [15:0] x:R:G:B 4:4:4:4 little endian
+    pub const XRGB4444: Self = Self(0x32315258);
+    ///16-bit xBGR format, [15:0] x:B:G:R 4:4:4:4 little endian
+    pub const XBGR4444: Self = Self(0x32314258);
+    ///16-bit RGBx format, [15:0] R:G:B:x 4:4:4:4 little endian
+    pub const RGBX4444: Self = Self(0x32315852);
+    ///16-bit BGRx format, [15:0] B:G:R:x 4:4:4:4 little endian
+    pub const BGRX4444: Self = Self(0x32315842);
+    ///16-bit ARGB format, [15:0] A:R:G:B 4:4:4:4 little endian
+    pub const ARGB4444: Self = Self(0x32315241);
+    ///16-bit ABGR format, [15:0] A:B:G:R 4:4:4:4 little endian
+    pub const ABGR4444: Self = Self(0x32314241);
+    ///16-bit RBGA format, [15:0] R:G:B:A 4:4:4:4 little endian
+    pub const RGBA4444: Self = Self(0x32314152);
+    ///16-bit BGRA format, [15:0] B:G:R:A 4:4:4:4 little endian
+    pub const BGRA4444: Self = Self(0x32314142);
+    ///16-bit xRGB format, [15:0] x:R:G:B 1:5:5:5 little endian
+    pub const XRGB1555: Self = Self(0x35315258);
+    ///16-bit xBGR 1555 format, [15:0] x:B:G:R 1:5:5:5 little endian
+    pub const XBGR1555: Self = Self(0x35314258);
+    ///16-bit RGBx 5551 format, [15:0] R:G:B:x 5:5:5:1 little endian
+    pub const RGBX5551: Self = Self(0x35315852);
+    ///16-bit BGRx 5551 format, [15:0] B:G:R:x 5:5:5:1 little endian
+    pub const BGRX5551: Self = Self(0x35315842);
+    ///16-bit ARGB 1555 format, [15:0] A:R:G:B 1:5:5:5 little endian
+    pub const ARGB1555: Self = Self(0x35315241);
+    ///16-bit ABGR 1555 format, [15:0] A:B:G:R 1:5:5:5 little endian
+    pub const ABGR1555: Self = Self(0x35314241);
+    ///16-bit RGBA 5551 format, [15:0] R:G:B:A 5:5:5:1 little endian
+    pub const RGBA5551: Self = Self(0x35314152);
+    ///16-bit BGRA 5551 format, [15:0] B:G:R:A 5:5:5:1 little endian
+    pub const BGRA5551: Self = Self(0x35314142);
+    ///16-bit RGB 565 format, [15:0] R:G:B 5:6:5 little endian
+    pub const RGB565: Self = Self(0x36314752);
+    ///16-bit BGR 565 format, [15:0] B:G:R 5:6:5 little endian
+    pub const BGR565: Self = Self(0x36314742);
+    ///24-bit RGB format, [23:0] R:G:B little endian
+    pub const RGB888: Self = Self(0x34324752);
+    ///24-bit BGR format, [23:0] B:G:R little endian
+    pub const BGR888: Self = Self(0x34324742);
+    ///32-bit xBGR format, [31:0] x:B:G:R 8:8:8:8 little endian
+    pub const XBGR8888: Self = Self(0x34324258);
+    ///32-bit RGBx format, [31:0] R:G:B:x 8:8:8:8 little endian
+    pub const RGBX8888: Self = Self(0x34325852);
+    ///32-bit BGRx format, [31:0] B:G:R:x 8:8:8:8 little endian
+    pub const BGRX8888: Self = Self(0x34325842);
+    ///32-bit ABGR format, [31:0] A:B:G:R 8:8:8:8 little endian
+    pub const ABGR8888: Self = Self(0x34324241);
+    ///32-bit RGBA format, [31:0] R:G:B:A 8:8:8:8 little endian
+    pub const RGBA8888: Self = Self(0x34324152);
+    ///32-bit BGRA format, [31:0] B:G:R:A 8:8:8:8 little endian
+    pub const BGRA8888: Self = Self(0x34324142);
+    ///32-bit xRGB format, [31:0] x:R:G:B 2:10:10:10 little endian
+    pub const XRGB2101010: Self = Self(0x30335258);
+    ///32-bit xBGR format, [31:0] x:B:G:R 2:10:10:10 little endian
+    pub const XBGR2101010: Self = Self(0x30334258);
+    ///32-bit RGBx format, [31:0] R:G:B:x 10:10:10:2 little endian
+    pub const RGBX1010102: Self = Self(0x30335852);
+    ///32-bit BGRx format, [31:0] B:G:R:x 10:10:10:2 little endian
+    pub const BGRX1010102: Self = Self(0x30335842);
+    ///32-bit ARGB format, [31:0] A:R:G:B 2:10:10:10 little endian
+    pub const ARGB2101010: Self = Self(0x30335241);
+    ///32-bit ABGR format, [31:0] A:B:G:R 2:10:10:10 little endian
+    pub const ABGR2101010: Self = Self(0x30334241);
+    ///32-bit RGBA format, [31:0] R:G:B:A 10:10:10:2 little endian
+    pub const RGBA1010102: Self = Self(0x30334152);
+    ///32-bit BGRA format, [31:0] B:G:R:A 10:10:10:2 little endian
+    pub const BGRA1010102: Self = Self(0x30334142);
+    ///packed YCbCr format, [31:0] Cr0:Y1:Cb0:Y0 8:8:8:8 little endian
+    pub const YUYV: Self = Self(0x56595559);
+    ///packed YCbCr format, [31:0] Cb0:Y1:Cr0:Y0 8:8:8:8 little endian
+    pub const YVYU: Self = Self(0x55595659);
+    ///packed YCbCr format, [31:0] Y1:Cr0:Y0:Cb0 8:8:8:8 little endian
+    pub const UYVY: Self = Self(0x59565955);
+    ///packed YCbCr format, [31:0] Y1:Cb0:Y0:Cr0 8:8:8:8 little endian
+    pub const VYUY: Self = Self(0x59555956);
+    ///packed AYCbCr format, [31:0] A:Y:Cb:Cr 8:8:8:8 little endian
+    pub const AYUV: Self = Self(0x56555941);
+    ///2 plane YCbCr Cr:Cb format, 2x2 subsampled Cr:Cb plane
+    pub const NV12: Self = Self(0x3231564e);
+    ///2 plane YCbCr Cb:Cr format, 2x2 subsampled Cb:Cr plane
+    pub const NV21: Self = Self(0x3132564e);
+    ///2 plane YCbCr Cr:Cb format, 2x1 subsampled Cr:Cb plane
+    pub const NV16: Self = Self(0x3631564e);
+    ///2 plane YCbCr Cb:Cr format, 2x1 subsampled Cb:Cr plane
+    pub const NV61: Self = Self(0x3136564e);
+    ///3 plane YCbCr format, 4x4 subsampled Cb (1) and Cr (2) planes
+    pub const YUV410: Self = Self(0x39565559);
+    ///3 plane YCbCr format, 4x4 subsampled Cr (1) and Cb (2) planes
+    pub const YVU410: Self = Self(0x39555659);
+    ///3 plane YCbCr format, 4x1 subsampled Cb (1) and Cr (2) planes
+    pub const YUV411: Self = Self(0x31315559);
+    ///3 plane YCbCr format, 4x1 subsampled Cr (1) and Cb (2) planes
+    pub const YVU411: Self = Self(0x31315659);
+    ///3 plane YCbCr format, 2x2 subsampled Cb (1) and Cr (2) planes
+    pub const YUV420: Self = Self(0x32315559);
+    ///3 plane YCbCr format, 2x2 subsampled Cr (1) and Cb (2) planes
+    pub const YVU420: Self = Self(0x32315659);
+    ///3 plane YCbCr format, 2x1 subsampled Cb (1) and Cr (2) planes
+    pub const YUV422: Self = Self(0x36315559);
+    ///3 plane YCbCr format, 2x1 subsampled Cr (1) and Cb (2) planes
+    pub const YVU422: Self = Self(0x36315659);
+    ///3 plane YCbCr format, non-subsampled Cb (1) and Cr (2) planes
+    pub const YUV444: Self = Self(0x34325559);
+    ///3 plane YCbCr format, non-subsampled Cr (1) and Cb (2) planes
+    pub const YVU444: Self = Self(0x34325659);
+    ///[7:0] R
+    pub const R8: Self = Self(0x20203852);
+    ///[15:0] R little endian
+    pub const R16: Self = Self(0x20363152);
+    ///[15:0] R:G 8:8 little endian
+    pub const RG88: Self = Self(0x38384752);
+    ///[15:0] G:R 8:8 little endian
+    pub const GR88: Self = Self(0x38385247);
+    ///[31:0] R:G 16:16 little endian
+    pub const RG1616: Self = Self(0x32334752);
+    ///[31:0] G:R 16:16 little endian
+    pub const GR1616: Self = Self(0x32335247);
+    ///[63:0] x:R:G:B 16:16:16:16 little endian
+    pub const XRGB16161616F: Self = Self(0x48345258);
+    ///[63:0] x:B:G:R 16:16:16:16 little endian
+    pub const XBGR16161616F: Self = Self(0x48344258);
+    ///[63:0] A:R:G:B 16:16:16:16 little endian
+    pub const ARGB16161616F: Self = Self(0x48345241);
+    ///[63:0] A:B:G:R 16:16:16:16 little endian
+    pub const ABGR16161616F: Self = Self(0x48344241);
+    ///[31:0] X:Y:Cb:Cr 8:8:8:8 little endian
+    pub const XYUV8888: Self = Self(0x56555958);
+    ///[23:0] Cr:Cb:Y 8:8:8 little endian
+    pub const VUY888: Self = Self(0x34325556);
+    ///Y followed by U then V, 10:10:10. Non-linear modifier only
+    pub const VUY101010: Self = Self(0x30335556);
+    ///[63:0] Cr0:0:Y1:0:Cb0:0:Y0:0 10:6:10:6:10:6:10:6 little endian per 2 Y pixels
+    pub const Y210: Self = Self(0x30313259);
+    ///[63:0] Cr0:0:Y1:0:Cb0:0:Y0:0 12:4:12:4:12:4:12:4 little endian per 2 Y pixels
+    pub const Y212: Self = Self(0x32313259);
+    ///[63:0] Cr0:Y1:Cb0:Y0 16:16:16:16 little endian per 2 Y pixels
+    pub const Y216: Self = Self(0x36313259);
+    ///[31:0] A:Cr:Y:Cb 2:10:10:10 little endian
+    pub const Y410: Self = Self(0x30313459);
+    ///[63:0] A:0:Cr:0:Y:0:Cb:0 12:4:12:4:12:4:12:4 little endian
+    pub const Y412: Self = Self(0x32313459);
+    ///[63:0] A:Cr:Y:Cb 16:16:16:16 little endian
+    pub const Y416: Self = Self(0x36313459);
+    ///[31:0] X:Cr:Y:Cb 2:10:10:10 little endian
+    pub const XVYU2101010: Self = Self(0x30335658);
+    ///[63:0] X:0:Cr:0:Y:0:Cb:0 12:4:12:4:12:4:12:4 little endian
+    pub const XVYU12_16161616: Self = Self(0x36335658);
+    ///[63:0] X:Cr:Y:Cb 16:16:16:16 little endian
+    pub const XVYU16161616: Self = Self(0x38345658);
+    ///[63:0]   A3:A2:Y3:0:Cr0:0:Y2:0:A1:A0:Y1:0:Cb0:0:Y0:0  1:1:8:2:8:2:8:2:1:1:8:2:8:2:8:2 little endian
+    pub const Y0L0: Self = Self(0x304c3059);
+    ///[63:0]   X3:X2:Y3:0:Cr0:0:Y2:0:X1:X0:Y1:0:Cb0:0:Y0:0  1:1:8:2:8:2:8:2:1:1:8:2:8:2:8:2 little endian
+    pub const X0L0: Self = Self(0x304c3058);
+    ///[63:0]   A3:A2:Y3:Cr0:Y2:A1:A0:Y1:Cb0:Y0  1:1:10:10:10:1:1:10:10:10 little endian
+    pub const Y0L2: Self = Self(0x324c3059);
+    ///[63:0]   X3:X2:Y3:Cr0:Y2:X1:X0:Y1:Cb0:Y0  1:1:10:10:10:1:1:10:10:10 little endian
+    pub const X0L2: Self = Self(0x324c3058);
+    pub const YUV420_8BIT: Self = Self(0x38305559);
+    pub const YUV420_10BIT: Self = Self(0x30315559);
+    pub const XRGB8888_A8: Self = Self(0x38415258);
+    pub const XBGR8888_A8: Self = Self(0x38414258);
+    pub const RGBX8888_A8: Self = Self(0x38415852);
+    pub const BGRX8888_A8: Self = Self(0x38415842);
+    pub const RGB888_A8: Self = Self(0x38413852);
+    pub const BGR888_A8: Self = Self(0x38413842);
+    pub const RGB565_A8: Self = Self(0x38413552);
+    pub const BGR565_A8: Self = Self(0x38413542);
+    ///non-subsampled Cr:Cb plane
+    pub const NV24: Self = Self(0x3432564e);
+    ///non-subsampled Cb:Cr plane
+    pub const NV42: Self = Self(0x3234564e);
+    ///2x1 subsampled Cr:Cb plane, 10 bit per channel
+    pub const P210: Self = Self(0x30313250);
+    ///2x2 subsampled Cr:Cb plane 10 bits per channel
+    pub const P010: Self = Self(0x30313050);
+    ///2x2 subsampled Cr:Cb plane 12 bits per channel
+    pub const P012: Self = Self(0x32313050);
+    ///2x2 subsampled Cr:Cb plane 16 bits per channel
+    pub const P016: Self = Self(0x36313050);
+    ///[63:0] A:x:B:x:G:x:R:x 10:6:10:6:10:6:10:6 little endian
+    pub const AXBXGXRX106106106106: Self = Self(0x30314241);
+    ///2x2 subsampled Cr:Cb plane
+    pub const NV15: Self = Self(0x3531564e);
+    pub const Q410: Self = Self(0x30313451);
+    pub const Q401: Self = Self(0x31303451);
+    ///[63:0] x:R:G:B 16:16:16:16 little endian
+    pub const XRGB16161616: Self = Self(0x38345258);
+    ///[63:0] x:B:G:R 16:16:16:16 little endian
+    pub const XBGR16161616: Self = Self(0x38344258);
+    ///[63:0] A:R:G:B 16:16:16:16 little endian
+    pub const ARGB16161616: Self = Self(0x38345241);
+    ///[63:0] A:B:G:R 16:16:16:16 little endian
+    pub const ABGR16161616: Self = Self(0x38344241);
+    ///[7:0] C0:C1:C2:C3:C4:C5:C6:C7 1:1:1:1:1:1:1:1 eight pixels/byte
+    pub const C1: Self = Self(0x20203143);
+    ///[7:0] C0:C1:C2:C3 2:2:2:2 four pixels/byte
+    pub const C2: Self = Self(0x20203243);
+    ///[7:0] C0:C1 4:4 two pixels/byte
+    pub const C4: Self = Self(0x20203443);
+    ///[7:0] D0:D1:D2:D3:D4:D5:D6:D7 1:1:1:1:1:1:1:1 eight pixels/byte
+    pub const D1: Self = Self(0x20203144);
+    ///[7:0] D0:D1:D2:D3 2:2:2:2 four pixels/byte
+    pub const D2: Self = Self(0x20203244);
+    ///[7:0] D0:D1 4:4 two pixels/byte
+    pub const D4: Self = Self(0x20203444);
+    ///[7:0] D
+    pub const D8: Self = Self(0x20203844);
+    ///[7:0] R0:R1:R2:R3:R4:R5:R6:R7 1:1:1:1:1:1:1:1 eight pixels/byte
+    pub const R1: Self = Self(0x20203152);
+    ///[7:0] R0:R1:R2:R3 2:2:2:2 four pixels/byte
+    pub const R2: Self = Self(0x20203252);
+    ///[7:0] R0:R1 4:4 two pixels/byte
+    pub const R4: Self = Self(0x20203452);
+    ///[15:0] x:R 6:10 little endian
+    pub const R10: Self = Self(0x20303152);
+    ///[15:0] x:R 4:12 little endian
+    pub const R12: Self = Self(0x20323152);
+    ///[31:0] A:Cr:Cb:Y 8:8:8:8 little endian
+    pub const AVUY8888: Self = Self(0x59555641);
+    ///[31:0] X:Cr:Cb:Y 8:8:8:8 little endian
+    pub const XVUY8888: Self = Self(0x59555658);
+    ///2x2 subsampled Cr:Cb plane 10 bits per channel packed
+    pub const P030: Self = Self(0x30333050);
+    pub fn new(value: u32) -> ::wl::server::Result<Self> {
+        use ::std::convert::Into;
+        const VALUES: &[u32] = &[
+            0u32,
+            1u32,
+            0x20203143,
+            0x20203144,
+            0x20203152,
+            0x20203243,
+            0x20203244,
+            0x20203252,
+            0x20203443,
+            0x20203444,
+            0x20203452,
+            0x20203843,
+            0x20203844,
+            0x20203852,
+            0x20303152,
+            0x20323152,
+            0x20363152,
+            0x30313050,
+            0x30313250,
+            0x30313259,
+            0x30313451,
+            0x30313459,
+            0x30314241,
+            0x30315559,
+            0x30333050,
+            0x30334142,
+            0x30334152,
+            0x30334241,
+            0x30334258,
+            0x30335241,
+            0x30335258,
+            0x30335556,
+            0x30335658,
+            0x30335842,
+            0x30335852,
+            0x304c3058,
+            0x304c3059,
+            0x31303451,
+            0x31315559,
+            0x31315659,
+            0x3132564e,
+            0x3136564e,
+            0x32313050,
+            0x32313259,
+            0x32313459,
+            0x32314142,
+            0x32314152,
+            0x32314241,
+            0x32314258,
+            0x32315241,
+            0x32315258,
+            0x32315559,
+            0x3231564e,
+            0x32315659,
+            0x32315842,
+            0x32315852,
+            0x32334752,
+            0x32335247,
+            0x3234564e,
+            0x324c3058,
+            0x324c3059,
+            0x34324142,
+            0x34324152,
+            0x34324241,
+            0x34324258,
+            0x34324742,
+            0x34324752,
+            0x34325556,
+            0x34325559,
+            0x3432564e,
+            0x34325659,
+            0x34325842,
+            0x34325852,
+            0x35314142,
+            0x35314152,
+            0x35314241,
+            0x35314258,
+            0x35315241,
+            0x35315258,
+            0x3531564e,
+            0x35315842,
+            0x35315852,
+            0x36313050,
+            0x36313259,
+            0x36313459,
+            0x36314742,
+            0x36314752,
+            0x36315559,
+            0x3631564e,
+            0x36315659,
+            0x36335658,
+            0x38305559,
+            0x38344241,
+            0x38344258,
+            0x38345241,
+            0x38345258,
+            0x38345658,
+            0x38384752,
+            0x38385247,
+            0x38413542,
+            0x38413552,
+            0x38413842,
+            0x38413852,
+            0x38414258,
+            0x38415258,
+            0x38415842,
+            0x38415852,
+            0x38424752,
+            0x38524742,
+            0x39555659,
+            0x39565559,
+            0x48344241,
+            0x48344258,
+            0x48345241,
+            0x48345258,
+            0x55595659,
+            0x56555941,
+            0x56555958,
+            0x56595559,
+            0x59555641,
+            0x59555658,
+            0x59555956,
+            0x59565955,
+        ];
+        if VALUES.binary_search(&value).is_ok() {
+            ::std::result::Result::Ok(Self(value))
+        } else {
+            ::std::result::Result::Err(
+                ::wl::DispatchError::NoVariant {
+                    name: Self::ENUM_NAME,
+                    variant: value,
+                }
+                    .into(),
+            )
+        }
+    }
+}
+impl ::std::convert::Into<u32> for WlShmFormat {
+    fn into(self) -> u32 {
+        self.0
+    }
+}
+impl ::std::ops::Deref for WlShmFormat {
+    type Target = u32;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl ::std::default::Default for WlShmFormat {
+    fn default() -> Self {
+        Self::ARGB8888
+    }
+}
@@ -0,0 +1,434 @@
+pub mod wayland {
+    /*!Interfaces of the `wayland` protocol:
+
+| Interface | Version | Implementation | Summary |
+|---|---|---|---|
+| `wl_display` | 1 | not bound | core global object |
+| [`wl_callback`](WlCallback) | 1 | `crate::Callback` | callback object |
+| `wl_registry` | 1 | not bound | global registry object |
+| `wl_compositor` | 6 | not bound | the compositor singleton |
+| `wl_surface` | 6 | not bound | an onscreen surface |
+| `wl_buffer` | 1 | not bound | content for a wl_surface |
+| `wl_region` | 1 | not bound | region interface |
+| `wl_shm` | 2 | not bound | shared memory support |
+| `wl_shm_pool` | 2 | not bound | a shared memory pool |
+| `wl_data_offer` | 3 | not bound | offer to transfer data |
+| `wl_data_device_manager` | 3 | not bound | data transfer interface |
+| `wl_data_source` | 3 | not bound | offer to transfer data |
+| `wl_data_device` | 3 | not bound | data transfer device |
+| `wl_seat` | 9 | not bound | group of input devices |
+| `wl_pointer` | 9 | not bound | pointer input device |
+| `wl_keyboard` | 9 | not bound | keyboard input device |
+| `wl_touch` | 9 | not bound | touchscreen input device |
+| `wl_shell` | 1 | not bound | create desktop-style surfaces |
+| `wl_shell_surface` | 1 | not bound | desktop-style metadata interface |
+| `wl_output` | 4 | not bound | compositor output region |
+| `wl_subcompositor` | 1 | not bound | sub-surface compositing |
+| `wl_subsurface` | 1 | not bound | sub-surface interface to a wl_surface |
+
+*/
+    const _: () = if !(::std::mem::size_of::<::wl::Fixed>() == 4
+        && ::std::mem::align_of::<::wl::Fixed>() <= 4)
+    {
+        ::std::panic!("The runtime's Fixed must be a single 24.8 fixed point word")
+    };
+    const _: fn(&::wl::Message) -> u16 = |message| message.opcode;
+    const _: fn(
+        &::wl::Message,
+        &'static str,
+        u32,
+    ) -> ::wl::server::Result<::wl::NewId> = |message, interface, version| {
+        message.args().next_new_id(interface, version)
+    };
+    const _: fn(&mut ::wl::Message, ::wl::NewId) = |message, id| {
+        message.push_new_id(id);
+    };
+    const _: fn(&mut ::wl::server::Client) -> ::wl::server::Result<::std::fs::File> = |
+        client|
+    client.next_file();
+    const _: fn(&mut ::wl::Message, &::std::fs::File) = |message, file| {
+        message.push_file(file);
+    };
+    const _: &[u8] = ::std::include_bytes!("$CARGO_MANIFEST_DIR/tests/wayland.toml");
+    /// The first of the merged protocols
+    pub const PROTOCOL: &'static str = "wayland";
+    pub const PROTOCOLS: &'static [&'static str] = &["wayland"];
+    /// Likely mistakes in the protocol specification that do not prevent generation
+    pub const PROTOCOL_LINTS: &'static [&'static str] = &[
+        "Enum wl_shell.error has a single entry",
+        "Enum wl_shell_surface.transient has a single entry",
+        "Enum wl_seat.error has a single entry",
+        "Enum wl_pointer.error has a single entry",
+        "Enum wl_subsurface.error has a single entry",
+    ];
+    /// The copyright notice of the first of the merged protocols, empty if it has none
+    pub const COPYRIGHT: &'static str = "Copyright © 2008-2011 Kristian Høgsberg\n    Copyright © 2010-2011 Intel Corporation\n    Copyright © 2012-2013 Collabora, Ltd.\n\n    Permission is hereby granted, free of charge, to any person\n    obtaining a copy of this software and associated documentation files\n    (the \"Software\"), to deal in the Software without restriction,\n    including without limitation the rights to use, copy, modify, merge,\n    publish, distribute, sublicense, and/or sell copies of the Software,\n    and to permit persons to whom the Software is furnished to do so,\n    subject to the following conditions:\n\n    The above copyright notice and this permission notice (including the\n    next paragraph) shall be included in all copies or substantial\n    portions of the Software.\n\n    THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND,\n    EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF\n    MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND\n    NONINFRINGEMENT.  IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS\n    BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN\n    ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN\n    CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE\n    SOFTWARE.";
+    /// The copyright notice of each protocol in `PROTOCOLS`, empty where a protocol has none
+    pub const COPYRIGHTS: &'static [&'static str] = &[
+        "Copyright © 2008-2011 Kristian Høgsberg\n    Copyright © 2010-2011 Intel Corporation\n    Copyright © 2012-2013 Collabora, Ltd.\n\n    Permission is hereby granted, free of charge, to any person\n    obtaining a copy of this software and associated documentation files\n    (the \"Software\"), to deal in the Software without restriction,\n    including without limitation the rights to use, copy, modify, merge,\n    publish, distribute, sublicense, and/or sell copies of the Software,\n    and to permit persons to whom the Software is furnished to do so,\n    subject to the following conditions:\n\n    The above copyright notice and this permission notice (including the\n    next paragraph) shall be included in all copies or substantial\n    portions of the Software.\n\n    THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND,\n    EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF\n    MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND\n    NONINFRINGEMENT.  IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS\n    BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN\n    ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN\n    CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE\n    SOFTWARE.",
+    ];
+    /// A hash of the wire format of every interface of the merged protocols, whether bound or not
+    pub const SIGNATURE_HASH: u64 = 5433625463556557154u64;
+    /// Introspection data for a request or event, indexed by opcode in the per-interface tables
+    #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone)]
+    pub struct MessageInfo {
+        pub name: &'static str,
+        pub opcode: u16,
+        /// The interface version the message was introduced in
+        pub since: u32,
+        /// The request sets pending state that is only applied once the object's state is latched
+        pub double_buffered: bool,
+        /// The request applies previously pending state
+        pub latching: bool,
+        /// The smallest size in bytes of the message on the wire, with its strings and arrays empty and its
+        /// 8 byte header included
+        pub min_size: u32,
+        /// The size in bytes of the message on the wire, if none of its arguments vary in length
+        pub fixed_size: ::std::option::Option<u32>,
+    }
+    /// An interface implemented by this module's bindings, listed in `IMPLEMENTATIONS`
+    #[derive(::std::fmt::Debug, ::std::marker::Copy, ::std::clone::Clone)]
+    pub struct ImplementationInfo {
+        pub interface: &'static str,
+        /// The highest version the binding supports
+        pub version: u32,
+        /// The implementation as written in the binding
+        pub implementor: &'static str,
+        pub requests: usize,
+        pub events: usize,
+    }
+    /// The interfaces implemented by this module in specification order
+    pub const IMPLEMENTATIONS: &'static [ImplementationInfo] = &[
+        ImplementationInfo {
+            interface: "wl_callback",
+            version: 1u32,
+            implementor: "crate::Callback",
+            requests: 0usize,
+            events: 1usize,
+        },
+    ];
+    /// Events serialized by the `_into` event methods, sent together in a single write where the module uses the
+    /// runtime's `send_all`
+    #[derive(::std::default::Default)]
+    pub struct EventBatch {
+        messages: ::std::vec::Vec<::wl::Message>,
+        /// The first event rejected before it was serialized, failing the whole batch
+        error: ::std::option::Option<::wl::DispatchError>,
+    }
+    impl EventBatch {
+        pub fn new() -> Self {
+            Self::default()
+        }
+        pub fn push(&mut self, message: ::wl::Message) {
+            self.messages.push(message)
+        }
+        /// Fail the batch with `error` when it is sent, so that none of its events are written. Only the first
+        /// error is kept
+        pub fn reject(&mut self, error: ::wl::DispatchError) {
+            self.error.get_or_insert(error);
+        }
+        pub fn len(&self) -> usize {
+            self.messages.len()
+        }
+        pub fn is_empty(&self) -> bool {
+            self.messages.is_empty()
+        }
+        /// Send every collected event to the client, stopping at the first that fails
+        pub fn send(
+            self,
+            client: &mut ::wl::server::Client,
+        ) -> ::wl::server::Result<()> {
+            if let ::std::option::Option::Some(error) = self.error {
+                return ::std::result::Result::Err(error.into());
+            }
+            self.messages.into_iter().try_for_each(|message| client.send(message))
+        }
+    }
+    ///Clients can handle the 'done' event to get notified when the related request is done.  Note, because wl_callback objects are created from multiple independent factory interfaces, the wl_callback interface is frozen at version 1.
+    /**
+# Events
+*/
+    ///- [`done(callback_data)`](Self::done) (opcode 0, since 1): done event
+    /**
+# Implementation
+*/
+    ///Implemented in this build by [`crate::Callback`](crate::Callback). Implementations are owned by the client's leases, so must be `Sized` and `'static`, owning their data rather than borrowing it.
+    pub trait WlCallback: ::wl::Object {
+        const VERSION: u32 = 1u32;
+        const INTERFACE: &'static str = "wl_callback";
+        const REQUESTS: &'static [MessageInfo] = wl_callback::REQUESTS;
+        const EVENTS: &'static [MessageInfo] = wl_callback::EVENTS;
+        ///done event
+        /**
+*/
+        ///Notify the client when the related request is done.
+        fn done(
+            &mut self,
+            client: &mut ::wl::server::Client,
+            wl_callback_data: u32,
+        ) -> ::wl::server::Result<()> {
+            if *::wl::DEBUG {
+                if *::wl::DEBUG_VERBOSE {
+                    ::std::eprintln!(
+                        "-> {}@{}.{}(callback_data: {})", "wl_callback", self.object(),
+                        "done", wl_callback_data
+                    )
+                } else {
+                    ::std::eprintln!(
+                        "-> {}@{}.{}({})", "wl_callback", self.object(), "done",
+                        wl_callback_data
+                    )
+                }
+            }
+            let message = wl_callback::encode_done(self.object(), wl_callback_data);
+            client.send(message)
+        }
+        ///Serialize the `done` event into `batch` to be sent along with other events
+        fn done_into(&mut self, batch: &mut EventBatch, wl_callback_data: u32) {
+            if *::wl::DEBUG {
+                if *::wl::DEBUG_VERBOSE {
+                    ::std::eprintln!(
+                        "-> {}@{}.{}(callback_data: {})", "wl_callback", self.object(),
+                        "done", wl_callback_data
+                    )
+                } else {
+                    ::std::eprintln!(
+                        "-> {}@{}.{}({})", "wl_callback", self.object(), "done",
+                        wl_callback_data
+                    )
+                }
+            }
+            let message = wl_callback::encode_done(self.object(), wl_callback_data);
+            batch.push(message)
+        }
+    }
+    const _: () = {
+        #[allow(dead_code)]
+        trait ImplementationMustBeSizedAndStatic: ::std::any::Any + ::std::marker::Sized {}
+        impl ImplementationMustBeSizedAndStatic for crate::Callback {}
+    };
+    ///Dispatches requests to `wl_callback` objects to the methods of [`WlCallback`]
+    #[allow(deprecated)]
+    impl ::wl::server::Dispatch for crate::Callback {
+        const INTERFACE: &'static str = "wl_callback";
+        const VERSION: u32 = 1u32;
+        fn dispatch(
+            lease: ::wl::server::Lease<dyn ::std::any::Any>,
+            client: &mut ::wl::server::Client,
+            message: ::wl::Message,
+        ) -> ::wl::server::Result<()> {
+            use ::wl::Object;
+            use ::std::convert::Into;
+            let _ = client;
+            let lease: ::wl::server::Lease<crate::Callback> = lease.downcast().unwrap();
+            match message.opcode {
+                _ => {
+                    ::std::result::Result::Err(
+                        ::wl::DispatchError::InvalidRequest {
+                            opcode: message.opcode,
+                            object: lease.object(),
+                            interface: Self::INTERFACE,
+                        }
+                            .into(),
+                    )
+                }
+            }
+        }
+    }
+    ///Borrows the implementation of `wl_callback` from its lease or the implementation itself
+    pub trait AsWlCallback {
+        fn as_wl_callback(&self) -> &crate::Callback;
+        fn as_wl_callback_mut(&mut self) -> &mut crate::Callback;
+    }
+    impl AsWlCallback for crate::Callback {
+        fn as_wl_callback(&self) -> &crate::Callback {
+            self
+        }
+        fn as_wl_callback_mut(&mut self) -> &mut crate::Callback {
+            self
+        }
+    }
+    impl AsWlCallback for ::wl::server::Lease<crate::Callback> {
+        fn as_wl_callback(&self) -> &crate::Callback {
+            self
+        }
+        fn as_wl_callback_mut(&mut self) -> &mut crate::Callback {
+            self
+        }
+    }
+    ///Introspection metadata and message encoders for the `wl_callback` interface
+    pub mod wl_callback {
+        /// A hash of the interface's wire format, changing whenever its version, or the order, version or argument
+        /// types of its messages do
+        pub const SIGNATURE_HASH: u64 = 18044897595404676376u64;
+        /// The largest `min_size` of the interface's requests and events, the size in bytes its largest message
+        /// takes on the wire with its strings and arrays empty
+        pub const LARGEST_FIXED_MESSAGE: u32 = 12u32;
+        pub const REQUESTS: &[super::MessageInfo] = &[];
+        pub const EVENTS: &[super::MessageInfo] = &[
+            super::MessageInfo {
+                name: "done",
+                opcode: 0u16,
+                since: 1u32,
+                double_buffered: false,
+                latching: false,
+                min_size: 12u32,
+                fixed_size: ::std::option::Option::Some(12u32),
+            },
+        ];
+        /// The version each request was introduced in, indexed by opcode
+        pub const REQUEST_SINCE: &[u32] = &[];
+        /// The version each event was introduced in, indexed by opcode
+        pub const EVENT_SINCE: &[u32] = &[1u32];
+        /// The version the request with the given opcode was introduced in, or 0 if there is no such request
+        pub const fn request_since(opcode: u16) -> u32 {
+            if (opcode as usize) < REQUEST_SINCE.len() {
+                REQUEST_SINCE[opcode as usize]
+            } else {
+                0
+            }
+        }
+        /// The version the event with the given opcode was introduced in, or 0 if there is no such event
+        pub const fn event_since(opcode: u16) -> u32 {
+            if (opcode as usize) < EVENT_SINCE.len() {
+                EVENT_SINCE[opcode as usize]
+            } else {
+                0
+            }
+        }
+        /// Whether the request with the given opcode sets double-buffered state
+        pub const fn is_double_buffered(opcode: u16) -> bool {
+            let _ = opcode;
+            false
+        }
+        ///Serialize the `wl_callback.done` event sent from `object`
+        pub fn encode_done(object: u32, wl_callback_data: u32) -> ::wl::Message {
+            let mut message = ::wl::Message::new(object, 0u16);
+            message.push_u32(wl_callback_data);
+            message
+        }
+    }
+    /// Constructs the implementation of an object created by a `new_id` argument
+    pub trait Construct: ::std::marker::Sized {
+        fn construct(client: &mut ::wl::server::Client, id: ::wl::NewId) -> Self;
+    }
+    impl<T: ::std::default::Default> Construct for T {
+        fn construct(_: &mut ::wl::server::Client, _: ::wl::NewId) -> Self {
+            ::std::default::Default::default()
+        }
+    }
+    /// The version an object was bound at, compared by the `try_` event methods to the version each event was
+    /// introduced in. Types other than leases sending events, such as handles, implement it to use those methods
+    pub trait BoundVersion {
+        fn bound_version(&self) -> u32;
+    }
+    /// The version to create an object of `interface` with when a client requests `requested`, the lower of the
+    /// requested version and the highest version the interface's binding supports. `None` if the interface is not
+    /// bound by this module
+    pub fn negotiate(interface: &str, requested: u32) -> ::std::option::Option<u32> {
+        match interface {
+            "wl_callback" => {
+                ::std::option::Option::Some(::std::cmp::min(requested, 1u32))
+            }
+            _ => ::std::option::Option::None,
+        }
+    }
+    /// The interface of an object received untyped, found by the type of its implementation, so that it may be
+    /// matched by name and downcast once. `None` if its type is not the implementation of any binding in this
+    /// module. A type bound to several interfaces is named as the first of them
+    pub fn interface_of(
+        lease: &::wl::server::Lease<dyn ::std::any::Any>,
+    ) -> ::std::option::Option<&'static str> {
+        static INTERFACES: &[(fn() -> ::std::any::TypeId, &str)] = &[
+            (::std::any::TypeId::of::<crate::Callback>, "wl_callback"),
+        ];
+        let type_id = ::std::any::Any::type_id(&**lease);
+        INTERFACES
+            .iter()
+            .find(|(implementation, _)| implementation() == type_id)
+            .map(|(_, interface)| *interface)
+    }
+    /// A client a broadcast event could not be sent to
+    #[derive(::std::fmt::Debug)]
+    pub struct BroadcastFailure {
+        /// The position of the client among those broadcast to
+        pub client: usize,
+        pub object: u32,
+        pub error: ::wl::DispatchError,
+    }
+    /// The clients a `broadcast_` function could not send its event to
+    #[derive(::std::fmt::Debug)]
+    pub struct BroadcastError {
+        pub event: &'static str,
+        /// The number of clients the event was sent to
+        pub sent: usize,
+        pub failures: ::std::vec::Vec<BroadcastFailure>,
+    }
+    impl ::std::fmt::Display for BroadcastError {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            ::std::write!(
+                f, "{} could not be sent to {} clients, and was sent to {}", self.event,
+                self.failures.len(), self.sent
+            )
+        }
+    }
+    impl ::std::error::Error for BroadcastError {}
+    ///Send the `wl_callback.done` event to the object chosen by `object_for` in each client, skipping clients without one. Returns the number of clients the event was sent to
+    pub fn broadcast_wl_callback_done<'a>(
+        clients: impl ::std::iter::IntoIterator<Item = &'a mut ::wl::server::Client>,
+        object_for: impl ::std::ops::Fn(
+            &::wl::server::Client,
+        ) -> ::std::option::Option<u32>,
+        wl_callback_data: u32,
+    ) -> ::std::result::Result<usize, BroadcastError> {
+        let mut sent = 0;
+        let mut failures = ::std::vec::Vec::new();
+        for (index, client) in clients.into_iter().enumerate() {
+            let object = match object_for(client) {
+                ::std::option::Option::Some(object) => object,
+                ::std::option::Option::None => continue,
+            };
+            if *::wl::DEBUG {
+                if *::wl::DEBUG_VERBOSE {
+                    ::std::eprintln!(
+                        "-> {}@{}.{}(callback_data: {})", "wl_callback", object, "done",
+                        wl_callback_data
+                    )
+                } else {
+                    ::std::eprintln!(
+                        "-> {}@{}.{}({})", "wl_callback", object, "done",
+                        wl_callback_data
+                    )
+                }
+            }
+            let message = wl_callback::encode_done(object, wl_callback_data);
+            match client.send(message) {
+                ::std::result::Result::Ok(()) => sent += 1,
+                ::std::result::Result::Err(error) => {
+                    failures
+                        .push(BroadcastFailure {
+                            client: index,
+                            object,
+                            error,
+                        })
+                }
+            }
+        }
+        if failures.is_empty() {
+            ::std::result::Result::Ok(sent)
+        } else {
+            ::std::result::Result::Err(BroadcastError {
+                event: "wl_callback.done",
+                sent,
+                failures,
+            })
+        }
+    }
+    /// Re-exports of the generated traits and enums along with the runtime types used in their signatures
+    #[allow(deprecated)]
+    pub mod prelude {
+        pub use ::wl::server::{Client, Lease, Result};
+        pub use ::wl::{Array, Fixed, NewId, Nullable, Object};
+        pub use super::{BoundVersion, Construct, EventBatch, WlCallback, AsWlCallback};
+    }
+}
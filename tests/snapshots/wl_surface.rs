@@ -0,0 +1,1108 @@
+///A surface is a rectangular area that may be displayed on zero or more outputs, and shown any number of times at the compositor's discretion. They can present wl_buffers, receive user input, and define a local coordinate system.  The size of a surface (and relative positions on it) is described in surface-local coordinates, which may differ from the buffer coordinates of the pixel content, in case a buffer_transform or a buffer_scale is used.  A surface without a "role" is fairly useless: a compositor does not know where, when or how to present it. The role is the purpose of a wl_surface. Examples of roles are a cursor for a pointer (as set by wl_pointer.set_cursor), a drag icon (wl_data_device.start_drag), a sub-surface (wl_subcompositor.get_subsurface), and a window as defined by a shell protocol (e.g. wl_shell.get_shell_surface).  A surface can have only one role at a time. Initially a wl_surface does not have a role. Once a wl_surface is given a role, it is set permanently for the whole lifetime of the wl_surface object. Giving the current role again is allowed, unless explicitly forbidden by the relevant interface specification.  Surface roles are given by requests in other interfaces such as wl_pointer.set_cursor. The request should explicitly mention that this request gives a role to a wl_surface. Often, this request also creates a new protocol object that represents the role and adds additional functionality to wl_surface. When a client wants to destroy a wl_surface, they must destroy this role object before the wl_surface, otherwise a defunct_role_object error is sent.  Destroying the role object does not remove the role from the wl_surface, but it may stop the wl_surface from "playing the role". For instance, if a wl_subsurface object is destroyed, the wl_surface it was created for will be unmapped and forget its position and z-order. It is allowed to create a wl_subsurface for the same wl_surface again, but it is not allowed to use the wl_surface as a cursor (cursor is a different role than sub-surface, and role switching is not allowed).
+/**
+# Requests
+*/
+///- [`destroy()`](Self::destroy) (opcode 0, since 1): delete surface
+///- [`attach(buffer, x, y)`](Self::attach) (opcode 1, since 1): set the surface contents
+///- [`damage(x, y, width, height)`](Self::damage) (opcode 2, since 1): mark part of the surface damaged
+///- [`frame(callback)`](Self::frame) (opcode 3, since 1): request a frame throttling hint
+///- [`set_opaque_region(region)`](Self::set_opaque_region) (opcode 4, since 1): set opaque region
+///- [`set_input_region(region)`](Self::set_input_region) (opcode 5, since 1): set input region
+///- [`commit()`](Self::commit) (opcode 6, since 1): commit pending surface state
+///- [`set_buffer_transform(transform)`](Self::set_buffer_transform) (opcode 7, since 2): sets the buffer transformation
+///- [`set_buffer_scale(scale)`](Self::set_buffer_scale) (opcode 8, since 3): sets the buffer scaling factor
+///- [`damage_buffer(x, y, width, height)`](Self::damage_buffer) (opcode 9, since 4): mark part of the surface damaged using buffer coordinates
+///- [`offset(x, y)`](Self::offset) (opcode 10, since 5): set the surface contents offset
+/**
+# Events
+*/
+///- [`enter(output)`](Self::enter) (opcode 0, since 1): surface enters an output
+///- [`leave(output)`](Self::leave) (opcode 1, since 1): surface leaves an output
+///- [`preferred_buffer_scale(factor)`](Self::preferred_buffer_scale) (opcode 2, since 6): preferred buffer scale for the surface
+///- [`preferred_buffer_transform(transform)`](Self::preferred_buffer_transform) (opcode 3, since 6): preferred buffer transform for the surface
+/**
+# Implementation
+*/
+///Implemented in this build by [`crate::Surface`](crate::Surface). Implementations are owned by the client's leases, so must be `Sized` and `'static`, owning their data rather than borrowing it.
+pub trait WlSurface: ::wl::Object {
+    const VERSION: u32 = 6u32;
+    const INTERFACE: &'static str = "wl_surface";
+    const REQUESTS: &'static [MessageInfo] = wl_surface::REQUESTS;
+    const EVENTS: &'static [MessageInfo] = wl_surface::EVENTS;
+    ///surface enters an output
+    /**
+*/
+    ///This is emitted whenever a surface's creation, movement, or resizing results in some part of it being within the scanout region of an output.  Note that a surface may be overlapping with zero or more outputs.
+    fn enter(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_output: &dyn ::wl::Object,
+    ) -> ::wl::server::Result<()> {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(output: {})", "wl_surface", self.object(), "enter",
+                    wl_output
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(), "enter", wl_output
+                )
+            }
+        }
+        let message = wl_surface::encode_enter(self.object(), wl_output);
+        client.send(message)
+    }
+    ///Serialize the `enter` event into `batch` to be sent along with other events
+    fn enter_into(&mut self, batch: &mut EventBatch, wl_output: &dyn ::wl::Object) {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(output: {})", "wl_surface", self.object(), "enter",
+                    wl_output
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(), "enter", wl_output
+                )
+            }
+        }
+        let message = wl_surface::encode_enter(self.object(), wl_output);
+        batch.push(message)
+    }
+    ///surface leaves an output
+    /**
+*/
+    ///This is emitted whenever a surface's creation, movement, or resizing results in it no longer having any part of it within the scanout region of an output.  Clients should not use the number of outputs the surface is on for frame throttling purposes. The surface might be hidden even if no leave event has been sent, and the compositor might expect new surface content updates even if no enter event has been sent. The frame event should be used instead.
+    fn leave(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_output: &dyn ::wl::Object,
+    ) -> ::wl::server::Result<()> {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(output: {})", "wl_surface", self.object(), "leave",
+                    wl_output
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(), "leave", wl_output
+                )
+            }
+        }
+        let message = wl_surface::encode_leave(self.object(), wl_output);
+        client.send(message)
+    }
+    ///Serialize the `leave` event into `batch` to be sent along with other events
+    fn leave_into(&mut self, batch: &mut EventBatch, wl_output: &dyn ::wl::Object) {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(output: {})", "wl_surface", self.object(), "leave",
+                    wl_output
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(), "leave", wl_output
+                )
+            }
+        }
+        let message = wl_surface::encode_leave(self.object(), wl_output);
+        batch.push(message)
+    }
+    ///preferred buffer scale for the surface
+    /**
+*/
+    ///This event indicates the preferred buffer scale for this surface. It is sent whenever the compositor's preference changes.  Before receiving this event the preferred buffer scale for this surface is 1.  It is intended that scaling aware clients use this event to scale their content and use wl_surface.set_buffer_scale to indicate the scale they have rendered with. This allows clients to supply a higher detail buffer.  The compositor shall emit a scale value greater than 0.
+    fn preferred_buffer_scale(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_factor: i32,
+    ) -> ::wl::server::Result<()> {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(factor: {})", "wl_surface", self.object(),
+                    "preferred_buffer_scale", wl_factor
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(),
+                    "preferred_buffer_scale", wl_factor
+                )
+            }
+        }
+        let message = wl_surface::encode_preferred_buffer_scale(
+            self.object(),
+            wl_factor,
+        );
+        client.send(message)
+    }
+    ///Serialize the `preferred_buffer_scale` event into `batch` to be sent along with other events
+    fn preferred_buffer_scale_into(&mut self, batch: &mut EventBatch, wl_factor: i32) {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(factor: {})", "wl_surface", self.object(),
+                    "preferred_buffer_scale", wl_factor
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(),
+                    "preferred_buffer_scale", wl_factor
+                )
+            }
+        }
+        let message = wl_surface::encode_preferred_buffer_scale(
+            self.object(),
+            wl_factor,
+        );
+        batch.push(message)
+    }
+    ///Send the `preferred_buffer_scale` event if the object was bound at version 6 or later, returning whether it was sent. Older clients do not know the event, so it is skipped for them
+    fn try_preferred_buffer_scale(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_factor: i32,
+    ) -> ::wl::server::Result<bool>
+    where
+        Self: BoundVersion,
+    {
+        if self.bound_version() < 6u32 {
+            return ::std::result::Result::Ok(false);
+        }
+        self.preferred_buffer_scale(client, wl_factor)?;
+        ::std::result::Result::Ok(true)
+    }
+    ///preferred buffer transform for the surface
+    /**
+*/
+    ///This event indicates the preferred buffer transform for this surface. It is sent whenever the compositor's preference changes.  Before receiving this event the preferred buffer transform for this surface is normal.  Applying this transformation to the surface buffer contents and using wl_surface.set_buffer_transform might allow the compositor to use the surface buffer more efficiently.
+    fn preferred_buffer_transform(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_transform: u32,
+    ) -> ::wl::server::Result<()> {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(transform: {})", "wl_surface", self.object(),
+                    "preferred_buffer_transform", wl_transform
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(),
+                    "preferred_buffer_transform", wl_transform
+                )
+            }
+        }
+        let message = wl_surface::encode_preferred_buffer_transform(
+            self.object(),
+            wl_transform,
+        );
+        client.send(message)
+    }
+    ///Serialize the `preferred_buffer_transform` event into `batch` to be sent along with other events
+    fn preferred_buffer_transform_into(
+        &mut self,
+        batch: &mut EventBatch,
+        wl_transform: u32,
+    ) {
+        if *::wl::DEBUG {
+            if *::wl::DEBUG_VERBOSE {
+                ::std::eprintln!(
+                    "-> {}@{}.{}(transform: {})", "wl_surface", self.object(),
+                    "preferred_buffer_transform", wl_transform
+                )
+            } else {
+                ::std::eprintln!(
+                    "-> {}@{}.{}({})", "wl_surface", self.object(),
+                    "preferred_buffer_transform", wl_transform
+                )
+            }
+        }
+        let message = wl_surface::encode_preferred_buffer_transform(
+            self.object(),
+            wl_transform,
+        );
+        batch.push(message)
+    }
+    ///Send the `preferred_buffer_transform` event if the object was bound at version 6 or later, returning whether it was sent. Older clients do not know the event, so it is skipped for them
+    fn try_preferred_buffer_transform(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_transform: u32,
+    ) -> ::wl::server::Result<bool>
+    where
+        Self: BoundVersion,
+    {
+        if self.bound_version() < 6u32 {
+            return ::std::result::Result::Ok(false);
+        }
+        self.preferred_buffer_transform(client, wl_transform)?;
+        ::std::result::Result::Ok(true)
+    }
+    ///delete surface
+    /**
+*/
+    ///Deletes the surface and invalidates its object ID.
+    fn destroy(&mut self, client: &mut ::wl::server::Client) -> ::wl::server::Result<()>;
+    ///set the surface contents
+    /**
+*/
+    ///Set a buffer as the content of this surface.  The new size of the surface is calculated based on the buffer size transformed by the inverse buffer_transform and the inverse buffer_scale. This means that at commit time the supplied buffer size must be an integer multiple of the buffer_scale. If that's not the case, an invalid_size error is sent.  The x and y arguments specify the location of the new pending buffer's upper left corner, relative to the current buffer's upper left corner, in surface-local coordinates. In other words, the x and y, combined with the new surface size define in which directions the surface's size changes. Setting anything other than 0 as x and y arguments is discouraged, and should instead be replaced with using the separate wl_surface.offset request.  When the bound wl_surface version is 5 or higher, passing any non-zero x or y is a protocol violation, and will result in an 'invalid_offset' error being raised. The x and y arguments are ignored and do not change the pending state. To achieve equivalent semantics, use wl_surface.offset.  Surface contents are double-buffered state, see wl_surface.commit.  The initial surface contents are void; there is no content. wl_surface.attach assigns the given wl_buffer as the pending wl_buffer. wl_surface.commit makes the pending wl_buffer the new surface contents, and the size of the surface becomes the size calculated from the wl_buffer, as described above. After commit, there is no pending buffer until the next attach.  Committing a pending wl_buffer allows the compositor to read the pixels in the wl_buffer. The compositor may access the pixels at any time after the wl_surface.commit request. When the compositor will not access the pixels anymore, it will send the wl_buffer.release event. Only after receiving wl_buffer.release, the client may reuse the wl_buffer. A wl_buffer that has been attached and then replaced by another attach instead of committed will not receive a release event, and is not used by the compositor.  If a pending wl_buffer has been committed to more than one wl_surface, the delivery of wl_buffer.release events becomes undefined. A well behaved client should not rely on wl_buffer.release events in this case. Alternatively, a client could create multiple wl_buffer objects from the same backing storage or use wp_linux_buffer_release.  Destroying the wl_buffer after wl_buffer.release does not change the surface contents. Destroying the wl_buffer before wl_buffer.release is allowed as long as the underlying buffer storage isn't re-used (this can happen e.g. on client process termination). However, if the client destroys the wl_buffer before receiving the wl_buffer.release event and mutates the underlying buffer storage, the surface contents become undefined immediately.  If wl_surface.attach is sent with a NULL wl_buffer, the following wl_surface.commit will remove the surface content.  If a pending wl_buffer has been destroyed, the result is not specified. Many compositors are known to remove the surface content on the following wl_surface.commit, but this behaviour is not universal. Clients seeking to maximise compatibility should not destroy pending buffers and should ensure that they explicitly remove content from surfaces, even after destroying buffers.
+    /**
+*/
+    ///`wl_buffer` is untyped because interface `wl_buffer` is not bound
+    fn attach(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_buffer: ::wl::Nullable<::wl::server::Lease<dyn ::std::any::Any>>,
+        wl_x: i32,
+        wl_y: i32,
+    ) -> ::wl::server::Result<()>;
+    ///mark part of the surface damaged
+    /**
+*/
+    ///This request is used to describe the regions where the pending buffer is different from the current surface contents, and where the surface therefore needs to be repainted. The compositor ignores the parts of the damage that fall outside of the surface.  Damage is double-buffered state, see wl_surface.commit.  The damage rectangle is specified in surface-local coordinates, where x and y specify the upper left corner of the damage rectangle.  The initial value for pending damage is empty: no damage. wl_surface.damage adds pending damage: the new pending damage is the union of old pending damage and the given rectangle.  wl_surface.commit assigns pending damage as the current damage, and clears pending damage. The server will clear the current damage as it repaints the surface.  Note! New clients should not use this request. Instead damage can be posted with wl_surface.damage_buffer which uses buffer coordinates instead of surface coordinates.
+    fn damage(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_x: i32,
+        wl_y: i32,
+        wl_width: i32,
+        wl_height: i32,
+    ) -> ::wl::server::Result<()>;
+    ///request a frame throttling hint
+    /**
+*/
+    ///Request a notification when it is a good time to start drawing a new frame, by creating a frame callback. This is useful for throttling redrawing operations, and driving animations.  When a client is animating on a wl_surface, it can use the 'frame' request to get notified when it is a good time to draw and commit the next frame of animation. If the client commits an update earlier than that, it is likely that some updates will not make it to the display, and the client is wasting resources by drawing too often.  The frame request will take effect on the next wl_surface.commit. The notification will only be posted for one frame unless requested again. For a wl_surface, the notifications are posted in the order the frame requests were committed.  The server must send the notifications so that a client will not send excessive updates, while still allowing the highest possible update rate for clients that wait for the reply before drawing again. The server should give some time for the client to draw and commit after sending the frame callback events to let it hit the next output refresh.  A server should avoid signaling the frame callbacks if the surface is not visible in any way, e.g. the surface is off-screen, or completely obscured by other opaque surfaces.  The object returned by this request will be destroyed by the compositor after the callback is fired and as such the client must not attempt to use it after that point.  The callback_data passed in the callback is the current time, in milliseconds, with an undefined base.
+    fn frame(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_callback: ::wl::NewId,
+    ) -> ::wl::server::Result<()>;
+    ///set opaque region
+    /**
+*/
+    ///This request sets the region of the surface that contains opaque content.  The opaque region is an optimization hint for the compositor that lets it optimize the redrawing of content behind opaque regions.  Setting an opaque region is not required for correct behaviour, but marking transparent content as opaque will result in repaint artifacts.  The opaque region is specified in surface-local coordinates.  The compositor ignores the parts of the opaque region that fall outside of the surface.  Opaque region is double-buffered state, see wl_surface.commit.  wl_surface.set_opaque_region changes the pending opaque region. wl_surface.commit copies the pending region to the current region. Otherwise, the pending and current regions are never changed.  The initial value for an opaque region is empty. Setting the pending opaque region has copy semantics, and the wl_region object can be destroyed immediately. A NULL wl_region causes the pending opaque region to be set to empty.
+    /**
+*/
+    ///`wl_region` is untyped because interface `wl_region` is not bound
+    fn set_opaque_region(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_region: ::wl::Nullable<::wl::server::Lease<dyn ::std::any::Any>>,
+    ) -> ::wl::server::Result<()>;
+    ///set input region
+    /**
+*/
+    ///This request sets the region of the surface that can receive pointer and touch events.  Input events happening outside of this region will try the next surface in the server surface stack. The compositor ignores the parts of the input region that fall outside of the surface.  The input region is specified in surface-local coordinates.  Input region is double-buffered state, see wl_surface.commit.  wl_surface.set_input_region changes the pending input region. wl_surface.commit copies the pending region to the current region. Otherwise the pending and current regions are never changed, except cursor and icon surfaces are special cases, see wl_pointer.set_cursor and wl_data_device.start_drag.  The initial value for an input region is infinite. That means the whole surface will accept input. Setting the pending input region has copy semantics, and the wl_region object can be destroyed immediately. A NULL wl_region causes the input region to be set to infinite.
+    /**
+*/
+    ///`wl_region` is untyped because interface `wl_region` is not bound
+    fn set_input_region(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_region: ::wl::Nullable<::wl::server::Lease<dyn ::std::any::Any>>,
+    ) -> ::wl::server::Result<()>;
+    ///commit pending surface state
+    /**
+*/
+    ///Surface state (input, opaque, and damage regions, attached buffers, etc.) is double-buffered. Protocol requests modify the pending state, as opposed to the active state in use by the compositor.  A commit request atomically creates a content update from the pending state, even if the pending state has not been touched. The content update is placed in a queue until it becomes active. After commit, the new pending state is as documented for each related request.  When the content update is applied, the wl_buffer is applied before all other state. This means that all coordinates in double-buffered state are relative to the newly attached wl_buffers, except for wl_surface.attach itself. If there is no newly attached wl_buffer, the coordinates are relative to the previous content update.  All requests that need a commit to become effective are documented to affect double-buffered state.  Other interfaces may add further double-buffered surface state.
+    fn commit(&mut self, client: &mut ::wl::server::Client) -> ::wl::server::Result<()>;
+    ///sets the buffer transformation
+    /**
+*/
+    ///This request sets the transformation that the client has already applied to the content of the buffer. The accepted values for the transform parameter are the values for wl_output.transform.  The compositor applies the inverse of this transformation whenever it uses the buffer contents.  Buffer transform is double-buffered state, see wl_surface.commit.  A newly created surface has its buffer transformation set to normal.  wl_surface.set_buffer_transform changes the pending buffer transformation. wl_surface.commit copies the pending buffer transformation to the current one. Otherwise, the pending and current values are never changed.  The purpose of this request is to allow clients to render content according to the output transform, thus permitting the compositor to use certain optimizations even if the display is rotated. Using hardware overlays and scanning out a client buffer for fullscreen surfaces are examples of such optimizations. Those optimizations are highly dependent on the compositor implementation, so the use of this request should be considered on a case-by-case basis.  Note that if the transform value includes 90 or 270 degree rotation, the width of the buffer will become the surface height and the height of the buffer will become the surface width.  If transform is not one of the values from the wl_output.transform enum the invalid_transform protocol error is raised.
+    fn set_buffer_transform(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_transform: i32,
+    ) -> ::wl::server::Result<()>;
+    ///sets the buffer scaling factor
+    /**
+*/
+    ///This request sets an optional scaling factor on how the compositor interprets the contents of the buffer attached to the window.  Buffer scale is double-buffered state, see wl_surface.commit.  A newly created surface has its buffer scale set to 1.  wl_surface.set_buffer_scale changes the pending buffer scale. wl_surface.commit copies the pending buffer scale to the current one. Otherwise, the pending and current values are never changed.  The purpose of this request is to allow clients to supply higher resolution buffer data for use on high resolution outputs. It is intended that you pick the same buffer scale as the scale of the output that the surface is displayed on. This means the compositor can avoid scaling when rendering the surface on that output.  Note that if the scale is larger than 1, then you have to attach a buffer that is larger (by a factor of scale in each dimension) than the desired surface size.  If scale is not greater than 0 the invalid_scale protocol error is raised.
+    fn set_buffer_scale(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_scale: i32,
+    ) -> ::wl::server::Result<()>;
+    ///mark part of the surface damaged using buffer coordinates
+    /**
+*/
+    ///This request is used to describe the regions where the pending buffer is different from the current surface contents, and where the surface therefore needs to be repainted. The compositor ignores the parts of the damage that fall outside of the surface.  Damage is double-buffered state, see wl_surface.commit.  The damage rectangle is specified in buffer coordinates, where x and y specify the upper left corner of the damage rectangle.  The initial value for pending damage is empty: no damage. wl_surface.damage_buffer adds pending damage: the new pending damage is the union of old pending damage and the given rectangle.  wl_surface.commit assigns pending damage as the current damage, and clears pending damage. The server will clear the current damage as it repaints the surface.  This request differs from wl_surface.damage in only one way - it takes damage in buffer coordinates instead of surface-local coordinates. While this generally is more intuitive than surface coordinates, it is especially desirable when using wp_viewport or when a drawing library (like EGL) is unaware of buffer scale and buffer transform.  Note: Because buffer transformation changes and damage requests may be interleaved in the protocol stream, it is impossible to determine the actual mapping between surface and buffer damage until wl_surface.commit time. Therefore, compositors wishing to take both kinds of damage into account will have to accumulate damage from the two requests separately and only transform from one to the other after receiving the wl_surface.commit.
+    fn damage_buffer(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_x: i32,
+        wl_y: i32,
+        wl_width: i32,
+        wl_height: i32,
+    ) -> ::wl::server::Result<()>;
+    ///set the surface contents offset
+    /**
+*/
+    ///The x and y arguments specify the location of the new pending buffer's upper left corner, relative to the current buffer's upper left corner, in surface-local coordinates. In other words, the x and y, combined with the new surface size define in which directions the surface's size changes.  Surface location offset is double-buffered state, see wl_surface.commit.  This request is semantically equivalent to and the replaces the x and y arguments in the wl_surface.attach request in wl_surface versions prior to 5. See wl_surface.attach for details.
+    fn offset(
+        &mut self,
+        client: &mut ::wl::server::Client,
+        wl_x: i32,
+        wl_y: i32,
+    ) -> ::wl::server::Result<()>;
+}
+const _: () = {
+    #[allow(dead_code)]
+    trait ImplementationMustBeSizedAndStatic: ::std::any::Any + ::std::marker::Sized {}
+    impl ImplementationMustBeSizedAndStatic for crate::Surface {}
+};
+///Dispatches requests to `wl_surface` objects to the methods of [`WlSurface`]
+#[allow(deprecated)]
+impl ::wl::server::Dispatch for crate::Surface {
+    const INTERFACE: &'static str = "wl_surface";
+    const VERSION: u32 = 6u32;
+    fn dispatch(
+        lease: ::wl::server::Lease<dyn ::std::any::Any>,
+        client: &mut ::wl::server::Client,
+        message: ::wl::Message,
+    ) -> ::wl::server::Result<()> {
+        use ::wl::Object;
+        use ::std::convert::Into;
+        let mut lease: ::wl::server::Lease<crate::Surface> = lease.downcast().unwrap();
+        match message.opcode {
+            0u16 => {
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}()", "wl_surface", lease.object(), "destroy"
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}()", "wl_surface", lease.object(), "destroy"
+                        )
+                    }
+                }
+                lease.destroy(client)
+            }
+            1u16 => {
+                let mut args = message.args();
+                let wl_buffer = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok({
+                            let id = args.next_u32()?;
+                            if id == 0 {
+                                ::wl::Nullable::Null
+                            } else {
+                                ::wl::Nullable::Object(client.get_any(id)?)
+                            }
+                        }),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed buffer>) [opcode 1]", "wl_surface",
+                                    lease.object(), "attach"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed buffer>) [opcode 1]", "wl_surface",
+                                    lease.object(), "attach"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_x = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(buffer: {}, <malformed x>) [opcode 1]",
+                                    "wl_surface", lease.object(), "attach", wl_buffer
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, <malformed x>) [opcode 1]", "wl_surface",
+                                    lease.object(), "attach", wl_buffer
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_y = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(buffer: {}, x: {}, <malformed y>) [opcode 1]",
+                                    "wl_surface", lease.object(), "attach", wl_buffer, wl_x
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, {}, <malformed y>) [opcode 1]", "wl_surface",
+                                    lease.object(), "attach", wl_buffer, wl_x
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(buffer: {}, x: {}, y: {})", "wl_surface", lease
+                            .object(), "attach", wl_buffer, wl_x, wl_y
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({}, {}, {})", "wl_surface", lease.object(),
+                            "attach", wl_buffer, wl_x, wl_y
+                        )
+                    }
+                }
+                lease.attach(client, wl_buffer, wl_x, wl_y)
+            }
+            2u16 => {
+                let mut args = message.args();
+                let wl_x = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed x>) [opcode 2]", "wl_surface", lease
+                                    .object(), "damage"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed x>) [opcode 2]", "wl_surface", lease
+                                    .object(), "damage"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_y = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, <malformed y>) [opcode 2]", "wl_surface",
+                                    lease.object(), "damage", wl_x
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, <malformed y>) [opcode 2]", "wl_surface",
+                                    lease.object(), "damage", wl_x
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_width = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, y: {}, <malformed width>) [opcode 2]",
+                                    "wl_surface", lease.object(), "damage", wl_x, wl_y
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, {}, <malformed width>) [opcode 2]",
+                                    "wl_surface", lease.object(), "damage", wl_x, wl_y
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_height = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, y: {}, width: {}, <malformed height>) [opcode 2]",
+                                    "wl_surface", lease.object(), "damage", wl_x, wl_y, wl_width
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, {}, {}, <malformed height>) [opcode 2]",
+                                    "wl_surface", lease.object(), "damage", wl_x, wl_y, wl_width
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(x: {}, y: {}, width: {}, height: {})",
+                            "wl_surface", lease.object(), "damage", wl_x, wl_y, wl_width,
+                            wl_height
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({}, {}, {}, {})", "wl_surface", lease.object(),
+                            "damage", wl_x, wl_y, wl_width, wl_height
+                        )
+                    }
+                }
+                lease.damage(client, wl_x, wl_y, wl_width, wl_height)
+            }
+            3u16 => {
+                let mut args = message.args();
+                let wl_callback = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(
+                            args
+                                .next_new_id(
+                                    "wl_callback",
+                                    <crate::Callback as ::wl::server::Dispatch>::VERSION,
+                                )?,
+                        ),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed callback>) [opcode 3]", "wl_surface",
+                                    lease.object(), "frame"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed callback>) [opcode 3]", "wl_surface",
+                                    lease.object(), "frame"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(callback: {})", "wl_surface", lease.object(),
+                            "frame", wl_callback
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({})", "wl_surface", lease.object(), "frame",
+                            wl_callback
+                        )
+                    }
+                }
+                lease.frame(client, wl_callback)
+            }
+            4u16 => {
+                let mut args = message.args();
+                let wl_region = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok({
+                            let id = args.next_u32()?;
+                            if id == 0 {
+                                ::wl::Nullable::Null
+                            } else {
+                                ::wl::Nullable::Object(client.get_any(id)?)
+                            }
+                        }),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed region>) [opcode 4]", "wl_surface",
+                                    lease.object(), "set_opaque_region"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed region>) [opcode 4]", "wl_surface",
+                                    lease.object(), "set_opaque_region"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(region: {})", "wl_surface", lease.object(),
+                            "set_opaque_region", wl_region
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({})", "wl_surface", lease.object(),
+                            "set_opaque_region", wl_region
+                        )
+                    }
+                }
+                lease.set_opaque_region(client, wl_region)
+            }
+            5u16 => {
+                let mut args = message.args();
+                let wl_region = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok({
+                            let id = args.next_u32()?;
+                            if id == 0 {
+                                ::wl::Nullable::Null
+                            } else {
+                                ::wl::Nullable::Object(client.get_any(id)?)
+                            }
+                        }),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed region>) [opcode 5]", "wl_surface",
+                                    lease.object(), "set_input_region"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed region>) [opcode 5]", "wl_surface",
+                                    lease.object(), "set_input_region"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(region: {})", "wl_surface", lease.object(),
+                            "set_input_region", wl_region
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({})", "wl_surface", lease.object(),
+                            "set_input_region", wl_region
+                        )
+                    }
+                }
+                lease.set_input_region(client, wl_region)
+            }
+            6u16 => {
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}()", "wl_surface", lease.object(), "commit"
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}()", "wl_surface", lease.object(), "commit"
+                        )
+                    }
+                }
+                lease.commit(client)
+            }
+            7u16 => {
+                let mut args = message.args();
+                let wl_transform = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed transform>) [opcode 7]", "wl_surface",
+                                    lease.object(), "set_buffer_transform"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed transform>) [opcode 7]", "wl_surface",
+                                    lease.object(), "set_buffer_transform"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(transform: {})", "wl_surface", lease.object(),
+                            "set_buffer_transform", wl_transform
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({})", "wl_surface", lease.object(),
+                            "set_buffer_transform", wl_transform
+                        )
+                    }
+                }
+                lease.set_buffer_transform(client, wl_transform)
+            }
+            8u16 => {
+                let mut args = message.args();
+                let wl_scale = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed scale>) [opcode 8]", "wl_surface",
+                                    lease.object(), "set_buffer_scale"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed scale>) [opcode 8]", "wl_surface",
+                                    lease.object(), "set_buffer_scale"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(scale: {})", "wl_surface", lease.object(),
+                            "set_buffer_scale", wl_scale
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({})", "wl_surface", lease.object(),
+                            "set_buffer_scale", wl_scale
+                        )
+                    }
+                }
+                lease.set_buffer_scale(client, wl_scale)
+            }
+            9u16 => {
+                let mut args = message.args();
+                let wl_x = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed x>) [opcode 9]", "wl_surface", lease
+                                    .object(), "damage_buffer"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed x>) [opcode 9]", "wl_surface", lease
+                                    .object(), "damage_buffer"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_y = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, <malformed y>) [opcode 9]", "wl_surface",
+                                    lease.object(), "damage_buffer", wl_x
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, <malformed y>) [opcode 9]", "wl_surface",
+                                    lease.object(), "damage_buffer", wl_x
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_width = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, y: {}, <malformed width>) [opcode 9]",
+                                    "wl_surface", lease.object(), "damage_buffer", wl_x, wl_y
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, {}, <malformed width>) [opcode 9]",
+                                    "wl_surface", lease.object(), "damage_buffer", wl_x, wl_y
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_height = match (|| -> ::wl::server::Result<
+                    ::wl::server::Result<_>,
+                > {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, y: {}, width: {}, <malformed height>) [opcode 9]",
+                                    "wl_surface", lease.object(), "damage_buffer", wl_x, wl_y,
+                                    wl_width
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, {}, {}, <malformed height>) [opcode 9]",
+                                    "wl_surface", lease.object(), "damage_buffer", wl_x, wl_y,
+                                    wl_width
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(x: {}, y: {}, width: {}, height: {})",
+                            "wl_surface", lease.object(), "damage_buffer", wl_x, wl_y,
+                            wl_width, wl_height
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({}, {}, {}, {})", "wl_surface", lease.object(),
+                            "damage_buffer", wl_x, wl_y, wl_width, wl_height
+                        )
+                    }
+                }
+                lease.damage_buffer(client, wl_x, wl_y, wl_width, wl_height)
+            }
+            10u16 => {
+                let mut args = message.args();
+                let wl_x = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed x>) [opcode 10]", "wl_surface", lease
+                                    .object(), "offset"
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(<malformed x>) [opcode 10]", "wl_surface", lease
+                                    .object(), "offset"
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                let wl_y = match (|| -> ::wl::server::Result<::wl::server::Result<_>> {
+                    ::std::result::Result::Ok(
+                        ::std::result::Result::Ok(args.next_i32()?),
+                    )
+                })() {
+                    ::std::result::Result::Ok(::std::result::Result::Ok(arg)) => arg,
+                    ::std::result::Result::Ok(::std::result::Result::Err(rejection)) => {
+                        return ::std::result::Result::Err(rejection);
+                    }
+                    ::std::result::Result::Err(error) => {
+                        if *::wl::DEBUG {
+                            if *::wl::DEBUG_VERBOSE {
+                                ::std::eprintln!(
+                                    "{}@{}.{}(x: {}, <malformed y>) [opcode 10]", "wl_surface",
+                                    lease.object(), "offset", wl_x
+                                )
+                            } else {
+                                ::std::eprintln!(
+                                    "{}@{}.{}({}, <malformed y>) [opcode 10]", "wl_surface",
+                                    lease.object(), "offset", wl_x
+                                )
+                            }
+                        }
+                        return ::std::result::Result::Err(error);
+                    }
+                };
+                if *::wl::DEBUG {
+                    if *::wl::DEBUG_VERBOSE {
+                        ::std::eprintln!(
+                            "{}@{}.{}(x: {}, y: {})", "wl_surface", lease.object(),
+                            "offset", wl_x, wl_y
+                        )
+                    } else {
+                        ::std::eprintln!(
+                            "{}@{}.{}({}, {})", "wl_surface", lease.object(), "offset",
+                            wl_x, wl_y
+                        )
+                    }
+                }
+                lease.offset(client, wl_x, wl_y)
+            }
+            _ => {
+                ::std::result::Result::Err(
+                    ::wl::DispatchError::InvalidRequest {
+                        opcode: message.opcode,
+                        object: lease.object(),
+                        interface: Self::INTERFACE,
+                    }
+                        .into(),
+                )
+            }
+        }
+    }
+}
+///Borrows the implementation of `wl_surface` from its lease or the implementation itself
+pub trait AsWlSurface {
+    fn as_wl_surface(&self) -> &crate::Surface;
+    fn as_wl_surface_mut(&mut self) -> &mut crate::Surface;
+}
+impl AsWlSurface for crate::Surface {
+    fn as_wl_surface(&self) -> &crate::Surface {
+        self
+    }
+    fn as_wl_surface_mut(&mut self) -> &mut crate::Surface {
+        self
+    }
+}
+impl AsWlSurface for ::wl::server::Lease<crate::Surface> {
+    fn as_wl_surface(&self) -> &crate::Surface {
+        self
+    }
+    fn as_wl_surface_mut(&mut self) -> &mut crate::Surface {
+        self
+    }
+}
@@ -0,0 +1,119 @@
+// A stand-in for the runtime that generated code targets, enough for expansions to compile beyond the diagnostics under test
+#![allow(dead_code, unused)]
+use std::{any::Any, fmt, fs::File, marker::PhantomData, ops::{Deref, DerefMut}};
+
+pub struct Debug;
+impl Deref for Debug { type Target = bool; fn deref(&self) -> &bool { if std::env::var_os("WAYLAND_DEBUG").is_some() { &true } else { &false } } }
+pub static DEBUG: Debug = Debug;
+pub struct DebugWire;
+impl Deref for DebugWire { type Target = bool; fn deref(&self) -> &bool { if std::env::var_os("WAYLAND_DEBUG_WIRE").is_some() { &true } else { &false } } }
+pub static DEBUG_WIRE: DebugWire = DebugWire;
+pub struct DebugVerbose;
+impl Deref for DebugVerbose { type Target = bool; fn deref(&self) -> &bool { if std::env::var_os("WAYLAND_DEBUG_VERBOSE").is_some() { &true } else { &false } } }
+pub static DEBUG_VERBOSE: DebugVerbose = DebugVerbose;
+
+pub trait Object { fn object(&self) -> u32; }
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Fixed(pub i32);
+impl From<Fixed> for f64 { fn from(fixed: Fixed) -> f64 { fixed.0 as f64 / 256.0 } }
+impl fmt::Display for Fixed { fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.0 as f64 / 256.0) } }
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Array(pub Vec<u8>);
+impl fmt::Display for Array { fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{:?}", self.0) } }
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewId { pub id: u32, pub version: u32, pub interface: &'static str }
+impl fmt::Display for NewId { fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "new id {}", self.id) } }
+pub enum Nullable<T> { Null, Object(T) }
+impl<T: fmt::Display> fmt::Display for Nullable<T> { fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { match self { Nullable::Null => write!(f, "null"), Nullable::Object(o) => o.fmt(f) } } }
+
+#[derive(Debug)]
+pub enum DispatchError {
+    InvalidRequest { opcode: u16, object: u32, interface: &'static str },
+    NoVariant { name: &'static str, variant: u32 },
+    TrailingData { opcode: u16, object: u32, interface: &'static str },
+    InvalidSince { opcode: u16, object: u32, interface: &'static str, version: u32 },
+    DuplicateObject { request: &'static str, object: u32 },
+    ArgumentTooLong { request: &'static str, argument: &'static str, limit: u32 },
+    ArgumentOutOfRange { request: &'static str, argument: &'static str, value: f64, min: f64, max: f64 },
+    MalformedString { request: &'static str, argument: &'static str, error: Box<DispatchError> },
+    InteriorNul { event: &'static str, argument: &'static str },
+    AccessDenied { request: &'static str, object: u32 },
+    Disconnected,
+    Protocol { name: &'static str, code: u32, message: String },
+    Other(String),
+}
+
+pub struct Message { pub object: u32, pub opcode: u16, pub data: Vec<u32> }
+impl Message {
+    pub fn new(object: u32, opcode: u16) -> Self { Self { object, opcode, data: Vec::new() } }
+    pub fn to_bytes(&self) -> Vec<u8> { let mut bytes = self.object.to_ne_bytes().to_vec(); bytes.extend_from_slice(&((((8 + 4 * self.data.len()) as u32) << 16) | self.opcode as u32).to_ne_bytes()); for word in &self.data { bytes.extend_from_slice(&word.to_ne_bytes()) } bytes }
+    pub fn size(&self) -> usize { 8 + 4 * self.data.len() }
+    pub fn args(&self) -> Args<'_> { Args { message: self, index: 0 } }
+    pub fn push_i32(&mut self, v: i32) { self.data.push(v as u32) }
+    pub fn push_u32(&mut self, v: u32) { self.data.push(v) }
+    pub fn push_fixed(&mut self, v: Fixed) { self.data.push(v.0 as u32) }
+    fn push_bytes(&mut self, v: &[u8]) { self.data.push(v.len() as u32); for chunk in v.chunks(4) { let mut w = [0u8; 4]; w[..chunk.len()].copy_from_slice(chunk); self.data.push(u32::from_ne_bytes(w)) } }
+    pub fn push_str(&mut self, v: &str) { self.push_bytes(v.as_bytes()) }
+    pub fn push_array(&mut self, v: Array) { self.push_bytes(&v.0) }
+    pub fn push_file(&mut self, v: &File) {}
+    pub fn push_new_id(&mut self, v: NewId) { self.data.push(v.id) }
+    pub fn push_dynamic_new_id(&mut self, v: NewId) { self.data.push(v.id) }
+}
+pub struct Args<'a> { message: &'a Message, index: usize }
+impl<'a> Args<'a> {
+    pub fn is_empty(&self) -> bool { self.index >= self.message.data.len() }
+    fn next(&mut self) -> server::Result<u32> { let v = self.message.data.get(self.index).copied().ok_or(DispatchError::Other("exhausted".into()))?; self.index += 1; Ok(v) }
+    pub fn peek_u32(&self) -> server::Result<u32> { self.message.data.get(self.index).copied().ok_or(DispatchError::Other("exhausted".into())) }
+    pub fn next_i32(&mut self) -> server::Result<i32> { self.next().map(|v| v as i32) }
+    pub fn next_u32(&mut self) -> server::Result<u32> { self.next() }
+    pub fn next_fixed(&mut self) -> server::Result<Fixed> { self.next().map(|v| Fixed(v as i32)) }
+    fn next_bytes(&mut self) -> server::Result<Vec<u8>> { let len = self.next()? as usize; let mut bytes = Vec::new(); for _ in 0..(len + 3) / 4 { bytes.extend_from_slice(&self.next()?.to_ne_bytes()) } bytes.truncate(len); Ok(bytes) }
+    pub fn next_str(&mut self) -> server::Result<String> { String::from_utf8(self.next_bytes()?).map_err(|_| DispatchError::Other("utf8".into())) }
+    pub fn next_array(&mut self) -> server::Result<Array> { self.next_bytes().map(Array) }
+    pub fn next_array_ref(&mut self) -> server::Result<&'a [u8]> { let len = self.next()? as usize; let words = self.message.data.get(self.index..self.index + (len + 3) / 4).ok_or(DispatchError::Other("exhausted".into()))?; self.index += words.len(); let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, words.len() * 4) }; Ok(&bytes[..len]) }
+    pub fn next_new_id(&mut self, interface: &'static str, version: u32) -> server::Result<NewId> { self.next().map(|id| NewId { id, version, interface }) }
+    pub fn next_dynamic_new_id(&mut self) -> server::Result<NewId> { self.next().map(|id| NewId { id, version: 1, interface: "" }) }
+}
+
+pub mod server {
+    use super::*;
+    pub type Result<T> = std::result::Result<T, DispatchError>;
+    pub trait Dispatch {
+        const INTERFACE: &'static str;
+        const VERSION: u32;
+        fn dispatch(lease: Lease<dyn Any>, client: &mut Client, message: Message) -> Result<()>;
+    }
+    pub struct Lease<T: ?Sized> { pub object: u32, pub version: u32, pub value: Box<T> }
+    impl<T: ?Sized> Lease<T> { pub fn version(&self) -> u32 { self.version } }
+    impl<T: ?Sized> Object for Lease<T> { fn object(&self) -> u32 { self.object } }
+    impl Lease<dyn Any> {
+        pub fn downcast<T: Any>(self) -> Option<Lease<T>> { let (object, version) = (self.object, self.version); self.value.downcast().ok().map(|value| Lease { object, version, value }) }
+    }
+    impl<T: ?Sized> Deref for Lease<T> { type Target = T; fn deref(&self) -> &T { &self.value } }
+    impl<T: ?Sized> DerefMut for Lease<T> { fn deref_mut(&mut self) -> &mut T { &mut self.value } }
+    impl<T: ?Sized> fmt::Display for Lease<T> { fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.object) } }
+    #[derive(Default)]
+    pub struct Client { pub sent: Vec<Message>, pub writes: usize, pub flushes: usize, pub hung_up: bool, pub dead: bool, pub objects: std::collections::HashMap<u32, Box<dyn Any>>, pub versions: std::collections::HashMap<u32, u32>, pub destroyed: std::collections::HashSet<u32>, pub data: std::collections::HashMap<std::any::TypeId, Box<dyn Any>> }
+    impl Client {
+        pub fn insert_data<T: Any>(&mut self, value: T) { self.data.insert(std::any::TypeId::of::<T>(), Box::new(value)); }
+        pub fn take_data<T: Any>(&mut self) -> Result<Box<T>> { self.data.remove(&std::any::TypeId::of::<T>()).ok_or(DispatchError::Other("no data".into()))?.downcast().map_err(|_| DispatchError::Other("wrong data".into())) }
+        pub fn put_data<T: Any>(&mut self, value: Box<T>) { self.data.insert(std::any::TypeId::of::<T>(), value); }
+        pub fn insert<T: Any>(&mut self, id: u32, value: T) { self.destroyed.remove(&id); self.objects.insert(id, Box::new(value)); }
+        pub fn create<T: Any>(&mut self, id: NewId, value: T) -> Result<Lease<T>> { if self.objects.contains_key(&id.id) { return Err(DispatchError::Other("id in use".into())) } self.destroyed.remove(&id.id); self.versions.insert(id.id, id.version); Ok(Lease { object: id.id, version: id.version, value: Box::new(value) }) }
+        pub fn mark_destroyed(&mut self, id: u32) { self.destroyed.insert(id); }
+        pub fn is_destroyed(&self, id: u32) -> bool { self.destroyed.contains(&id) }
+        pub fn next_file(&mut self) -> Result<File> { Err(DispatchError::Other("no fd".into())) }
+        pub fn get<T: Any>(&mut self, id: u32) -> Result<Lease<T>> { self.get_any(id)?.downcast().ok_or(DispatchError::Other("wrong type".into())) }
+        pub fn get_any(&mut self, id: u32) -> Result<Lease<dyn Any>> { let value = self.objects.remove(&id).ok_or(DispatchError::Other("no object".into()))?; Ok(Lease { object: id, version: self.versions.get(&id).copied().unwrap_or(1), value }) }
+        pub fn is_disconnected(&self) -> bool { self.hung_up }
+        pub fn mark_dead(&mut self) { self.dead = true }
+        pub fn is_dead(&self) -> bool { self.dead }
+        pub fn send(&mut self, message: Message) -> Result<()> { if self.hung_up { return Err(DispatchError::Other("broken pipe".into())) } self.writes += 1; self.sent.push(message); Ok(()) }
+        pub fn flush(&mut self) -> Result<()> { self.flushes += 1; Ok(()) }
+        pub fn send_all(&mut self, messages: Vec<Message>) -> Result<()> { if self.hung_up { return Err(DispatchError::Other("broken pipe".into())) } self.writes += 1; self.sent.extend(messages); Ok(()) }
+    }
+}
+impl fmt::Display for dyn Object + '_ { fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.object()) } }
+impl<T: Object> Object for Nullable<T> { fn object(&self) -> u32 { match self { Nullable::Null => 0, Nullable::Object(o) => o.object() } } }
+impl<T: Object + ?Sized> Object for &T { fn object(&self) -> u32 { (**self).object() } }
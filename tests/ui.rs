@@ -0,0 +1,7 @@
+// The cases are compiled from `target/tests/trybuild/wl-macro`, so they name the vendored protocol relative to it
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+    cases.pass("tests/pass/*.rs");
+}
@@ -0,0 +1,14 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::Lease;
+
+pub struct Callback<'a>(&'a str);
+impl wayland::WlCallback for Lease<Callback<'_>> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlCallback = crate::Callback<'_>;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlCallback = crate::Callback,
+    type WlShm = crate::Shm,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{NewId, server::{Client, Lease}};
+
+#[derive(Default)]
+pub struct Callback;
+impl wayland::WlCallback for Lease<Callback> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![runtime(create)]
+    type WlCallback = crate::Callback;
+}
+
+/// Not bound by the module
+#[derive(Default)]
+pub struct Output;
+
+fn main() {
+    let mut client = Client::default();
+    let id = NewId { id: 2, version: 1, interface: "wl_output" };
+    let _ = wayland::create(&mut client, id, Output);
+}
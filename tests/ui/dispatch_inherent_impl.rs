@@ -0,0 +1,10 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+pub struct Callback;
+#[wl_macro::dispatch("../../../../tests/wayland.toml")]
+impl Callback {
+    pub fn done(&self) {}
+}
+
+fn main() {}
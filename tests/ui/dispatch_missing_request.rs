@@ -0,0 +1,21 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+
+#[derive(Default)]
+pub struct Region;
+#[wl_macro::dispatch("../../../../tests/wayland.toml")]
+impl wayland::WlRegion for Lease<Region> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    type WlRegion = crate::Region;
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+
+#[derive(Default)]
+pub struct Region;
+#[wl_macro::dispatch("../../../../tests/wayland.toml")]
+impl wayland::WlRegionRequests for Lease<Region> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+    fn add(&mut self, _: &mut Client, _: i32, _: i32, _: i32, _: i32) -> Result<()> {
+        Ok(())
+    }
+    fn enter(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+impl wayland::WlRegionEvents for Lease<Region> {}
+
+#[derive(Default)]
+pub struct Callback;
+impl wayland::WlCallbackRequests for Lease<Callback> {}
+#[wl_macro::dispatch("../../../../tests/wayland.toml")]
+impl wayland::WlCallbackEvents for Lease<Callback> {
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    #![split_traits]
+    type WlRegion = crate::Region;
+    type WlCallback = crate::Callback;
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::{Client, Lease, Result};
+
+#[derive(Default)]
+pub struct Callback;
+#[wl_macro::dispatch("../../../../tests/wayland.toml")]
+impl wayland::WlCallback for Lease<Callback> {
+    fn finish(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    #![loose_objects]
+    type WlCallback = crate::Callback;
+}
+
+fn main() {}
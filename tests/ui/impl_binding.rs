@@ -0,0 +1,14 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    impl crate::Registry for WlRegistry;
+    impl WlCallback for Callback {}
+    impl WlOutput for crate::outputs::Output {}
+    impl crate::Seat for crate::seats::Seat;
+    // Implementations naming no interface are ordinary items
+    impl Default for Cursor {}
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{NewId, server::{Client, Lease, Result}};
+
+pub struct Registry;
+impl wayland::WlRegistry for Lease<Registry> {
+    fn bind(&mut self, _: &mut Client, _: u32, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+// Missing the client parameter
+pub fn make_registry(_: NewId) -> Registry {
+    Registry
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlRegistry = crate::Registry (init<crate::make_registry>);
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::{NewId, server::{Client, Lease, Result}};
+
+pub struct Compositor;
+impl wayland::WlCompositor for Lease<Compositor> {
+    fn create_surface(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+    fn create_region(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlCompositor = crate::Compositor;
+}
+
+fn main() {}
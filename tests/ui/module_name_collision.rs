@@ -0,0 +1,12 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+#[derive(Default)]
+pub struct Callback;
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wl_callback {
+    type WlCallback = crate::Callback;
+}
+
+fn main() {}
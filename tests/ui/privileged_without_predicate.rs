@@ -0,0 +1,34 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use std::fs::File;
+use runtime::{NewId, server::{Client, Lease, Result}};
+
+#[derive(Default)]
+pub struct Manager;
+impl capture::ExtCaptureManager for Lease<Manager> {
+    fn capture(&mut self, _: &mut Client, _: NewId) -> Result<()> {
+        Ok(())
+    }
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+#[derive(Default)]
+pub struct Capture;
+impl capture::ExtCapture for Lease<Capture> {
+    fn copy(&mut self, _: &mut Client, _: File) -> Result<()> {
+        Ok(())
+    }
+    fn destroy(&mut self, _: &mut Client) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[wl_macro::server_protocol("../../../../tests/privileged.toml", crate = crate::runtime)]
+mod capture {
+    type ExtCaptureManager = crate::Manager;
+    type ExtCapture = crate::Capture;
+}
+
+fn main() {}
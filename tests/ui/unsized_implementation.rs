@@ -0,0 +1,13 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+use runtime::server::Lease;
+
+impl wayland::WlCallback for Lease<str> {}
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlCallback = str;
+}
+
+fn main() {}
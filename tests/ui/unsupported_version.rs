@@ -0,0 +1,9 @@
+#[path = "../support/runtime.rs"]
+mod runtime;
+
+#[wl_macro::server_protocol("../../../../tests/wayland.toml", crate = crate::runtime)]
+mod wayland {
+    type WlCallback = crate::Callback (version<2>);
+}
+
+fn main() {}